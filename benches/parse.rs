@@ -0,0 +1,79 @@
+//! Benchmarks of parsing os-release files.
+//!
+//! Run with `cargo bench --bench parse`.
+//! Besides the time per iteration, the number of heap allocations per iteration is reported,
+//! as it does not depend on the machine.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    io::{BufRead, BufReader},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use etc_os_release::{OsRelease, SPEC_EXAMPLE};
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+// SAFETY: all operations are delegated to `System`.
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn bench(name: &str, mut f: impl FnMut() -> OsRelease) {
+    // `cargo test --benches` runs this without `--bench`, just to check that it works.
+    let iterations = if std::env::args().any(|arg| arg == "--bench") {
+        100_000
+    } else {
+        1
+    };
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        black_box(f());
+    }
+    let elapsed = start.elapsed() / iterations;
+    println!("{name:<24} {elapsed:>10.2?}/iter {allocations:>4} allocations/iter");
+}
+
+fn main() {
+    let input = SPEC_EXAMPLE.as_bytes();
+
+    bench("from_reader", || {
+        OsRelease::from_reader(black_box(input)).unwrap()
+    });
+    // The previous implementation of `from_reader()`, allocating a `String` for each line.
+    bench("from_reader (per line)", || {
+        BufReader::new(black_box(input))
+            .lines()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+            .into_iter()
+            .collect()
+    });
+    bench("from_bytes", || {
+        OsRelease::from_bytes(black_box(input)).unwrap()
+    });
+}
@@ -0,0 +1,76 @@
+use std::{env, fs, path::PathBuf};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
+
+use crate::syntax::{parse_assignment, validate_line, LineErrorKind};
+
+/// Parses the os-release file at `path` and expands to an expression building the `OsRelease`.
+///
+/// The file is checked at compile time with the same parser as `OsReleaseLine::parse_strict()`,
+/// but the `OsRelease` is built at run time from the parsed entries, each time the expression is evaluated.
+pub(crate) fn expand(path: &LitStr) -> syn::Result<TokenStream> {
+    let manifest_dir = env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let full_path = PathBuf::from(manifest_dir).join(path.value());
+    let error = |msg: String| syn::Error::new(path.span(), msg);
+
+    let input = fs::read_to_string(&full_path)
+        .map_err(|e| error(format!("failed to read {}: {e}", full_path.display())))?;
+    let entries = input
+        .strip_prefix('\u{feff}')
+        .unwrap_or(&input)
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| parse_line(line).map_err(|e| (i, e)).transpose())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|(i, e)| error(format!("{}:{}: {e}", full_path.display(), i + 1)))?;
+
+    let full_path = full_path.to_string_lossy();
+    let entries = entries.iter().map(|(key, value)| {
+        quote! { ::etc_os_release::OsReleaseEntry::new(#key, #value) }
+    });
+    Ok(quote! {{
+        // Rebuild when the file changes.
+        const _: &[u8] = ::std::include_bytes!(#full_path);
+        <::etc_os_release::OsRelease as ::std::iter::FromIterator<_>>::from_iter([#(#entries),*])
+    }})
+}
+
+/// Parses a line strictly, as `OsReleaseLine::parse_strict()` does.
+///
+/// Returns `None` if the line is empty or a comment.
+fn parse_line(line: &str) -> Result<Option<(&str, String)>, LineErrorKind> {
+    validate_line(line)?;
+    Ok(parse_assignment(line).map(|(key, value)| (key, value.into_owned())))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_line() {
+        let entry = |key, value: &str| Ok(Some((key, value.to_owned())));
+        assert_eq!(parse_line(""), Ok(None));
+        assert_eq!(parse_line("  # comment"), Ok(None));
+        assert_eq!(parse_line("ID=fedora"), entry("ID", "fedora"));
+        assert_eq!(
+            parse_line(r#"NAME="Fedora \"Linux\"""#),
+            entry("NAME", r#"Fedora "Linux""#)
+        );
+        assert_eq!(parse_line(r"NAME='a\b'  "), entry("NAME", r"a\b"));
+        assert_eq!(parse_line(r"NAME=a\ b"), entry("NAME", "a b"));
+
+        assert!(parse_line("ID").is_err());
+        assert!(parse_line("1D=fedora").is_err());
+        assert!(parse_line("NAME=Fedora Linux").is_err());
+        assert!(parse_line(r#"NAME="Fedora"#).is_err());
+        assert!(parse_line(r#"NAME="$HOME""#).is_err());
+        assert!(parse_line(r#"NAME="a"b"#).is_err());
+        assert_eq!(
+            parse_line("ID=fedora # comment"),
+            Err(LineErrorKind::TrailingComment)
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Procedural macros for the [`etc-os-release`] crate.
+//!
+//! Use the macros re-exported by [`etc-os-release`] with the `derive` feature, instead of this crate directly.
+//!
+//! [`etc-os-release`]: https://docs.rs/etc-os-release
+
+#![warn(missing_docs, unreachable_pub)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+mod include;
+// The parser of the `etc-os-release` crate, so that `include_os_release!` accepts the same files.
+#[path = "../../src/syntax.rs"]
+#[allow(dead_code, unreachable_pub)]
+mod syntax;
+
+/// Derives `FromOsRelease` for a struct with named fields.
+///
+/// See the documentation of `etc_os_release::FromOsRelease` for details.
+///
+/// Tuple structs, unit structs, enums and unions are rejected:
+///
+/// ```compile_fail
+/// use etc_os_release::FromOsRelease;
+///
+/// #[derive(FromOsRelease)]
+/// struct MyOs(String);
+/// ```
+#[proc_macro_derive(FromOsRelease, attributes(os_release))]
+pub fn derive_from_os_release(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Parses an os-release file at compile time.
+#[proc_macro]
+pub fn include_os_release(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    include::expand(&path)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "`FromOsRelease` can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "`FromOsRelease` can only be derived for structs",
+            ))
+        }
+    };
+
+    let inits = fields
+        .iter()
+        .map(|field| {
+            let ident = field.ident.as_ref().unwrap();
+            let ty = &field.ty;
+            let key = match rename(&field.attrs)? {
+                Some(key) => key,
+                None => ident.to_string().trim_start_matches("r#").to_uppercase(),
+            };
+            Ok(quote! {
+                #ident: <#ty as ::etc_os_release::FromOsReleaseField>::from_field(
+                    #key,
+                    os_release.get_value(#key),
+                )?
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::etc_os_release::FromOsRelease for #name #ty_generics #where_clause {
+            fn from_os_release(
+                os_release: &::etc_os_release::OsRelease,
+            ) -> ::std::result::Result<Self, ::etc_os_release::FromOsReleaseError> {
+                ::std::result::Result::Ok(Self {
+                    #(#inits,)*
+                })
+            }
+        }
+    })
+}
+
+/// Returns the key given by `#[os_release(rename = "KEY")]`, if any.
+fn rename(attrs: &[syn::Attribute]) -> syn::Result<Option<String>> {
+    let mut key = None;
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("os_release"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                key = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else {
+                Err(meta.error("unsupported `os_release` attribute"))
+            }
+        })?;
+    }
+    Ok(key)
+}
@@ -0,0 +1,41 @@
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use etc_os_release::{FromOsRelease, FromOsReleaseError, OsRelease};
+
+#[derive(Debug, PartialEq, FromOsRelease)]
+struct MyOs {
+    id: String,
+    #[os_release(rename = "SUPPORT_END")]
+    end_of_life: Option<NaiveDate>,
+    r#type: Option<String>,
+}
+
+#[test]
+fn test_derive() {
+    let os_release = OsRelease::from_str("ID=fedora\nSUPPORT_END=2025-05-13\nTYPE=x\n").unwrap();
+    assert_eq!(
+        MyOs::from_os_release(&os_release).unwrap(),
+        MyOs {
+            id: "fedora".into(),
+            end_of_life: NaiveDate::from_ymd_opt(2025, 5, 13),
+            r#type: Some("x".into()),
+        }
+    );
+
+    let os_release = OsRelease::from_str("ID=fedora\n").unwrap();
+    let my_os = MyOs::from_os_release(&os_release).unwrap();
+    assert_eq!(my_os.end_of_life, None);
+
+    let os_release = OsRelease::from_str("ID=fedora\nSUPPORT_END=soon\n").unwrap();
+    assert!(matches!(
+        MyOs::from_os_release(&os_release),
+        Err(FromOsReleaseError::Invalid { key, .. }) if key == "SUPPORT_END"
+    ));
+
+    let os_release = OsRelease::from_str("SUPPORT_END=2025-05-13\n").unwrap();
+    assert!(matches!(
+        MyOs::from_os_release(&os_release),
+        Err(FromOsReleaseError::Missing(key)) if key == "ID"
+    ));
+}
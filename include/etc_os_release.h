@@ -0,0 +1,114 @@
+/*
+ * C API of the etc-os-release crate.
+ *
+ * Build the crate with `cargo rustc --release --features ffi --crate-type cdylib`
+ * (or `staticlib`) and link against the resulting library.
+ */
+
+#ifndef ETC_OS_RELEASE_H
+#define ETC_OS_RELEASE_H
+
+/* Generated from src/ffi.rs by cbindgen. Do not edit by hand. */
+
+/*
+ * The function succeeded.
+ */
+#define OS_RELEASE_OK 0
+
+/*
+ * No os-release file is found.
+ */
+#define OS_RELEASE_ERR_NOT_FOUND 1
+
+/*
+ * The os-release file cannot be opened or read.
+ */
+#define OS_RELEASE_ERR_IO 2
+
+/*
+ * The os-release file is malformed.
+ */
+#define OS_RELEASE_ERR_MALFORMED 3
+
+/*
+ * An argument is a null pointer or not valid UTF-8.
+ */
+#define OS_RELEASE_ERR_INVALID_ARGUMENT 4
+
+/*
+ * An opaque handle to a parsed os-release file.
+ */
+typedef struct OsReleaseHandle OsReleaseHandle;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ * Open the os-release file of the running system and parse it, as [`OsRelease::open()`].
+ *
+ * On success, stores the handle in `*out` and returns [`OS_RELEASE_OK`].
+ * The handle must be released with [`os_release_free()`].
+ * On failure, stores a null pointer in `*out` and returns an error code.
+ *
+ * # Safety
+ *
+ * `out` must be a valid pointer to write to.
+ */
+int os_release_open(OsReleaseHandle **out);
+
+/*
+ * Open the os-release file of the operating system tree at `root` and parse it,
+ * as [`OsRelease::open_root()`].
+ *
+ * The result is returned in the same way as [`os_release_open()`].
+ *
+ * # Safety
+ *
+ * `root` must be a valid pointer to a NUL-terminated string, and `out` must be a valid pointer to write to.
+ */
+int os_release_open_root(const char *root, OsReleaseHandle **out);
+
+/*
+ * Parse a string containing the contents of the os-release file.
+ *
+ * The result is returned in the same way as [`os_release_open()`].
+ *
+ * # Safety
+ *
+ * `s` must be a valid pointer to a NUL-terminated string, and `out` must be a valid pointer to write to.
+ */
+int os_release_parse(const char *s, OsReleaseHandle **out);
+
+/*
+ * Returns the value of the field `key`, or a null pointer if it is not set.
+ *
+ * The returned string is valid until the handle is released. Default values of fields are not applied.
+ * If the value contains a NUL character, the returned string ends before it.
+ *
+ * # Safety
+ *
+ * `os_release` must be a handle returned by this library and not yet released,
+ * and `key` must be a valid pointer to a NUL-terminated string.
+ */
+const char *os_release_get(const OsReleaseHandle *os_release, const char *key);
+
+/*
+ * Release a handle. Does nothing if `os_release` is a null pointer.
+ *
+ * # Safety
+ *
+ * `os_release` must be null or a handle returned by this library and not yet released.
+ */
+void os_release_free(OsReleaseHandle *os_release);
+
+/*
+ * Returns a static string describing an error code.
+ */
+const char *os_release_strerror(int code);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ETC_OS_RELEASE_H */
@@ -0,0 +1,320 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+use crate::OsRelease;
+
+/// A CPU architecture identifier, as used by the [`ARCHITECTURE=`] field.
+///
+/// The identifiers are the ones defined by systemd for `ConditionArchitecture=` in [`systemd.unit(5)`].
+/// Unknown identifiers are preserved as [`Architecture::Other`].
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::Architecture;
+///
+/// let arch: Architecture = "x86-64".parse().unwrap();
+/// assert_eq!(arch, Architecture::X86_64);
+/// assert_eq!(arch.to_string(), "x86-64");
+///
+/// let arch: Architecture = "vax".parse().unwrap();
+/// assert_eq!(arch, Architecture::Other("vax".to_owned()));
+/// ```
+///
+/// [`ARCHITECTURE=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#ARCHITECTURE=
+/// [`systemd.unit(5)`]: https://www.freedesktop.org/software/systemd/man/systemd.unit.html#ConditionArchitecture=
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Architecture {
+    /// `alpha`
+    Alpha,
+    /// `arc`
+    Arc,
+    /// `arc-be`
+    ArcBe,
+    /// `arm`
+    Arm,
+    /// `arm-be`
+    ArmBe,
+    /// `arm64`
+    Arm64,
+    /// `arm64-be`
+    Arm64Be,
+    /// `cris`
+    Cris,
+    /// `ia64`
+    Ia64,
+    /// `loongarch64`
+    LoongArch64,
+    /// `m68k`
+    M68k,
+    /// `mips`
+    Mips,
+    /// `mips-le`
+    MipsLe,
+    /// `mips64`
+    Mips64,
+    /// `mips64-le`
+    Mips64Le,
+    /// `nios2`
+    Nios2,
+    /// `parisc`
+    Parisc,
+    /// `parisc64`
+    Parisc64,
+    /// `ppc`
+    Ppc,
+    /// `ppc-le`
+    PpcLe,
+    /// `ppc64`
+    Ppc64,
+    /// `ppc64-le`
+    Ppc64Le,
+    /// `riscv32`
+    Riscv32,
+    /// `riscv64`
+    Riscv64,
+    /// `s390`
+    S390,
+    /// `s390x`
+    S390x,
+    /// `sh`
+    Sh,
+    /// `sh64`
+    Sh64,
+    /// `sparc`
+    Sparc,
+    /// `sparc64`
+    Sparc64,
+    /// `tilegx`
+    Tilegx,
+    /// `x86`
+    X86,
+    /// `x86-64`
+    X86_64,
+    /// An architecture identifier not known to this crate.
+    Other(String),
+}
+
+/// Known architectures and their identifiers.
+const ARCHITECTURES: &[(Architecture, &str)] = &[
+    (Architecture::Alpha, "alpha"),
+    (Architecture::Arc, "arc"),
+    (Architecture::ArcBe, "arc-be"),
+    (Architecture::Arm, "arm"),
+    (Architecture::ArmBe, "arm-be"),
+    (Architecture::Arm64, "arm64"),
+    (Architecture::Arm64Be, "arm64-be"),
+    (Architecture::Cris, "cris"),
+    (Architecture::Ia64, "ia64"),
+    (Architecture::LoongArch64, "loongarch64"),
+    (Architecture::M68k, "m68k"),
+    (Architecture::Mips, "mips"),
+    (Architecture::MipsLe, "mips-le"),
+    (Architecture::Mips64, "mips64"),
+    (Architecture::Mips64Le, "mips64-le"),
+    (Architecture::Nios2, "nios2"),
+    (Architecture::Parisc, "parisc"),
+    (Architecture::Parisc64, "parisc64"),
+    (Architecture::Ppc, "ppc"),
+    (Architecture::PpcLe, "ppc-le"),
+    (Architecture::Ppc64, "ppc64"),
+    (Architecture::Ppc64Le, "ppc64-le"),
+    (Architecture::Riscv32, "riscv32"),
+    (Architecture::Riscv64, "riscv64"),
+    (Architecture::S390, "s390"),
+    (Architecture::S390x, "s390x"),
+    (Architecture::Sh, "sh"),
+    (Architecture::Sh64, "sh64"),
+    (Architecture::Sparc, "sparc"),
+    (Architecture::Sparc64, "sparc64"),
+    (Architecture::Tilegx, "tilegx"),
+    (Architecture::X86, "x86"),
+    (Architecture::X86_64, "x86-64"),
+];
+
+impl Architecture {
+    /// Returns the architecture this crate was compiled for.
+    ///
+    /// This is derived from the `target_arch` and `target_endian` of the compilation target,
+    /// so it is the architecture of the running binary rather than of the running kernel.
+    ///
+    /// Returns `None` if the target architecture has no systemd identifier.
+    pub fn host() -> Option<Self> {
+        let little = cfg!(target_endian = "little");
+        let arch = if cfg!(target_arch = "x86") {
+            Self::X86
+        } else if cfg!(target_arch = "x86_64") {
+            Self::X86_64
+        } else if cfg!(target_arch = "arm") {
+            if little {
+                Self::Arm
+            } else {
+                Self::ArmBe
+            }
+        } else if cfg!(target_arch = "aarch64") {
+            if little {
+                Self::Arm64
+            } else {
+                Self::Arm64Be
+            }
+        } else if cfg!(target_arch = "powerpc") {
+            if little {
+                Self::PpcLe
+            } else {
+                Self::Ppc
+            }
+        } else if cfg!(target_arch = "powerpc64") {
+            if little {
+                Self::Ppc64Le
+            } else {
+                Self::Ppc64
+            }
+        } else if cfg!(target_arch = "mips") {
+            if little {
+                Self::MipsLe
+            } else {
+                Self::Mips
+            }
+        } else if cfg!(target_arch = "mips64") {
+            if little {
+                Self::Mips64Le
+            } else {
+                Self::Mips64
+            }
+        } else if cfg!(target_arch = "riscv32") {
+            Self::Riscv32
+        } else if cfg!(target_arch = "riscv64") {
+            Self::Riscv64
+        } else if cfg!(target_arch = "s390x") {
+            Self::S390x
+        } else if cfg!(target_arch = "sparc") {
+            Self::Sparc
+        } else if cfg!(target_arch = "sparc64") {
+            Self::Sparc64
+        } else if cfg!(target_arch = "loongarch64") {
+            Self::LoongArch64
+        } else if cfg!(target_arch = "m68k") {
+            Self::M68k
+        } else {
+            return None;
+        };
+        Some(arch)
+    }
+
+    /// Returns `true` if binaries of this architecture can run on `host`.
+    ///
+    /// This is the case for the same architecture, and for the secondary architecture of `host` known to systemd:
+    /// `x86` on `x86-64`, `arm` on `arm64`, `ppc` on `ppc64` and `s390` on `s390x`, and their big-endian variants.
+    /// Note that not all `arm64` CPUs can run 32-bit `arm` binaries, and that the host kernel must support them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use etc_os_release::Architecture;
+    ///
+    /// assert!(Architecture::X86.can_run_on(&Architecture::X86_64));
+    /// assert!(!Architecture::X86_64.can_run_on(&Architecture::X86));
+    /// ```
+    pub fn can_run_on(&self, host: &Self) -> bool {
+        let secondary = match host {
+            Self::X86_64 => Some(Self::X86),
+            Self::Arm64 => Some(Self::Arm),
+            Self::Arm64Be => Some(Self::ArmBe),
+            Self::Ppc64 => Some(Self::Ppc),
+            Self::S390x => Some(Self::S390),
+            _ => None,
+        };
+        self == host || secondary.as_ref() == Some(self)
+    }
+
+    /// Returns the systemd identifier of the architecture.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Other(s) => s,
+            _ => ARCHITECTURES
+                .iter()
+                .find_map(|(arch, s)| (arch == self).then_some(*s))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl FromStr for Architecture {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(ARCHITECTURES
+            .iter()
+            .find_map(|(arch, id)| (*id == s).then(|| arch.clone()))
+            .unwrap_or_else(|| Self::Other(s.to_owned())))
+    }
+}
+
+impl fmt::Display for Architecture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Methods to check the architecture of the operating system.
+impl OsRelease {
+    /// Returns `true` if the userspace binaries of the operating system can run on the host.
+    ///
+    /// The architecture is compared with [`Architecture::host()`] by [`Architecture::can_run_on()`],
+    /// so that e.g. an `x86` operating system matches an `x86-64` host.
+    /// If [`Self::architecture()`] is not set, the operating system is not restricted to any architecture,
+    /// and `true` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str("ID=fedora\n").unwrap();
+    /// assert!(os_release.architecture_matches_host());
+    ///
+    /// let os_release = OsRelease::from_str("ID=fedora\nARCHITECTURE=vax\n").unwrap();
+    /// assert!(!os_release.architecture_matches_host());
+    /// ```
+    pub fn architecture_matches_host(&self) -> bool {
+        match self.architecture() {
+            Some(arch) => Architecture::host().is_some_and(|host| arch.can_run_on(&host)),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        for (arch, s) in ARCHITECTURES {
+            assert_eq!(&s.parse::<Architecture>().unwrap(), arch);
+            assert_eq!(arch.as_str(), *s);
+        }
+        let other = Architecture::from_str("x86_64").unwrap();
+        assert_eq!(other, Architecture::Other("x86_64".to_owned()));
+        assert_eq!(other.as_str(), "x86_64");
+    }
+
+    #[test]
+    fn test_can_run_on() {
+        use Architecture::*;
+
+        for (arch, _) in ARCHITECTURES {
+            assert!(arch.can_run_on(arch));
+        }
+        assert!(X86.can_run_on(&X86_64));
+        assert!(Arm.can_run_on(&Arm64));
+        assert!(ArmBe.can_run_on(&Arm64Be));
+        assert!(!Arm.can_run_on(&Arm64Be));
+        assert!(!X86_64.can_run_on(&X86));
+        assert!(!Arm64.can_run_on(&X86_64));
+        assert!(!PpcLe.can_run_on(&Ppc64Le));
+        assert!(Other("vax".into()).can_run_on(&Other("vax".into())));
+    }
+}
@@ -0,0 +1,159 @@
+use std::{
+    fs::{self, File, Metadata},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::{construct::os_release_path, Error, OsRelease, ParseOptions};
+
+/// An os-release file which is read again only when it changes.
+///
+/// The path, modification time and size of the file are remembered,
+/// so that [`Self::reload_if_changed()`] can skip parsing the file if it has not changed.
+/// This is a cheap alternative to watching the file for daemons which already poll periodically.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use etc_os_release::OsReleaseCache;
+///
+/// let mut cache = OsReleaseCache::open()?;
+/// loop {
+///     if let Some(os_release) = cache.reload_if_changed()? {
+///         println!("updated to {}", os_release.pretty_name());
+///     }
+///     std::thread::sleep(std::time::Duration::from_secs(60));
+/// }
+/// # Ok::<(), etc_os_release::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct OsReleaseCache {
+    /// The path given by the user, or `None` to search for the file as in `OsRelease::open()`.
+    path: Option<PathBuf>,
+    options: ParseOptions,
+    stamp: Stamp,
+    os_release: OsRelease,
+}
+
+/// The identity of a version of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Stamp {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    len: u64,
+    #[cfg(unix)]
+    ino: u64,
+}
+
+impl Stamp {
+    fn new(path: &Path, metadata: &Metadata) -> Self {
+        Self {
+            path: path.to_owned(),
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+            #[cfg(unix)]
+            ino: std::os::unix::fs::MetadataExt::ino(metadata),
+        }
+    }
+}
+
+impl OsReleaseCache {
+    /// Open the os-release file and parse it.
+    ///
+    /// The file is searched for in the same way as [`OsRelease::open()`], and searched for again on every reload,
+    /// so that switching from `/usr/lib/os-release` to `/etc/os-release` is noticed.
+    /// Unlike [`OsRelease::open()`], [`Error::NoOsRelease`] is returned if neither file exists.
+    pub fn open() -> Result<Self, Error> {
+        Self::load(None, ParseOptions::new())
+    }
+
+    /// Open the os-release file at `path` and parse it with the given options.
+    pub fn open_path_with(path: impl AsRef<Path>, options: &ParseOptions) -> Result<Self, Error> {
+        Self::load(Some(path.as_ref().to_owned()), options.clone())
+    }
+
+    fn load(path: Option<PathBuf>, options: ParseOptions) -> Result<Self, Error> {
+        let (stamp, os_release) = read(path.as_deref(), &options)?;
+        Ok(Self {
+            path,
+            options,
+            stamp,
+            os_release,
+        })
+    }
+
+    /// Returns the most recently read os-release file.
+    pub fn get(&self) -> &OsRelease {
+        &self.os_release
+    }
+
+    /// Returns the path of the most recently read os-release file.
+    pub fn path(&self) -> &Path {
+        &self.stamp.path
+    }
+
+    /// Reads the os-release file again if it has changed since it was last read.
+    ///
+    /// Returns the new value if the file was read, or `None` if its path, modification time and size are unchanged.
+    /// If reading the file fails, the cached value is left unchanged.
+    pub fn reload_if_changed(&mut self) -> Result<Option<OsRelease>, Error> {
+        let path = match &self.path {
+            Some(path) => path.as_path(),
+            None => os_release_path().ok_or(Error::NoOsRelease)?,
+        };
+        if let Ok(metadata) = fs::metadata(path) {
+            if Stamp::new(path, &metadata) == self.stamp {
+                return Ok(None);
+            }
+        }
+        let (stamp, os_release) = read(Some(path), &self.options)?;
+        self.stamp = stamp;
+        self.os_release = os_release;
+        Ok(Some(self.os_release.clone()))
+    }
+}
+
+fn read(path: Option<&Path>, options: &ParseOptions) -> Result<(Stamp, OsRelease), Error> {
+    let path = match path {
+        Some(path) => path,
+        None => os_release_path().ok_or(Error::NoOsRelease)?,
+    };
+    let file = File::open(path).map_err(|err| Error::Open {
+        path: path.to_owned(),
+        err,
+    })?;
+    // Take the metadata from the opened file, so that it matches the contents even if the file is replaced meanwhile.
+    let metadata = file
+        .metadata()
+        .map_err(|err| Error::read(err).with_path(path))?;
+    let os_release = OsRelease::from_reader_with(file, options).map_err(|e| e.with_path(path))?;
+    Ok((Stamp::new(path, &metadata), os_release))
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn test_reload_if_changed() {
+        let path = env::temp_dir().join(format!("etc-os-release-cache-{}", std::process::id()));
+        fs::write(&path, "ID=fedora\nVERSION_ID=40\n").unwrap();
+
+        let mut cache = OsReleaseCache::open_path_with(&path, &ParseOptions::new()).unwrap();
+        assert_eq!(cache.get().version_id(), Some("40"));
+        assert_eq!(cache.path(), path);
+        assert!(cache.reload_if_changed().unwrap().is_none());
+
+        fs::write(&path, "ID=fedora\nVERSION_ID=41\nVARIANT_ID=workstation\n").unwrap();
+        let os_release = cache.reload_if_changed().unwrap().unwrap();
+        assert_eq!(os_release.version_id(), Some("41"));
+        assert_eq!(cache.get().version_id(), Some("41"));
+        assert!(cache.reload_if_changed().unwrap().is_none());
+
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(cache.reload_if_changed(), Err(Error::Open { .. })));
+        assert_eq!(cache.get().version_id(), Some("41"));
+    }
+}
@@ -0,0 +1,66 @@
+use crate::OsRelease;
+
+/// How completely an os-release file describes the operating system.
+///
+/// See [`OsRelease::completeness()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Completeness {
+    /// The file identifies the operating system by at most `ID=`,
+    /// having none of `NAME=`, `PRETTY_NAME=`, `VERSION=` and `VERSION_ID=`.
+    ///
+    /// This is typical for stripped-down container images.
+    /// Getters with defaults, such as [`OsRelease::name()`] and [`OsRelease::pretty_name()`], return the defaults.
+    Minimal,
+    /// The file has some presentation or version information, but not all of it.
+    Basic,
+    /// The file has `NAME=`, `ID=`, `PRETTY_NAME=`, `HOME_URL=`, and either `VERSION_ID=`, `BUILD_ID=` or `IMAGE_VERSION=`.
+    Full,
+}
+
+/// Methods to check how completely the operating system is described.
+impl OsRelease {
+    /// Classifies how completely the os-release file describes the operating system.
+    ///
+    /// Applications can use this to degrade their user experience intentionally,
+    /// instead of showing the defaults of [`Self::name()`] or [`Self::pretty_name()`] as if they were set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::{Completeness, OsRelease};
+    ///
+    /// let os_release = OsRelease::from_str("ID=alpine\n").unwrap();
+    /// assert_eq!(os_release.completeness(), Completeness::Minimal);
+    /// assert_eq!(os_release.pretty_name(), "Linux");
+    /// assert!(!os_release.contains_key("PRETTY_NAME"));
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// NAME="Alpine Linux"
+    /// ID=alpine
+    /// VERSION_ID=3.19.1
+    /// "#).unwrap();
+    /// assert_eq!(os_release.completeness(), Completeness::Basic);
+    /// ```
+    pub fn completeness(&self) -> Completeness {
+        let has = |key| self.contains_key(key);
+        if !["NAME", "PRETTY_NAME", "VERSION_ID", "VERSION"]
+            .into_iter()
+            .any(has)
+        {
+            return Completeness::Minimal;
+        }
+        let has_version = ["VERSION_ID", "BUILD_ID", "IMAGE_VERSION"]
+            .into_iter()
+            .any(has);
+        if has_version
+            && ["NAME", "ID", "PRETTY_NAME", "HOME_URL"]
+                .into_iter()
+                .all(has)
+        {
+            return Completeness::Full;
+        }
+        Completeness::Basic
+    }
+}
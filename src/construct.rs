@@ -1,12 +1,17 @@
 use std::{
     convert::Infallible,
-    fs::File,
-    io::{self, BufRead, BufReader},
-    path::{Path, PathBuf},
+    fs::{self, File},
+    io::{self, Read as _},
+    path::{Component, Path, PathBuf},
     str::FromStr,
+    sync::Arc,
 };
 
-use crate::{entry::OsReleaseLine, OsRelease, OsReleaseEntry};
+use crate::{
+    entry::{parse_line, strip_bom, strip_trailing_comment, LineError, OsReleaseLine},
+    fields::intern_key,
+    OsRelease, OsReleaseEntry,
+};
 
 /// Errors that can occur while parsing the os-release file.
 #[derive(Debug, thiserror::Error)]
@@ -25,12 +30,322 @@ pub enum Error {
         err: std::io::Error,
     },
     /// The os-release file could not be read.
-    #[error("failed to read os-release file: {err:?}")]
+    ///
+    /// This is also returned if the file is not valid UTF-8, with [`io::ErrorKind::InvalidData`].
+    #[error("failed to read os-release file{}: {err:?}", format_location(path.as_deref(), *line))]
     Read {
+        /// The path to the os-release file, if it was read from a file.
+        path: Option<PathBuf>,
+        /// The 1-based number of the line being read when the error occurred, if known.
+        line: Option<usize>,
         /// The error that occurred while reading the file.
         #[source]
         err: std::io::Error,
     },
+    /// The os-release file is malformed.
+    ///
+    /// This error is only returned when parsing in strict mode.
+    #[error("malformed os-release file: {}", format_line_errors(errors, *omitted))]
+    Malformed {
+        /// The errors found in the file, in the order of the lines.
+        ///
+        /// At most [`ParseOptions::max_errors()`] errors are recorded.
+        errors: Vec<LineError>,
+        /// The number of errors that were found but not recorded in `errors`.
+        omitted: usize,
+    },
+    /// The os-release file is larger than [`ParseOptions::max_size()`].
+    #[error("os-release file is larger than {max_size} bytes")]
+    TooLarge {
+        /// The maximum size of the file in bytes.
+        max_size: u64,
+    },
+    /// The kernel information could not be queried with `uname(2)`.
+    #[cfg(feature = "uname")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "uname")))]
+    #[error("failed to query kernel information: {err:?}")]
+    Uname {
+        /// The error that occurred while calling `uname(2)`.
+        #[source]
+        err: std::io::Error,
+    },
+    /// The os-release file could not be watched for changes.
+    #[cfg(all(target_os = "linux", feature = "watch"))]
+    #[cfg_attr(docsrs, doc(cfg(all(target_os = "linux", feature = "watch"))))]
+    #[error("failed to watch os-release file: {err:?}")]
+    Watch {
+        /// The error that occurred while watching the file.
+        #[source]
+        err: std::io::Error,
+    },
+}
+
+fn format_location(path: Option<&Path>, line: Option<usize>) -> String {
+    match (path, line) {
+        (Some(path), Some(line)) => format!(" {}:{line}", path.display()),
+        (Some(path), None) => format!(" {}", path.display()),
+        (None, Some(line)) => format!(" at line {line}"),
+        (None, None) => String::new(),
+    }
+}
+
+fn format_line_errors(errors: &[LineError], omitted: usize) -> String {
+    let mut s = errors
+        .iter()
+        .map(LineError::to_string)
+        .collect::<Vec<_>>()
+        .join("; ");
+    if omitted > 0 {
+        s += &format!(" (and {omitted} more errors)");
+    }
+    s
+}
+
+impl Error {
+    pub(crate) fn read(err: io::Error) -> Self {
+        Self::Read {
+            path: None,
+            line: None,
+            err,
+        }
+    }
+
+    /// Sets the path of the file to a [`Self::Read`] error, if not set yet.
+    pub(crate) fn with_path(mut self, file_path: &Path) -> Self {
+        if let Self::Read {
+            path: path @ None, ..
+        } = &mut self
+        {
+            *path = Some(file_path.to_owned());
+        }
+        self
+    }
+
+    /// Returns the path of the file the error occurred on, if known.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::io;
+    ///
+    /// use etc_os_release::{OsReleaseCache, ParseOptions};
+    ///
+    /// let err = OsReleaseCache::open_path_with("/nonexistent/os-release", &ParseOptions::new()).unwrap_err();
+    /// assert_eq!(err.path().unwrap().to_str(), Some("/nonexistent/os-release"));
+    /// assert_eq!(err.io_kind(), Some(io::ErrorKind::NotFound));
+    /// ```
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::Open { path, .. } => Some(path),
+            Self::Read { path, .. } => path.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the 1-based number of the line being read when a [`Self::Read`] error occurred, if known.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::io;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let err = OsRelease::from_bytes(b"NAME=Fedora\nPRETTY_NAME=\"Caf\xe9\"\n").unwrap_err();
+    /// assert_eq!(err.line(), Some(2));
+    /// assert_eq!(err.io_kind(), Some(io::ErrorKind::InvalidData));
+    /// ```
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Self::Read { line, .. } => *line,
+            _ => None,
+        }
+    }
+
+    /// Returns the kind of the I/O error that caused the error, if any.
+    ///
+    /// This distinguishes, for example, permission errors from missing files without matching the error message.
+    pub fn io_kind(&self) -> Option<io::ErrorKind> {
+        match self {
+            Self::Open { err, .. } | Self::Read { err, .. } => Some(err.kind()),
+            #[cfg(feature = "uname")]
+            Self::Uname { err } => Some(err.kind()),
+            #[cfg(all(target_os = "linux", feature = "watch"))]
+            Self::Watch { err } => Some(err.kind()),
+            Self::NoOsRelease | Self::Malformed { .. } | Self::TooLarge { .. } => None,
+        }
+    }
+}
+
+/// Options to control how the os-release file is parsed.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::{Error, OsRelease, ParseOptions};
+///
+/// let options = ParseOptions::new().strict(true);
+/// let err = OsRelease::from_str_with("NAME=Fedora Linux\nID\n", &options).unwrap_err();
+/// let Error::Malformed { errors, .. } = err else {
+///     panic!("unexpected error: {err}");
+/// };
+/// assert_eq!(errors.len(), 2);
+/// assert_eq!(errors[0].line(), 1);
+/// assert_eq!(errors[1].line(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    strict: bool,
+    max_errors: usize,
+    max_size: u64,
+    empty_as_unset: bool,
+    strip_trailing_comments: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParseOptions {
+    /// Creates the default options.
+    ///
+    /// By default, malformed lines are silently ignored.
+    pub fn new() -> Self {
+        Self {
+            strict: false,
+            max_errors: 16,
+            max_size: 4 * 1024 * 1024,
+            empty_as_unset: false,
+            strip_trailing_comments: false,
+        }
+    }
+
+    /// Sets whether malformed lines are rejected.
+    ///
+    /// In strict mode, every line is checked with [`OsReleaseLine::parse_strict()`],
+    /// and [`Error::Malformed`] is returned if any line is malformed.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Returns whether malformed lines are rejected.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Sets the maximum number of errors recorded in [`Error::Malformed`].
+    ///
+    /// At least one error is always recorded. Defaults to 16.
+    pub fn max_errors(mut self, max_errors: usize) -> Self {
+        self.max_errors = max_errors;
+        self
+    }
+
+    /// Returns the maximum number of errors recorded in [`Error::Malformed`].
+    pub fn get_max_errors(&self) -> usize {
+        self.max_errors
+    }
+
+    /// Sets the maximum size of the input in bytes.
+    ///
+    /// Readers and byte buffers larger than this are rejected with [`Error::TooLarge`], without buffering the excess,
+    /// so that untrusted input cannot exhaust the memory. Defaults to 4 MiB.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::io::{self, Read};
+    ///
+    /// use etc_os_release::{Error, OsRelease, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().max_size(1024);
+    /// let reader = io::repeat(b'#').take(1 << 30);
+    /// let err = OsRelease::from_reader_with(reader, &options).unwrap_err();
+    /// assert!(matches!(err, Error::TooLarge { max_size: 1024 }));
+    ///
+    /// let err = OsRelease::from_bytes_with(&[b'#'; 1025], &options).unwrap_err();
+    /// assert!(matches!(err, Error::TooLarge { max_size: 1024 }));
+    /// ```
+    pub fn max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Returns the maximum size of the input in bytes.
+    pub fn get_max_size(&self) -> u64 {
+        self.max_size
+    }
+
+    /// Sets whether fields assigned an empty value are treated as unset.
+    ///
+    /// Some generators emit assignments such as `VERSION_ID=""` for unknown values,
+    /// which consumers rarely want to handle differently from an absent field.
+    /// As the last assignment of a key takes effect, an empty assignment also unsets an earlier one.
+    /// Defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use etc_os_release::{OsRelease, ParseOptions};
+    ///
+    /// let input = "ID=fedora\nVERSION_ID=\"\"\n";
+    /// let os_release = OsRelease::from_str_with(input, &ParseOptions::new()).unwrap();
+    /// assert_eq!(os_release.version_id(), Some(""));
+    ///
+    /// let options = ParseOptions::new().empty_as_unset(true);
+    /// let os_release = OsRelease::from_str_with(input, &options).unwrap();
+    /// assert_eq!(os_release.version_id(), None);
+    /// assert!(!os_release.contains_key("VERSION_ID"));
+    /// ```
+    pub fn empty_as_unset(mut self, empty_as_unset: bool) -> Self {
+        self.empty_as_unset = empty_as_unset;
+        self
+    }
+
+    /// Returns whether fields assigned an empty value are treated as unset.
+    pub fn is_empty_as_unset(&self) -> bool {
+        self.empty_as_unset
+    }
+
+    /// Sets whether comments at the end of assignment lines are stripped from the values.
+    ///
+    /// In the shell, `KEY=value # comment` assigns `value`, as a `#` at the beginning of a word starts a comment.
+    /// By default, the rest of the line is kept as a part of the value for compatibility,
+    /// and strict mode rejects such lines with [`LineErrorKind::TrailingComment`](crate::LineErrorKind::TrailingComment).
+    /// If enabled, the comments are stripped as the shell does. Defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use etc_os_release::{Error, LineErrorKind, OsRelease, ParseOptions};
+    ///
+    /// let input = "ID=fedora # the distribution\nNAME=\"Fedora # Linux\"\n";
+    /// let os_release = OsRelease::from_str_with(input, &ParseOptions::new()).unwrap();
+    /// assert_eq!(os_release.id(), "fedora # the distribution");
+    ///
+    /// let options = ParseOptions::new().strict(true);
+    /// let Err(Error::Malformed { errors, .. }) = OsRelease::from_str_with(input, &options) else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(errors[0].kind(), &LineErrorKind::TrailingComment);
+    ///
+    /// let options = options.strip_trailing_comments(true);
+    /// let os_release = OsRelease::from_str_with(input, &options).unwrap();
+    /// assert_eq!(os_release.id(), "fedora");
+    /// assert_eq!(os_release.name(), "Fedora # Linux");
+    /// ```
+    pub fn strip_trailing_comments(mut self, strip_trailing_comments: bool) -> Self {
+        self.strip_trailing_comments = strip_trailing_comments;
+        self
+    }
+
+    /// Returns whether comments at the end of assignment lines are stripped from the values.
+    pub fn is_strip_trailing_comments(&self) -> bool {
+        self.strip_trailing_comments
+    }
 }
 
 /// Methods to construct an `OsRelease`.
@@ -39,27 +354,176 @@ impl OsRelease {
     ///
     /// If `/etc/os-release` exists, it is opened.
     /// Otherwise, `/usr/lib/os-release` is opened.
-    /// If neither file exists, an error is returned.
+    /// If neither file exists, the [`OsRelease`] is synthesized from platform-specific information
+    /// as in [`Self::from_platform()`], and an error is returned if that is not available either.
     ///
     /// For simplicity, this function assumes that the file is well-formed.
+    ///
+    /// # WASI
+    ///
+    /// WASI targets are not yet supported, but the lookup is designed as follows.
+    /// On WASI targets, the files are looked up in the directories preopened by the host,
+    /// so `/etc` or `/usr/lib` of the host must be preopened at the same path,
+    /// e.g. `wasmtime run --dir /etc::/etc --dir /usr/lib::/usr/lib app.wasm`.
+    /// If neither is preopened, [`Error::NoOsRelease`] is returned.
     pub fn open() -> Result<Self, Error> {
-        let path = os_release_path().ok_or(Error::NoOsRelease)?;
+        Self::open_with(&ParseOptions::new())
+    }
+
+    /// Open the os-release file and parse it with the given options.
+    ///
+    /// The file is searched for in the same way as [`Self::open()`].
+    /// If it is synthesized by [`Self::from_platform()`], only [`ParseOptions::empty_as_unset()`] is applied,
+    /// as the other options concern the syntax of the file.
+    pub fn open_with(options: &ParseOptions) -> Result<Self, Error> {
+        let Some(path) = os_release_path() else {
+            let mut os_release = Self::from_platform()?;
+            if options.is_empty_as_unset() {
+                os_release.retain(|_, value| !value.is_empty());
+            }
+            return Ok(os_release);
+        };
+        Self::open_path_with(path, options)
+    }
+
+    /// Open the os-release file of the operating system tree at `root` and parse it.
+    ///
+    /// `<root>/etc/os-release` and `<root>/usr/lib/os-release` are searched for in the same way as [`Self::open()`].
+    /// Symbolic links are resolved as if `root` were the root directory: absolute links are resolved relative to `root`,
+    /// and `..` never leaves `root`, so that the files of the host are never read.
+    ///
+    /// This is useful to inspect unpacked container images or mounted disk images.
+    pub fn open_root(root: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = root_os_release_path(root.as_ref()).ok_or(Error::NoOsRelease)?;
+        Self::open_path_with(&path, &ParseOptions::new())
+    }
+
+    /// Open the os-release file of the systemd-machined machine image `name` and parse it.
+    ///
+    /// The image is searched for as a directory named `name` in the image directories of [`systemd-machined(8)`]
+    /// (`/etc/machines`, `/run/machines`, `/var/lib/machines`, `/usr/local/lib/machines` and `/usr/lib/machines`),
+    /// and its os-release file is read as in [`Self::open_root()`].
+    /// Disk images (`.raw` files) are not supported.
+    ///
+    /// Returns [`Error::NoOsRelease`] if the image or its os-release file is not found.
+    ///
+    /// [`systemd-machined(8)`]: https://www.freedesktop.org/software/systemd/man/systemd-machined.service.html
+    pub fn open_machine(name: &str) -> Result<Self, Error> {
+        if name.is_empty() || name == "." || name == ".." || name.contains('/') {
+            return Err(Error::NoOsRelease);
+        }
+        let root = [
+            "/etc/machines",
+            "/run/machines",
+            "/var/lib/machines",
+            "/usr/local/lib/machines",
+            "/usr/lib/machines",
+        ]
+        .into_iter()
+        .map(|dir| Path::new(dir).join(name))
+        .find(|root| root.is_dir())
+        .ok_or(Error::NoOsRelease)?;
+        Self::open_root(root)
+    }
+
+    /// Open the os-release file at `path` and parse it with the given options.
+    pub(crate) fn open_path_with(path: &Path, options: &ParseOptions) -> Result<Self, Error> {
         let file = File::open(path).map_err(|err| Error::Open {
             path: path.to_owned(),
             err,
         })?;
-        Self::from_reader(file)
+        Self::from_reader_with(file, options).map_err(|e| e.with_path(path))
     }
 
     /// Parse the os-release file from a reader.
     ///
     /// For simplicity, this function assumes that the file is well-formed.
     pub fn from_reader(reader: impl io::Read) -> Result<Self, Error> {
-        let reader = BufReader::new(reader);
-        reader
-            .lines()
-            .collect::<Result<_, _>>()
-            .map_err(|err| Error::Read { err })
+        Self::from_reader_with(reader, &ParseOptions::new())
+    }
+
+    /// Parse the os-release file from a reader with the given options.
+    ///
+    /// At most [`ParseOptions::max_size()`] bytes are read.
+    pub fn from_reader_with(reader: impl io::Read, options: &ParseOptions) -> Result<Self, Error> {
+        // os-release files are tiny, so read the whole file at once and parse slices of it,
+        // instead of allocating a `String` for each line.
+        let buf = read_limited(reader, options)?;
+        Self::from_bytes_with(&buf, options)
+    }
+
+    /// Parse a byte buffer containing the contents of the os-release file.
+    ///
+    /// Returns [`Error::Read`] with the line of the first invalid byte if the buffer is not valid UTF-8,
+    /// as [`Self::from_reader()`] does.
+    /// Use [`Self::from_bytes_lossy()`] to accept invalid UTF-8 sequences.
+    ///
+    /// For simplicity, this function assumes that the file is well-formed.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Self::from_bytes_with(bytes, &ParseOptions::new())
+    }
+
+    /// Parse a byte buffer containing the contents of the os-release file with the given options.
+    ///
+    /// Buffers larger than [`ParseOptions::max_size()`] are rejected with [`Error::TooLarge`].
+    pub fn from_bytes_with(bytes: &[u8], options: &ParseOptions) -> Result<Self, Error> {
+        Self::from_str_with(decode_with(bytes, options)?, options)
+    }
+
+    /// Parse a byte buffer containing the contents of the os-release file, replacing invalid UTF-8 sequences.
+    ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD REPLACEMENT CHARACTER`, as in [`String::from_utf8_lossy()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let bytes = b"ID=vendor\nPRETTY_NAME=\"Caf\xe9 OS\"\n";
+    /// assert!(OsRelease::from_bytes(bytes).is_err());
+    ///
+    /// let os_release = OsRelease::from_bytes_lossy(bytes);
+    /// assert_eq!(os_release.id(), "vendor");
+    /// assert_eq!(os_release.pretty_name(), "Caf\u{FFFD} OS");
+    /// ```
+    pub fn from_bytes_lossy(bytes: &[u8]) -> Self {
+        strip_bom(&String::from_utf8_lossy(bytes)).lines().collect()
+    }
+
+    /// Parse a string containing the contents of the os-release file with the given options.
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self, Error> {
+        Self::from_lines_with(strip_bom(s).lines(), options)
+    }
+
+    fn from_lines_with<'a>(
+        lines: impl IntoIterator<Item = &'a str>,
+        options: &ParseOptions,
+    ) -> Result<Self, Error> {
+        let strip = options.strip_trailing_comments;
+        let lines = lines.into_iter().map(move |line| {
+            if strip {
+                strip_trailing_comment(line)
+            } else {
+                line
+            }
+        });
+        let mut os_release = Self::collect_lines_with(lines, options)?;
+        if options.empty_as_unset {
+            os_release.retain(|_, value| !value.is_empty());
+        }
+        Ok(os_release)
+    }
+
+    fn collect_lines_with<'a>(
+        lines: impl IntoIterator<Item = &'a str>,
+        options: &ParseOptions,
+    ) -> Result<Self, Error> {
+        if !options.strict {
+            return Ok(lines.into_iter().collect());
+        }
+
+        let entries = parse_strict_lines(lines, options)?;
+        Ok(entries.into_iter().map(|(_, entry)| entry).collect())
     }
 }
 
@@ -68,42 +532,82 @@ impl<'a> FromIterator<OsReleaseEntry<'a>> for OsRelease {
     where
         T: IntoIterator<Item = OsReleaseEntry<'a>>,
     {
+        let fields = iter
+            .into_iter()
+            .map(|entry| {
+                let (key, value) = entry.into_parts();
+                (intern_key(key), value.into())
+            })
+            .collect();
         Self {
-            fields: iter
-                .into_iter()
-                .map(|entry| (entry.key().to_owned(), entry.value().to_owned()))
-                .collect(),
+            fields: Arc::new(fields),
         }
     }
 }
 
+/// Parses the lines of an os-release file, ignoring malformed lines.
+///
+/// With the `log` feature, a warning is logged for each ignored line and duplicate key.
 impl<'a> FromIterator<&'a str> for OsRelease {
     fn from_iter<T>(iter: T) -> Self
     where
         T: IntoIterator<Item = &'a str>,
     {
-        iter.into_iter()
-            .filter_map(|line| {
-                OsReleaseLine::from_str(line)
-                    .ok()
-                    .and_then(|line| line.into_entry())
+        #[cfg(not(feature = "log"))]
+        let entries = iter.into_iter().filter_map(parse_line);
+        #[cfg(feature = "log")]
+        let entries = {
+            let mut keys = std::collections::HashSet::new();
+            iter.into_iter().enumerate().filter_map(move |(i, line)| {
+                let entry = parse_line(line);
+                warn_line(i + 1, line, entry.as_ref(), &mut keys);
+                entry
             })
-            .collect()
+        };
+        entries.collect()
     }
 }
 
+/// Logs a warning if the line is ignored or overrides a previous line.
+#[cfg(feature = "log")]
+fn warn_line(
+    line_number: usize,
+    line: &str,
+    entry: Option<&OsReleaseEntry<'_>>,
+    keys: &mut std::collections::HashSet<String>,
+) {
+    match entry {
+        Some(entry) => {
+            if !keys.insert(entry.key().to_owned()) {
+                log::warn!(
+                    "line {line_number} of os-release file overrides the previous value of `{}`",
+                    entry.key()
+                );
+            }
+            if crate::entry::trailing_comment_start(line).is_some() {
+                log::warn!(
+                    "value of `{}` at line {line_number} of os-release file includes a trailing comment",
+                    entry.key()
+                );
+            }
+        }
+        None => {
+            let line = line.trim_start_matches([' ', '\t']);
+            if !line.is_empty() && !line.starts_with('#') {
+                log::warn!("ignoring malformed line {line_number} of os-release file: {line:?}");
+            }
+        }
+    }
+}
+
+/// Parses the lines of an os-release file, ignoring malformed lines, as the implementation for `&str`.
 impl FromIterator<String> for OsRelease {
     fn from_iter<T>(iter: T) -> Self
     where
         T: IntoIterator<Item = String>,
     {
-        iter.into_iter()
-            .filter_map(|line| {
-                OsReleaseLine::from_str(&line)
-                    .ok()
-                    .and_then(|line| line.into_entry())
-            })
-            .collect()
+        let lines = iter.into_iter().collect::<Vec<_>>();
+        lines.iter().map(String::as_str).collect()
     }
 }
 
@@ -111,12 +615,68 @@ impl FromStr for OsRelease {
     type Err = Infallible;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(s.lines().collect())
+        Ok(strip_bom(s).lines().collect())
+    }
+}
+
+/// Read at most one byte more than [`ParseOptions::max_size()`] from `reader`.
+pub(crate) fn read_limited(
+    reader: impl io::Read,
+    options: &ParseOptions,
+) -> Result<Vec<u8>, Error> {
+    let mut buf = vec![];
+    reader
+        .take(options.max_size.saturating_add(1))
+        .read_to_end(&mut buf)
+        .map_err(Error::read)?;
+    Ok(buf)
+}
+
+/// Check the size of the os-release file and decode it as UTF-8.
+pub(crate) fn decode_with<'a>(bytes: &'a [u8], options: &ParseOptions) -> Result<&'a str, Error> {
+    if u64::try_from(bytes.len()).map_or(true, |len| len > options.max_size) {
+        return Err(Error::TooLarge {
+            max_size: options.max_size,
+        });
     }
+    std::str::from_utf8(bytes).map_err(|e| {
+        let line = bytes[..e.valid_up_to()]
+            .iter()
+            .filter(|&&b| b == b'\n')
+            .count();
+        Error::Read {
+            path: None,
+            line: Some(line + 1),
+            err: io::Error::new(io::ErrorKind::InvalidData, e),
+        }
+    })
+}
+
+/// Parse the lines with [`OsReleaseLine::parse_strict()`], returning the entries with their 1-based line numbers.
+pub(crate) fn parse_strict_lines<'a>(
+    lines: impl IntoIterator<Item = &'a str>,
+    options: &ParseOptions,
+) -> Result<Vec<(usize, OsReleaseEntry<'static>)>, Error> {
+    let mut entries = vec![];
+    let mut errors = vec![];
+    let mut omitted = 0;
+    for (i, line) in lines.into_iter().enumerate() {
+        match OsReleaseLine::parse_strict(line) {
+            Ok(line) => entries.extend(line.into_entry().map(|entry| (i + 1, entry))),
+            Err(kind) if errors.len() < options.max_errors.max(1) => {
+                errors.push(LineError::new(i + 1, kind));
+            }
+            Err(_) => omitted += 1,
+        }
+    }
+    if !errors.is_empty() {
+        return Err(Error::Malformed { errors, omitted });
+    }
+    Ok(entries)
 }
 
 /// Find the os-release file to parse.
-fn os_release_path() -> Option<&'static Path> {
+pub(crate) fn os_release_path() -> Option<&'static Path> {
     [
         Path::new("/etc/os-release"),
         Path::new("/usr/lib/os-release"),
@@ -124,3 +684,158 @@ fn os_release_path() -> Option<&'static Path> {
     .into_iter()
     .find(|path| path.exists())
 }
+
+/// Find the os-release file to parse in the operating system tree at `root`.
+pub(crate) fn root_os_release_path(root: &Path) -> Option<PathBuf> {
+    ["etc/os-release", "usr/lib/os-release"]
+        .into_iter()
+        .filter_map(|path| resolve_in_root(root, Path::new(path)))
+        .find(|path| path.exists())
+}
+
+/// Resolve `path` relative to `root`, as if `root` were the root directory.
+///
+/// The path is resolved one component at a time, so that symbolic links of intermediate directories are followed.
+/// Absolute symbolic links are resolved relative to `root`, and `..` is clamped at `root`,
+/// so that the returned path never leaves `root`.
+///
+/// Returns `None` if too many symbolic links are followed.
+pub(crate) fn resolve_in_root(root: &Path, path: &Path) -> Option<PathBuf> {
+    const MAX_SYMLINKS: usize = 40;
+
+    let push_components = |pending: &mut Vec<PathBuf>, path: &Path| {
+        pending.extend(path.components().rev().map(|c| c.as_os_str().into()));
+    };
+
+    let mut resolved = PathBuf::new();
+    let mut pending = vec![];
+    push_components(&mut pending, path);
+    let mut symlinks = 0;
+    while let Some(component) = pending.pop() {
+        match component.components().next() {
+            Some(Component::Prefix(_) | Component::RootDir) => resolved.clear(),
+            Some(Component::CurDir) | None => {}
+            Some(Component::ParentDir) => {
+                resolved.pop();
+            }
+            Some(Component::Normal(name)) => {
+                let candidate = root.join(&resolved).join(name);
+                match fs::read_link(&candidate) {
+                    Ok(target) => {
+                        symlinks += 1;
+                        if symlinks > MAX_SYMLINKS {
+                            return None;
+                        }
+                        push_components(&mut pending, &target);
+                    }
+                    Err(_) => resolved.push(name),
+                }
+            }
+        }
+    }
+    Some(root.join(resolved))
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "log")]
+    use std::sync::{Mutex, Once};
+
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_in_root() {
+        use std::{env, os::unix::fs::symlink};
+
+        let root = env::temp_dir().join(format!("etc-os-release-root-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("usr/lib")).unwrap();
+        fs::create_dir_all(root.join("etc")).unwrap();
+        fs::write(root.join("usr/lib/os-release"), "ID=guest\n").unwrap();
+
+        symlink(
+            "../../../../../../etc/os-release",
+            root.join("etc/os-release"),
+        )
+        .unwrap();
+        // `..` is clamped at `root`, so the link points to itself.
+        assert_eq!(resolve_in_root(&root, Path::new("etc/os-release")), None);
+        assert!(matches!(OsRelease::open_root(&root), Ok(os) if os.id() == "guest"));
+
+        fs::remove_file(root.join("etc/os-release")).unwrap();
+        symlink("../usr/lib/os-release", root.join("etc/os-release")).unwrap();
+        assert_eq!(
+            resolve_in_root(&root, Path::new("etc/os-release")),
+            Some(root.join("usr/lib/os-release"))
+        );
+
+        fs::remove_dir_all(root.join("etc")).unwrap();
+        symlink("/usr/lib", root.join("etc")).unwrap();
+        assert_eq!(
+            resolve_in_root(&root, Path::new("etc/os-release")),
+            Some(root.join("usr/lib/os-release"))
+        );
+
+        fs::remove_file(root.join("etc")).unwrap();
+        symlink("/etc", root.join("etc")).unwrap();
+        assert_eq!(resolve_in_root(&root, Path::new("etc/os-release")), None);
+        assert!(matches!(OsRelease::open_root(&root), Ok(os) if os.id() == "guest"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(feature = "log")]
+    static RECORDS: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+    #[cfg(feature = "log")]
+    struct Logger;
+
+    #[cfg(feature = "log")]
+    impl log::Log for Logger {
+        fn enabled(&self, _metadata: &log::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record<'_>) {
+            RECORDS.lock().unwrap().push(record.args().to_string());
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn test_warn_lines() {
+        static INIT: Once = Once::new();
+        INIT.call_once(|| {
+            log::set_logger(&Logger).unwrap();
+            log::set_max_level(log::LevelFilter::Warn);
+        });
+
+        let os_release = OsRelease::from_str(
+            "# comment\n\nLOG_TEST_A=1\nLOG_TEST_B\nLOG_TEST_A=2\n  # indented comment\nLOG_TEST_C=3 # c\n",
+        )
+        .unwrap();
+        assert_eq!(os_release.get_value("LOG_TEST_A"), Some("2"));
+        let os_release = ["LOG_TEST_D=4".to_owned(), "LOG_TEST_E".to_owned()]
+            .into_iter()
+            .collect::<OsRelease>();
+        assert_eq!(os_release.get_value("LOG_TEST_D"), Some("4"));
+
+        let records = RECORDS.lock().unwrap();
+        let records = records
+            .iter()
+            .filter(|r| r.contains("LOG_TEST_"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            records,
+            [
+                r#"ignoring malformed line 4 of os-release file: "LOG_TEST_B""#,
+                "line 5 of os-release file overrides the previous value of `LOG_TEST_A`",
+                "value of `LOG_TEST_C` at line 7 of os-release file includes a trailing comment",
+                r#"ignoring malformed line 2 of os-release file: "LOG_TEST_E""#,
+            ]
+        );
+    }
+}
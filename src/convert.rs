@@ -0,0 +1,136 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::BuildHasher,
+};
+
+use crate::{OsRelease, OsReleaseEntry};
+
+fn from_pairs(iter: impl IntoIterator<Item = (String, String)>) -> OsRelease {
+    iter.into_iter()
+        .map(|(key, value)| OsReleaseEntry::new(key, value))
+        .collect()
+}
+
+fn into_pairs(os_release: OsRelease) -> impl Iterator<Item = (String, String)> {
+    os_release.into_iter().map(|entry| {
+        let (key, value) = entry.into_parts();
+        (key.into_owned(), value.into_owned())
+    })
+}
+
+/// Methods to access the fields as an `IndexMap`.
+#[cfg(feature = "indexmap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "indexmap")))]
+impl OsRelease {
+    /// Returns the fields as a map of borrowed strings, preserving the order.
+    ///
+    /// The fields are stored in a compact form internally, so this builds a new map of references to them,
+    /// which gives access to the full API of `IndexMap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::spec_example();
+    /// let map = os_release.as_map();
+    /// assert_eq!(map.get_index_of("ID"), Some(2));
+    /// assert_eq!(map["VERSION_ID"], "32");
+    /// ```
+    pub fn as_map(&self) -> indexmap::IndexMap<&str, &str> {
+        self.fields
+            .iter()
+            .map(|(key, value)| (key.as_ref(), value.as_ref()))
+            .collect()
+    }
+
+    /// Returns the fields as a map of owned strings, preserving the order.
+    ///
+    /// This is the same as `IndexMap::from(self)`.
+    pub fn into_fields(self) -> indexmap::IndexMap<String, String> {
+        self.into()
+    }
+}
+
+/// Creates an `OsRelease` from the fields.
+///
+/// The order of the fields is unspecified, as that of [`HashMap`].
+impl<S> From<HashMap<String, String, S>> for OsRelease {
+    fn from(map: HashMap<String, String, S>) -> Self {
+        from_pairs(map)
+    }
+}
+
+/// Creates an `OsRelease` from the fields, sorted by key.
+impl From<BTreeMap<String, String>> for OsRelease {
+    fn from(map: BTreeMap<String, String>) -> Self {
+        from_pairs(map)
+    }
+}
+
+/// Creates an `OsRelease` from the fields, preserving the order.
+#[cfg(feature = "indexmap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "indexmap")))]
+impl<S> From<indexmap::IndexMap<String, String, S>> for OsRelease {
+    fn from(map: indexmap::IndexMap<String, String, S>) -> Self {
+        from_pairs(map)
+    }
+}
+
+impl<S: BuildHasher + Default> From<OsRelease> for HashMap<String, String, S> {
+    fn from(os_release: OsRelease) -> Self {
+        into_pairs(os_release).collect()
+    }
+}
+
+impl From<OsRelease> for BTreeMap<String, String> {
+    fn from(os_release: OsRelease) -> Self {
+        into_pairs(os_release).collect()
+    }
+}
+
+/// Returns the fields, preserving the order.
+#[cfg(feature = "indexmap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "indexmap")))]
+impl<S: BuildHasher + Default> From<OsRelease> for indexmap::IndexMap<String, String, S> {
+    fn from(os_release: OsRelease) -> Self {
+        into_pairs(os_release).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_map_conversions() {
+        let os_release = OsRelease::spec_example();
+
+        let map = HashMap::<String, String>::from(os_release.clone());
+        assert_eq!(map.len(), os_release.len());
+        assert_eq!(map["ID"], "fedora");
+        assert_eq!(OsRelease::from(map), os_release);
+
+        let map = BTreeMap::from(os_release.clone());
+        assert_eq!(map.keys().next().unwrap(), "ANSI_COLOR");
+        let sorted = OsRelease::from(map);
+        assert_eq!(sorted, os_release);
+        assert_eq!(sorted.entries().next().unwrap().key(), "ANSI_COLOR");
+
+        #[cfg(feature = "indexmap")]
+        {
+            let map = indexmap::IndexMap::<String, String>::from(os_release.clone());
+            assert_eq!(map.get_index(0).unwrap().0, "NAME");
+            let converted = OsRelease::from(map);
+            assert!(converted.entries().eq(os_release.entries()));
+
+            let map = os_release.as_map();
+            assert_eq!(map.len(), os_release.len());
+            assert_eq!(
+                map.get_index(1),
+                Some((&"VERSION", &"32 (Workstation Edition)"))
+            );
+            assert_eq!(os_release.clone().into_fields()["NAME"], "Fedora");
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::{Error, OsRelease};
+
+/// The cached os-release file of the running system.
+static CURRENT: RwLock<Option<Arc<OsRelease>>> = RwLock::new(None);
+
+/// Methods to access the cached os-release file of the running system.
+impl OsRelease {
+    /// Returns the os-release file of the running system, opening it on the first call.
+    ///
+    /// The file is opened as in [`Self::open()`] and cached, so subsequent calls are cheap
+    /// and do not touch the file system.
+    /// Errors are not cached: if opening the file fails, the next call tries again.
+    ///
+    /// Use [`Self::reload()`] or [`Self::invalidate()`] to pick up changes of the file,
+    /// e.g. after an update of an image-based system.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::current()?;
+    /// println!("{}", os_release.pretty_name());
+    /// # Ok::<(), etc_os_release::Error>(())
+    /// ```
+    pub fn current() -> Result<Arc<Self>, Error> {
+        if let Some(os_release) = &*CURRENT.read().unwrap_or_else(PoisonError::into_inner) {
+            return Ok(Arc::clone(os_release));
+        }
+
+        let mut current = CURRENT.write().unwrap_or_else(PoisonError::into_inner);
+        if let Some(os_release) = &*current {
+            return Ok(Arc::clone(os_release));
+        }
+        let os_release = Arc::new(Self::open()?);
+        *current = Some(Arc::clone(&os_release));
+        Ok(os_release)
+    }
+
+    /// Opens the os-release file of the running system again, and replaces the value returned by [`Self::current()`].
+    ///
+    /// Values returned by previous calls of [`Self::current()`] keep the old contents,
+    /// which are dropped when the last of them is dropped.
+    ///
+    /// If opening the file fails, the cached value is left unchanged.
+    pub fn reload() -> Result<Arc<Self>, Error> {
+        let os_release = Arc::new(Self::open()?);
+        *CURRENT.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::clone(&os_release));
+        Ok(os_release)
+    }
+
+    /// Discards the value cached by [`Self::current()`], so that the next call opens the file again.
+    ///
+    /// Values returned by previous calls of [`Self::current()`] are not affected.
+    pub fn invalidate() {
+        *CURRENT.write().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_current() {
+        let Ok(first) = OsRelease::current() else {
+            // The running system has no os-release file.
+            return;
+        };
+        assert!(Arc::ptr_eq(&first, &OsRelease::current().unwrap()));
+
+        OsRelease::invalidate();
+        let second = OsRelease::current().unwrap();
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(first.id(), second.id());
+        // Only `first` refers to the old value, so it is dropped with it.
+        assert_eq!(Arc::strong_count(&first), 1);
+
+        let third = OsRelease::reload().unwrap();
+        assert!(Arc::ptr_eq(&third, &OsRelease::current().unwrap()));
+        assert!(!Arc::ptr_eq(&second, &third));
+        assert_eq!(Arc::strong_count(&second), 1);
+    }
+}
@@ -0,0 +1,514 @@
+use std::{
+    env,
+    io::{self, BufRead, BufReader, Read, Write},
+    os::unix::net::UnixStream,
+    path::PathBuf,
+    time::Duration,
+};
+
+use crate::{Error, HostnameProperties, OsRelease};
+
+/// The socket of the D-Bus system bus, used if `DBUS_SYSTEM_BUS_ADDRESS` is not set.
+const SYSTEM_BUS_SOCKET: &str = "/run/dbus/system_bus_socket";
+
+/// The timeout for the replies, which includes the bus activation of systemd-hostnamed.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+const METHOD_CALL: u8 = 1;
+const METHOD_RETURN: u8 = 2;
+const ERROR: u8 = 3;
+
+const FIELD_PATH: u8 = 1;
+const FIELD_INTERFACE: u8 = 2;
+const FIELD_MEMBER: u8 = 3;
+const FIELD_REPLY_SERIAL: u8 = 5;
+const FIELD_DESTINATION: u8 = 6;
+const FIELD_SIGNATURE: u8 = 8;
+
+/// Methods to construct an `OsRelease` from the D-Bus interface of systemd-hostnamed.
+impl OsRelease {
+    /// Query the operating system identity from the `org.freedesktop.hostname1` D-Bus service of systemd-hostnamed.
+    ///
+    /// This reads the `OperatingSystemPrettyName`, `OperatingSystemCPEName`, `HomeURL` and
+    /// `OperatingSystemSupportEnd` properties from the system bus, and converts them as in
+    /// [`Self::from_hostname_properties()`].
+    /// This is useful in sandboxes such as Flatpak, where `/etc/os-release` describes the runtime
+    /// rather than the host but the system bus is reachable.
+    /// Properties not provided by the running version of systemd-hostnamed are omitted.
+    ///
+    /// The bus is located with `DBUS_SYSTEM_BUS_ADDRESS` (only `unix:path=` addresses are supported),
+    /// falling back to `/run/dbus/system_bus_socket`.
+    ///
+    /// Returns [`Error::Open`] if the bus is not available, and [`Error::Read`] if the call fails.
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "dbus"))))]
+    pub fn from_dbus() -> Result<Self, Error> {
+        let path = system_bus_path();
+        let stream = UnixStream::connect(&path).map_err(|err| Error::Open {
+            path: path.clone(),
+            err,
+        })?;
+        let properties = query(stream).map_err(|err| Error::read(err).with_path(&path))?;
+        Ok(OsRelease::from_hostname_properties(&properties))
+    }
+}
+
+fn system_bus_path() -> PathBuf {
+    env::var("DBUS_SYSTEM_BUS_ADDRESS")
+        .ok()
+        .and_then(|address| {
+            address.split(';').find_map(|address| {
+                address
+                    .strip_prefix("unix:")?
+                    .split(',')
+                    .find_map(|param| param.strip_prefix("path="))
+                    .map(PathBuf::from)
+            })
+        })
+        .unwrap_or_else(|| PathBuf::from(SYSTEM_BUS_SOCKET))
+}
+
+/// Read the operating system properties of `org.freedesktop.hostname1`.
+fn query(stream: UnixStream) -> io::Result<HostnameProperties> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    let mut connection = Connection {
+        reader: BufReader::new(stream.try_clone()?),
+        writer: stream,
+        serial: 0,
+    };
+    connection.authenticate()?;
+    connection
+        .call(
+            "org.freedesktop.DBus",
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus",
+            "Hello",
+            &[],
+        )?
+        .ok_or_else(|| io::Error::other("D-Bus error: Hello failed"))?;
+
+    let mut properties = HostnameProperties::new();
+    if let Some(Variant::String(value)) = connection.get_property("OperatingSystemPrettyName")? {
+        properties = properties.pretty_name(value);
+    }
+    if let Some(Variant::String(value)) = connection.get_property("OperatingSystemCPEName")? {
+        properties = properties.cpe_name(value);
+    }
+    if let Some(Variant::String(value)) = connection.get_property("HomeURL")? {
+        properties = properties.home_url(value);
+    }
+    if let Some(Variant::U64(value)) = connection.get_property("OperatingSystemSupportEnd")? {
+        properties = properties.support_end_usec(value);
+    }
+    Ok(properties)
+}
+
+/// A property value of a type used by the queried properties.
+#[derive(Debug, PartialEq, Eq)]
+enum Variant {
+    String(String),
+    U64(u64),
+    Other,
+}
+
+struct Connection<R, W> {
+    reader: R,
+    writer: W,
+    serial: u32,
+}
+
+impl<R: BufRead, W: Write> Connection<R, W> {
+    /// Authenticate with the `EXTERNAL` mechanism, using the credentials of the socket.
+    fn authenticate(&mut self) -> io::Result<()> {
+        // SAFETY: `getuid()` is always successful.
+        let uid = unsafe { libc::getuid() };
+        let uid = uid
+            .to_string()
+            .bytes()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+        write!(self.writer, "\0AUTH EXTERNAL {uid}\r\n")?;
+        let mut line = String::new();
+        self.reader.read_line(&mut line)?;
+        if !line.starts_with("OK ") {
+            return Err(io::Error::other(format!(
+                "D-Bus authentication failed: {}",
+                line.trim_end()
+            )));
+        }
+        self.writer.write_all(b"BEGIN\r\n")
+    }
+
+    /// Call `org.freedesktop.DBus.Properties.Get` for a property of `org.freedesktop.hostname1`.
+    ///
+    /// Returns `None` if the call fails, e.g. because the property is unknown.
+    fn get_property(&mut self, name: &str) -> io::Result<Option<Variant>> {
+        let Some(reply) = self.call(
+            "org.freedesktop.hostname1",
+            "/org/freedesktop/hostname1",
+            "org.freedesktop.DBus.Properties",
+            "Get",
+            &["org.freedesktop.hostname1", name],
+        )?
+        else {
+            return Ok(None);
+        };
+        Decoder::new(&reply.body, reply.big_endian)
+            .variant()
+            .map(Some)
+    }
+
+    /// Call a method with string arguments, and return the reply.
+    ///
+    /// Returns `None` if the reply is an error.
+    fn call(
+        &mut self,
+        destination: &str,
+        path: &str,
+        interface: &str,
+        member: &str,
+        args: &[&str],
+    ) -> io::Result<Option<Message>> {
+        self.serial += 1;
+        let message = method_call(self.serial, destination, path, interface, member, args);
+        self.writer.write_all(&message)?;
+        loop {
+            // Skip signals such as `NameAcquired`, and replies to other calls.
+            let message = read_message(&mut self.reader)?;
+            if message.reply_serial == Some(self.serial) {
+                match message.kind {
+                    METHOD_RETURN => return Ok(Some(message)),
+                    ERROR => return Ok(None),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Marshal a method call with string arguments in little endian.
+fn method_call(
+    serial: u32,
+    destination: &str,
+    path: &str,
+    interface: &str,
+    member: &str,
+    args: &[&str],
+) -> Vec<u8> {
+    let mut body = Encoder::default();
+    for arg in args {
+        body.string(arg);
+    }
+    let signature = "s".repeat(args.len());
+
+    let mut message = Encoder::default();
+    message.buf.extend_from_slice(&[b'l', METHOD_CALL, 0, 1]);
+    message.u32(body.buf.len() as u32);
+    message.u32(serial);
+    let fields_len = message.buf.len();
+    message.u32(0);
+    let fields_start = message.buf.len();
+    message.header_field(FIELD_PATH, "o", |e| e.string(path));
+    message.header_field(FIELD_DESTINATION, "s", |e| e.string(destination));
+    message.header_field(FIELD_INTERFACE, "s", |e| e.string(interface));
+    message.header_field(FIELD_MEMBER, "s", |e| e.string(member));
+    if !signature.is_empty() {
+        message.header_field(FIELD_SIGNATURE, "g", |e| e.signature(&signature));
+    }
+    let len = (message.buf.len() - fields_start) as u32;
+    message.buf[fields_len..fields_start].copy_from_slice(&len.to_le_bytes());
+    message.align(8);
+    message.buf.extend_from_slice(&body.buf);
+    message.buf
+}
+
+#[derive(Debug)]
+struct Message {
+    kind: u8,
+    big_endian: bool,
+    reply_serial: Option<u32>,
+    body: Vec<u8>,
+}
+
+fn read_message(reader: &mut impl Read) -> io::Result<Message> {
+    let mut header = vec![0; 16];
+    reader.read_exact(&mut header)?;
+    let big_endian = match header[0] {
+        b'l' => false,
+        b'B' => true,
+        _ => return Err(invalid_data("invalid endianness")),
+    };
+    let mut decoder = Decoder::new(&header, big_endian);
+    decoder.pos = 4;
+    let body_len = decoder.u32()? as usize;
+    decoder.pos = 12;
+    let fields_len = decoder.u32()? as usize;
+
+    // The header is padded to a multiple of 8 bytes.
+    header.resize((16 + fields_len).next_multiple_of(8), 0);
+    reader.read_exact(&mut header[16..])?;
+    let mut body = vec![0; body_len];
+    reader.read_exact(&mut body)?;
+
+    let mut decoder = Decoder::new(&header[..16 + fields_len], big_endian);
+    decoder.pos = 16;
+    let mut reply_serial = None;
+    while decoder.pos < decoder.buf.len() {
+        decoder.align(8)?;
+        let code = decoder.u8()?;
+        let signature = decoder.signature()?;
+        match signature.as_str() {
+            "u" if code == FIELD_REPLY_SERIAL => reply_serial = Some(decoder.u32()?),
+            "u" => _ = decoder.u32()?,
+            "s" | "o" => _ = decoder.string()?,
+            "g" => _ = decoder.signature()?,
+            _ => return Err(invalid_data("unexpected header field type")),
+        }
+    }
+
+    Ok(Message {
+        kind: header[1],
+        big_endian,
+        reply_serial,
+        body,
+    })
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("D-Bus: {message}"))
+}
+
+#[derive(Debug, Default)]
+struct Encoder {
+    buf: Vec<u8>,
+}
+
+impl Encoder {
+    fn align(&mut self, n: usize) {
+        self.buf.resize(self.buf.len().next_multiple_of(n), 0);
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.align(4);
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn string(&mut self, value: &str) {
+        self.u32(value.len() as u32);
+        self.buf.extend_from_slice(value.as_bytes());
+        self.buf.push(0);
+    }
+
+    fn signature(&mut self, value: &str) {
+        self.buf.push(value.len() as u8);
+        self.buf.extend_from_slice(value.as_bytes());
+        self.buf.push(0);
+    }
+
+    fn header_field(&mut self, code: u8, signature: &str, value: impl FnOnce(&mut Self)) {
+        self.align(8);
+        self.buf.push(code);
+        self.signature(signature);
+        value(self);
+    }
+}
+
+struct Decoder<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    big_endian: bool,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(buf: &'a [u8], big_endian: bool) -> Self {
+        Self {
+            buf,
+            pos: 0,
+            big_endian,
+        }
+    }
+
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        let bytes = self
+            .buf
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| invalid_data("message truncated"))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn align(&mut self, n: usize) -> io::Result<()> {
+        let pos = self.pos.next_multiple_of(n);
+        self.take(pos - self.pos)?;
+        Ok(())
+    }
+
+    fn u8(&mut self) -> io::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        self.align(4)?;
+        let bytes = self.take(4)?.try_into().unwrap();
+        Ok(if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        self.align(8)?;
+        let bytes = self.take(8)?.try_into().unwrap();
+        Ok(if self.big_endian {
+            u64::from_be_bytes(bytes)
+        } else {
+            u64::from_le_bytes(bytes)
+        })
+    }
+
+    fn bytes_nul(&mut self, len: usize) -> io::Result<String> {
+        let bytes = self.take(len + 1)?;
+        String::from_utf8(bytes[..len].to_vec()).map_err(|_| invalid_data("invalid UTF-8"))
+    }
+
+    fn string(&mut self) -> io::Result<String> {
+        let len = self.u32()? as usize;
+        self.bytes_nul(len)
+    }
+
+    fn signature(&mut self) -> io::Result<String> {
+        let len = self.u8()? as usize;
+        self.bytes_nul(len)
+    }
+
+    fn variant(&mut self) -> io::Result<Variant> {
+        Ok(match self.signature()?.as_str() {
+            "s" => Variant::String(self.string()?),
+            "t" => Variant::U64(self.u64()?),
+            _ => Variant::Other,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+
+    use super::*;
+
+    /// Marshal a reply to `Properties.Get` in big endian, to exercise both byte orders.
+    fn get_reply(serial: u32, reply_serial: u32, value: Option<&Variant>) -> Vec<u8> {
+        let mut body = vec![];
+        match value {
+            Some(Variant::String(s)) => {
+                body.extend_from_slice(&[1, b's', 0, 0]);
+                body.extend_from_slice(&(s.len() as u32).to_be_bytes());
+                body.extend_from_slice(s.as_bytes());
+                body.push(0);
+            }
+            Some(Variant::U64(t)) => {
+                body.extend_from_slice(&[1, b't', 0, 0, 0, 0, 0, 0]);
+                body.extend_from_slice(&t.to_be_bytes());
+            }
+            Some(Variant::Other) => body.extend_from_slice(&[1, b'b', 0, 0, 0, 0, 0, 1]),
+            None => {}
+        }
+        let kind = if value.is_some() {
+            METHOD_RETURN
+        } else {
+            ERROR
+        };
+        let mut message = vec![b'B', kind, 0, 1];
+        message.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        message.extend_from_slice(&serial.to_be_bytes());
+        message.extend_from_slice(&8u32.to_be_bytes());
+        message.extend_from_slice(&[FIELD_REPLY_SERIAL, 1, b'u', 0]);
+        message.extend_from_slice(&reply_serial.to_be_bytes());
+        message.extend_from_slice(&body);
+        message
+    }
+
+    #[test]
+    fn test_method_call() {
+        let message = method_call(
+            7,
+            "org.freedesktop.hostname1",
+            "/org/freedesktop/hostname1",
+            "org.freedesktop.DBus.Properties",
+            "Get",
+            &["org.freedesktop.hostname1", "HomeURL"],
+        );
+        assert_eq!(&message[..4], b"l\x01\x00\x01");
+
+        let message = read_message(&mut &message[..]).unwrap();
+        assert_eq!(message.kind, METHOD_CALL);
+        assert_eq!(message.reply_serial, None);
+        let mut body = Decoder::new(&message.body, false);
+        assert_eq!(body.string().unwrap(), "org.freedesktop.hostname1");
+        assert_eq!(body.string().unwrap(), "HomeURL");
+        assert_eq!(body.pos, message.body.len());
+    }
+
+    #[test]
+    fn test_query() {
+        let (client, mut server) = UnixStream::pair().unwrap();
+        let server = thread::spawn(move || {
+            let mut reader = BufReader::new(server.try_clone().unwrap());
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            assert!(line.starts_with("\0AUTH EXTERNAL "));
+            server.write_all(b"OK 0123456789abcdef\r\n").unwrap();
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+            assert_eq!(line, "BEGIN\r\n");
+
+            let replies = [
+                Some(Variant::String(":1.42".into())),
+                Some(Variant::String(
+                    "Fedora Linux 40 (Workstation Edition)".into(),
+                )),
+                Some(Variant::String("cpe:/o:fedoraproject:fedora:40".into())),
+                Some(Variant::String(String::new())),
+                None,
+            ];
+            for (serial, reply) in (100..).zip(&replies) {
+                let call = read_message(&mut reader).unwrap();
+                assert_eq!(call.kind, METHOD_CALL);
+                let call_serial = serial - 99;
+                // A reply to another call before the reply is skipped.
+                server
+                    .write_all(&get_reply(serial + 10, 0, Some(&Variant::Other)))
+                    .unwrap();
+                server
+                    .write_all(&get_reply(serial, call_serial, reply.as_ref()))
+                    .unwrap();
+            }
+        });
+
+        let properties = query(client).unwrap();
+        server.join().unwrap();
+        let os_release = OsRelease::from_hostname_properties(&properties);
+        assert_eq!(
+            os_release.pretty_name(),
+            "Fedora Linux 40 (Workstation Edition)"
+        );
+        assert_eq!(
+            os_release.cpe_name(),
+            Some("cpe:/o:fedoraproject:fedora:40")
+        );
+        assert_eq!(os_release.get_value("HOME_URL"), None);
+        assert_eq!(os_release.get_value("SUPPORT_END"), None);
+    }
+
+    #[test]
+    fn test_variant() {
+        let reply = get_reply(1, 1, Some(&Variant::U64(1_747_094_400_000_000)));
+        let message = read_message(&mut &reply[..]).unwrap();
+        assert_eq!(message.kind, METHOD_RETURN);
+        assert_eq!(message.reply_serial, Some(1));
+        assert_eq!(
+            Decoder::new(&message.body, true).variant().unwrap(),
+            Variant::U64(1_747_094_400_000_000)
+        );
+    }
+}
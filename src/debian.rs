@@ -0,0 +1,148 @@
+use crate::OsRelease;
+
+/// A release of Debian, associating its version with its code name.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::DebianRelease;
+///
+/// let release = DebianRelease::from_codename("bookworm").unwrap();
+/// assert_eq!(release.version(), "12");
+///
+/// let release = DebianRelease::from_version("11.9").unwrap();
+/// assert_eq!(release.codename(), "bullseye");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DebianRelease {
+    version: &'static str,
+    codename: &'static str,
+}
+
+impl DebianRelease {
+    /// All Debian releases known to this crate, in ascending order.
+    ///
+    /// This includes releases which have a code name assigned but are not released yet.
+    pub const ALL: &'static [Self] = DEBIAN_RELEASES;
+
+    /// Returns the release with the given code name.
+    pub fn from_codename(codename: &str) -> Option<&'static Self> {
+        Self::ALL.iter().find(|r| r.codename == codename)
+    }
+
+    /// Returns the release with the given version.
+    ///
+    /// Point releases such as `12.5` are mapped to their major release.
+    pub fn from_version(version: &str) -> Option<&'static Self> {
+        Self::ALL.iter().find(|r| {
+            version
+                .strip_prefix(r.version)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        })
+    }
+
+    /// Returns the major version of the release, as used in `VERSION_ID=`.
+    pub const fn version(&self) -> &'static str {
+        self.version
+    }
+
+    /// Returns the code name of the release, as used in `VERSION_CODENAME=`.
+    pub const fn codename(&self) -> &'static str {
+        self.codename
+    }
+}
+
+const fn release(version: &'static str, codename: &'static str) -> DebianRelease {
+    DebianRelease { version, codename }
+}
+
+const DEBIAN_RELEASES: &[DebianRelease] = &[
+    release("1.1", "buzz"),
+    release("1.2", "rex"),
+    release("1.3", "bo"),
+    release("2.0", "hamm"),
+    release("2.1", "slink"),
+    release("2.2", "potato"),
+    release("3.0", "woody"),
+    release("3.1", "sarge"),
+    release("4", "etch"),
+    release("5", "lenny"),
+    release("6", "squeeze"),
+    release("7", "wheezy"),
+    release("8", "jessie"),
+    release("9", "stretch"),
+    release("10", "buster"),
+    release("11", "bullseye"),
+    release("12", "bookworm"),
+    release("13", "trixie"),
+    release("14", "forky"),
+    release("15", "duke"),
+];
+
+/// Methods to get information about Debian releases.
+impl OsRelease {
+    /// Returns the Debian release the operating system is, or is based on.
+    ///
+    /// `DEBIAN_CODENAME=` is used if set, as by some Debian derivatives.
+    /// Otherwise, if [`Self::id()`] is `debian`, the release is looked up by `VERSION_CODENAME=` and then by `VERSION_ID=`,
+    /// so that the version can be recovered for testing releases which set only `VERSION_CODENAME=`.
+    ///
+    /// Returns `None` for unstable (`sid`), which has no version.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// PRETTY_NAME="Debian GNU/Linux trixie/sid"
+    /// NAME="Debian GNU/Linux"
+    /// VERSION_CODENAME=trixie
+    /// ID=debian
+    /// "#).unwrap();
+    /// assert_eq!(os_release.debian_release().unwrap().version(), "13");
+    ///
+    /// let os_release = OsRelease::from_str("ID=debian\nVERSION_ID=12\n").unwrap();
+    /// assert_eq!(os_release.debian_release().unwrap().codename(), "bookworm");
+    /// ```
+    pub fn debian_release(&self) -> Option<&'static DebianRelease> {
+        if let Some(codename) = self.debian_codename() {
+            return DebianRelease::from_codename(codename);
+        }
+        if self.id() != "debian" {
+            return None;
+        }
+        self.version_codename()
+            .and_then(DebianRelease::from_codename)
+            .or_else(|| self.version_id().and_then(DebianRelease::from_version))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_debian_releases() {
+        for (i, release) in DebianRelease::ALL.iter().enumerate() {
+            assert_eq!(
+                DebianRelease::from_codename(release.codename()),
+                Some(release)
+            );
+            assert_eq!(
+                DebianRelease::from_version(release.version()),
+                Some(release)
+            );
+            if i > 0 {
+                let prev = &DebianRelease::ALL[i - 1];
+                assert!(crate::compare_versions(prev.version(), release.version()).is_lt());
+            }
+        }
+
+        assert_eq!(DebianRelease::from_version("1").map(|r| r.codename()), None);
+        assert_eq!(DebianRelease::from_version("1.10"), None);
+        assert_eq!(DebianRelease::from_codename("sid"), None);
+    }
+}
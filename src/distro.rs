@@ -0,0 +1,438 @@
+use crate::{OsRelease, ReleaseType};
+
+/// A package manager used by an operating system distribution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PackageManager {
+    /// `apt` (Debian and derivatives).
+    Apt,
+    /// `dnf` (Fedora, RHEL and derivatives).
+    Dnf,
+    /// `zypper` (openSUSE and SUSE Linux Enterprise).
+    Zypper,
+    /// `pacman` (Arch Linux and derivatives).
+    Pacman,
+    /// `apk` (Alpine Linux and derivatives).
+    Apk,
+    /// `emerge` (Gentoo).
+    Portage,
+    /// `nix` (NixOS).
+    Nix,
+    /// `xbps-install` (Void Linux).
+    Xbps,
+    /// `eopkg` (Solus).
+    Eopkg,
+    /// `swupd` (Clear Linux OS).
+    Swupd,
+    /// `slackpkg` (Slackware).
+    Slackpkg,
+}
+
+impl PackageManager {
+    /// Returns the name of the command used to install packages.
+    pub const fn command(self) -> &'static str {
+        match self {
+            Self::Apt => "apt",
+            Self::Dnf => "dnf",
+            Self::Zypper => "zypper",
+            Self::Pacman => "pacman",
+            Self::Apk => "apk",
+            Self::Portage => "emerge",
+            Self::Nix => "nix",
+            Self::Xbps => "xbps-install",
+            Self::Eopkg => "eopkg",
+            Self::Swupd => "swupd",
+            Self::Slackpkg => "slackpkg",
+        }
+    }
+}
+
+/// A family of operating system distributions sharing packaging and programming interfaces.
+///
+/// See [`OsRelease::family()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum OsFamily {
+    /// Debian and derivatives, such as Ubuntu and Linux Mint.
+    Debian,
+    /// Fedora, Red Hat Enterprise Linux and derivatives, such as CentOS, Rocky Linux and AlmaLinux.
+    RedHat,
+    /// openSUSE and SUSE Linux Enterprise.
+    Suse,
+    /// Arch Linux and derivatives, such as Manjaro.
+    Arch,
+    /// Alpine Linux and derivatives, such as postmarketOS.
+    Alpine,
+    /// Gentoo.
+    Gentoo,
+    /// NixOS.
+    NixOS,
+    /// Void Linux.
+    Void,
+    /// Solus.
+    Solus,
+    /// Clear Linux OS.
+    ClearLinux,
+    /// Slackware.
+    Slackware,
+}
+
+impl OsFamily {
+    /// Returns the family with the given identifier, as returned by [`KnownDistro::family()`] or used in `ID_LIKE=`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use etc_os_release::OsFamily;
+    ///
+    /// assert_eq!(OsFamily::from_family_id("rhel"), Some(OsFamily::RedHat));
+    /// assert_eq!(OsFamily::from_family_id("ubuntu"), None);
+    /// ```
+    pub const fn from_family_id(id: &str) -> Option<Self> {
+        let mut i = 0;
+        while i < FAMILIES.len() {
+            if str_eq(FAMILIES[i].0, id) {
+                return Some(FAMILIES[i].1);
+            }
+            i += 1;
+        }
+        None
+    }
+}
+
+/// Identifiers of the families, as used in `ID_LIKE=`.
+const FAMILIES: &[(&str, OsFamily)] = &[
+    ("debian", OsFamily::Debian),
+    ("fedora", OsFamily::RedHat),
+    ("rhel", OsFamily::RedHat),
+    ("suse", OsFamily::Suse),
+    ("opensuse", OsFamily::Suse),
+    ("arch", OsFamily::Arch),
+    ("alpine", OsFamily::Alpine),
+    ("gentoo", OsFamily::Gentoo),
+    ("nixos", OsFamily::NixOS),
+    ("void", OsFamily::Void),
+    ("solus", OsFamily::Solus),
+    ("clear-linux-os", OsFamily::ClearLinux),
+    ("slackware", OsFamily::Slackware),
+];
+
+/// An operating system distribution known to this crate.
+///
+/// The table of known distributions is available as [`KnownDistro::ALL`], and can be queried in const contexts.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::{KnownDistro, PackageManager};
+///
+/// const UBUNTU: &KnownDistro = match KnownDistro::from_id("ubuntu") {
+///     Some(distro) => distro,
+///     None => panic!("ubuntu is not known"),
+/// };
+///
+/// assert_eq!(UBUNTU.family(), "debian");
+/// assert_eq!(UBUNTU.package_manager(), PackageManager::Apt);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KnownDistro {
+    id: &'static str,
+    name: &'static str,
+    family: &'static str,
+    package_manager: PackageManager,
+    rolling: bool,
+}
+
+impl KnownDistro {
+    /// All distributions known to this crate.
+    pub const ALL: &'static [Self] = KNOWN_DISTROS;
+
+    /// Returns the known distribution with the given `ID=`.
+    pub const fn from_id(id: &str) -> Option<&'static Self> {
+        let mut i = 0;
+        while i < Self::ALL.len() {
+            if str_eq(Self::ALL[i].id, id) {
+                return Some(&Self::ALL[i]);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Returns the `ID=` of the distribution.
+    pub const fn id(&self) -> &'static str {
+        self.id
+    }
+
+    /// Returns the human-readable name of the distribution.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the identifier of the family this distribution belongs to.
+    ///
+    /// The identifier is the `ID=` of the distribution at the root of the family, as it appears in `ID_LIKE=`.
+    /// For example, the family of `ubuntu` is `debian`, the family of `debian` is `debian` itself,
+    /// and the family of `opensuse-leap` is `suse`.
+    pub const fn family(&self) -> &'static str {
+        self.family
+    }
+
+    /// Returns the [`OsFamily`] this distribution belongs to.
+    pub const fn os_family(&self) -> Option<OsFamily> {
+        OsFamily::from_family_id(self.family)
+    }
+
+    /// Returns the package manager used by the distribution.
+    pub const fn package_manager(&self) -> PackageManager {
+        self.package_manager
+    }
+
+    /// Returns `true` if the distribution is a rolling release, continuously updated rather than released in versions.
+    pub const fn is_rolling(&self) -> bool {
+        self.rolling
+    }
+
+    const fn rolling(self) -> Self {
+        Self {
+            rolling: true,
+            ..self
+        }
+    }
+}
+
+/// Methods to get information about known distributions.
+impl OsRelease {
+    /// Returns the known distribution matching [`Self::id()`], if any.
+    pub fn known_distro(&self) -> Option<&'static KnownDistro> {
+        KnownDistro::from_id(self.id())
+    }
+
+    /// Returns the family the operating system belongs to.
+    ///
+    /// The identifiers of [`Self::id_chain()`] are looked up in order, first as known distributions and then as family identifiers,
+    /// so that derivatives not known to this crate are classified by their `ID_LIKE=`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::{OsFamily, OsRelease};
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// ID=rocky
+    /// ID_LIKE="rhel centos fedora"
+    /// "#).unwrap();
+    /// assert_eq!(os_release.family(), Some(OsFamily::RedHat));
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// ID=my-own-distro
+    /// ID_LIKE="ubuntu"
+    /// "#).unwrap();
+    /// assert_eq!(os_release.family(), Some(OsFamily::Debian));
+    /// ```
+    pub fn family(&self) -> Option<OsFamily> {
+        self.id_chain()
+            .find_map(|id| match KnownDistro::from_id(id) {
+                Some(distro) => distro.os_family(),
+                None => OsFamily::from_family_id(id),
+            })
+    }
+
+    /// Returns `true` if the operating system is a rolling release.
+    ///
+    /// The following hints are checked in order:
+    ///
+    /// 1. [`Self::release_type()`]: [`ReleaseType::Stable`] or [`ReleaseType::Lts`] means a regular release.
+    ///    `rolling`, which is not defined by os-release(5), is taken as a rolling release.
+    /// 2. [`Self::known_distro()`]: known rolling releases, such as Arch Linux, Gentoo and openSUSE Tumbleweed.
+    /// 3. `VERSION_ID=`: operating systems without a version, such as Debian testing, are considered rolling releases.
+    ///
+    /// The last hint is a heuristic: a minimal file which sets only `ID=`, such as `ID=alpine`,
+    /// is also considered a rolling release.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str("ID=arch\nBUILD_ID=rolling\n").unwrap();
+    /// assert!(os_release.is_rolling_release());
+    ///
+    /// let os_release = OsRelease::from_str("ID=opensuse-tumbleweed\nVERSION_ID=20240101\n").unwrap();
+    /// assert!(os_release.is_rolling_release());
+    ///
+    /// let os_release = OsRelease::from_str("ID=debian\nVERSION_ID=12\n").unwrap();
+    /// assert!(!os_release.is_rolling_release());
+    /// ```
+    pub fn is_rolling_release(&self) -> bool {
+        match self.release_type() {
+            Some(ReleaseType::Stable | ReleaseType::Lts) => return false,
+            Some(ReleaseType::Other(s)) if s == "rolling" => return true,
+            _ => {}
+        }
+        self.known_distro().is_some_and(KnownDistro::is_rolling) || self.version_id().is_none()
+    }
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn distro(
+    id: &'static str,
+    name: &'static str,
+    family: &'static str,
+    package_manager: PackageManager,
+) -> KnownDistro {
+    KnownDistro {
+        id,
+        name,
+        family,
+        package_manager,
+        rolling: false,
+    }
+}
+
+const KNOWN_DISTROS: &[KnownDistro] = {
+    use PackageManager::*;
+    &[
+        distro("debian", "Debian", "debian", Apt),
+        distro("ubuntu", "Ubuntu", "debian", Apt),
+        distro("linuxmint", "Linux Mint", "debian", Apt),
+        distro("pop", "Pop!_OS", "debian", Apt),
+        distro("elementary", "elementary OS", "debian", Apt),
+        distro("kali", "Kali Linux", "debian", Apt).rolling(),
+        distro("raspbian", "Raspbian", "debian", Apt),
+        distro("devuan", "Devuan", "debian", Apt),
+        distro("zorin", "Zorin OS", "debian", Apt),
+        distro("neon", "KDE neon", "debian", Apt),
+        distro("fedora", "Fedora Linux", "fedora", Dnf),
+        distro("rhel", "Red Hat Enterprise Linux", "fedora", Dnf),
+        distro("centos", "CentOS", "fedora", Dnf),
+        distro("rocky", "Rocky Linux", "fedora", Dnf),
+        distro("almalinux", "AlmaLinux", "fedora", Dnf),
+        distro("ol", "Oracle Linux", "fedora", Dnf),
+        distro("amzn", "Amazon Linux", "fedora", Dnf),
+        distro("opensuse", "openSUSE", "suse", Zypper),
+        distro("opensuse-leap", "openSUSE Leap", "suse", Zypper),
+        distro("opensuse-tumbleweed", "openSUSE Tumbleweed", "suse", Zypper).rolling(),
+        distro("sles", "SUSE Linux Enterprise Server", "suse", Zypper),
+        distro("sled", "SUSE Linux Enterprise Desktop", "suse", Zypper),
+        distro("arch", "Arch Linux", "arch", Pacman).rolling(),
+        distro("manjaro", "Manjaro Linux", "arch", Pacman).rolling(),
+        distro("endeavouros", "EndeavourOS", "arch", Pacman).rolling(),
+        distro("artix", "Artix Linux", "arch", Pacman).rolling(),
+        distro("alpine", "Alpine Linux", "alpine", Apk),
+        distro("postmarketos", "postmarketOS", "alpine", Apk),
+        distro("gentoo", "Gentoo", "gentoo", Portage).rolling(),
+        distro("nixos", "NixOS", "nixos", Nix),
+        distro("void", "Void Linux", "void", Xbps).rolling(),
+        distro("solus", "Solus", "solus", Eopkg).rolling(),
+        distro("clear-linux-os", "Clear Linux OS", "clear-linux-os", Swupd).rolling(),
+        distro("slackware", "Slackware", "slackware", Slackpkg),
+    ]
+};
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_known_distros() {
+        for (i, distro) in KnownDistro::ALL.iter().enumerate() {
+            // IDs are unique
+            assert_eq!(KnownDistro::from_id(distro.id()), Some(distro));
+            assert!(KnownDistro::ALL[..i].iter().all(|d| d.id() != distro.id()));
+
+            // families are not nested
+            if let Some(root) = KnownDistro::from_id(distro.family()) {
+                assert_eq!(root.family(), root.id());
+            }
+
+            // families are classified
+            assert!(distro.os_family().is_some());
+        }
+
+        assert!(KnownDistro::from_id("").is_none());
+        assert!(KnownDistro::from_id("Debian").is_none());
+    }
+
+    #[test]
+    fn test_is_rolling_release() {
+        let cases = [
+            (
+                "ID=arch
+", true,
+            ),
+            (
+                "ID=arch
+RELEASE_TYPE=stable
+",
+                false,
+            ),
+            (
+                "ID=gentoo
+RELEASE_TYPE=lts
+",
+                false,
+            ),
+            (
+                "ID=debian
+VERSION_ID=12
+",
+                false,
+            ),
+            (
+                "ID=debian
+",
+                true,
+            ),
+            (
+                "ID=debian
+VERSION_ID=12
+RELEASE_TYPE=rolling
+",
+                true,
+            ),
+            (
+                "ID=fedora
+VERSION_ID=41
+RELEASE_TYPE=development
+",
+                false,
+            ),
+            (
+                "ID=fedora
+RELEASE_TYPE=development
+",
+                true,
+            ),
+            (
+                "ID=alpine
+",
+                true,
+            ),
+        ];
+        for (input, expected) in cases {
+            let os_release = OsRelease::from_str(input).unwrap();
+            assert_eq!(os_release.is_rolling_release(), expected, "{input:?}");
+        }
+    }
+}
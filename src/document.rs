@@ -0,0 +1,392 @@
+use std::{
+    convert::Infallible,
+    fmt,
+    fs::{self, File},
+    io,
+    path::Path,
+    str::FromStr,
+};
+
+use crate::{
+    construct::{decode_with, read_limited},
+    entry::{is_valid_key, strip_bom},
+    Error, OsRelease, OsReleaseEntry, OsReleaseLine, ParseOptions,
+};
+
+/// An os-release file which can be edited, preserving its formatting.
+///
+/// Lines which are not edited, including comments, empty lines and malformed lines, are written back verbatim,
+/// so that configuration management tools can change a field without rewriting the whole file.
+/// Line terminators are kept as well: each line is written with the terminator it was read with,
+/// and added lines use the terminator of the first line, so that files with CRLF line terminators stay so.
+/// The comment block directly above a field can be read and replaced with [`Self::comment()`] and [`Self::set_comment()`],
+/// e.g. to record the provenance of a value, and it moves with the field when the field is edited.
+/// Comment lines at the beginning of the file are the header of the file, not the comment of the first field.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::str::FromStr;
+///
+/// use etc_os_release::OsReleaseDocument;
+///
+/// let mut document = OsReleaseDocument::from_str("# Vendor OS\nID=vendor\nVERSION_ID='1.0'\n").unwrap();
+/// document.set("VERSION_ID", "1.1");
+/// document.set("BUILD_ID", "20241004");
+/// document.set_comment("BUILD_ID", "Set by the image build pipeline.");
+///
+/// assert_eq!(
+///     document.to_string(),
+///     "# Vendor OS\nID=vendor\nVERSION_ID=1.1\n# Set by the image build pipeline.\nBUILD_ID=20241004\n",
+/// );
+/// assert_eq!(document.comment("BUILD_ID").as_deref(), Some("Set by the image build pipeline."));
+/// assert_eq!(document.to_os_release().build_id(), Some("20241004"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OsReleaseDocument {
+    lines: Vec<DocumentLine>,
+}
+
+/// A line of an [`OsReleaseDocument`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DocumentLine {
+    /// The text of the line as read, or `None` if the line has been edited.
+    raw: Option<String>,
+    line: OsReleaseLine<'static>,
+    /// The line terminator, `"\n"` or `"\r\n"`, or empty for the last line of a file without a final newline.
+    terminator: &'static str,
+}
+
+impl DocumentLine {
+    fn new(line: OsReleaseLine<'static>, terminator: &'static str) -> Self {
+        Self {
+            raw: None,
+            line,
+            terminator,
+        }
+    }
+
+    fn key(&self) -> Option<&str> {
+        match &self.line {
+            OsReleaseLine::Entry(entry) => Some(entry.key()),
+            _ => None,
+        }
+    }
+
+    fn is_comment(&self) -> bool {
+        matches!(self.line, OsReleaseLine::Comment(_))
+    }
+}
+
+impl fmt::Display for DocumentLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.raw {
+            Some(raw) => f.write_str(raw)?,
+            None => self.line.fmt(f)?,
+        }
+        f.write_str(self.terminator)
+    }
+}
+
+impl OsReleaseDocument {
+    /// Open the os-release file at `path` for editing.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|err| Error::Open {
+            path: path.to_owned(),
+            err,
+        })?;
+        Self::from_reader(file).map_err(|e| e.with_path(path))
+    }
+
+    /// Read the os-release file from a reader for editing.
+    ///
+    /// At most [`ParseOptions::max_size()`] bytes of the default options are read.
+    pub fn from_reader(reader: impl io::Read) -> Result<Self, Error> {
+        let options = ParseOptions::new();
+        let buf = read_limited(reader, &options)?;
+        Ok(decode_with(&buf, &options)?
+            .parse()
+            .unwrap_or_else(|e: Infallible| match e {}))
+    }
+
+    /// Writes the document to the file at `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Returns the value of the field `key`.
+    ///
+    /// If the field is assigned more than once, the last assignment takes effect, as in [`OsRelease`].
+    pub fn get(&self, key: &str) -> Option<&str> {
+        let index = self.position(key)?;
+        match &self.lines[index].line {
+            OsReleaseLine::Entry(entry) => Some(entry.value()),
+            _ => None,
+        }
+    }
+
+    /// Sets the value of the field `key`.
+    ///
+    /// The last assignment of the field is rewritten in place, or a new assignment is appended to the end of the file.
+    /// Other lines are kept as they are.
+    /// Line breaks in `value` cannot be represented and are written as spaces.
+    ///
+    /// Returns `false`, leaving the document unchanged, if `key` is not a valid field name.
+    pub fn set(&mut self, key: &str, value: &str) -> bool {
+        if !is_valid_key(key) {
+            return false;
+        }
+        let line = OsReleaseLine::Entry(OsReleaseEntry::new(key, value).into_owned());
+        match self.position(key) {
+            Some(index) => {
+                let terminator = self.lines[index].terminator;
+                self.lines[index] = DocumentLine::new(line, terminator);
+            }
+            None => {
+                let newline = self.newline();
+                if let Some(last) = self.lines.last_mut() {
+                    last.terminator = newline;
+                }
+                self.lines.push(DocumentLine::new(line, newline));
+            }
+        }
+        true
+    }
+
+    /// Removes all assignments of the field `key` and the comment blocks directly above them.
+    ///
+    /// The header comment of the file is kept.
+    ///
+    /// Returns the value of the field, if it was set.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let value = self.get(key).map(str::to_owned);
+        while let Some(index) = self.position(key) {
+            let start = self.comment_start(index);
+            self.lines.drain(start..=index);
+        }
+        value
+    }
+
+    /// Returns the comment block directly above the last assignment of the field `key`.
+    ///
+    /// The lines of the block are joined with `\n`, with the leading `#` and one following space removed.
+    /// Returns `None` if the field is not set or has no comment.
+    pub fn comment(&self, key: &str) -> Option<String> {
+        let index = self.position(key)?;
+        let start = self.comment_start(index);
+        if start == index {
+            return None;
+        }
+        let lines = self.lines[start..index]
+            .iter()
+            .map(|line| match &line.line {
+                OsReleaseLine::Comment(comment) => {
+                    let comment = comment.trim_start_matches([' ', '\t']);
+                    let comment = comment.strip_prefix('#').unwrap_or(comment);
+                    comment.strip_prefix(' ').unwrap_or(comment)
+                }
+                _ => unreachable!(),
+            });
+        Some(lines.collect::<Vec<_>>().join("\n"))
+    }
+
+    /// Replaces the comment block directly above the last assignment of the field `key`.
+    ///
+    /// Each line of `comment` is written as a `# ` comment line. If `comment` is empty, the block is removed.
+    /// The block is kept when the field is changed with [`Self::set()`].
+    /// If the field is the first line of the file or directly follows the header comment,
+    /// an empty line is inserted above the block to separate it from the header.
+    ///
+    /// Returns `false` if the field is not set.
+    pub fn set_comment(&mut self, key: &str, comment: &str) -> bool {
+        let Some(index) = self.position(key) else {
+            return false;
+        };
+        let start = self.comment_start(index);
+        let newline = self.newline();
+        let separator = (!comment.is_empty()
+            && self.lines[..start].iter().all(DocumentLine::is_comment))
+        .then(|| DocumentLine::new(OsReleaseLine::Empty, newline));
+        let lines = comment.lines().map(|line| {
+            let comment = match line {
+                "" => "#".to_owned(),
+                line => format!("# {line}"),
+            };
+            DocumentLine::new(OsReleaseLine::Comment(comment.into()), newline)
+        });
+        self.lines
+            .splice(start..index, separator.into_iter().chain(lines));
+        true
+    }
+
+    /// Converts to an [`OsRelease`].
+    pub fn to_os_release(&self) -> OsRelease {
+        self.lines
+            .iter()
+            .filter_map(|line| line.line.clone().into_entry())
+            .collect()
+    }
+
+    /// Returns the line terminator for added lines: that of the first line, or `"\n"`.
+    fn newline(&self) -> &'static str {
+        self.lines
+            .iter()
+            .map(|line| line.terminator)
+            .find(|terminator| !terminator.is_empty())
+            .unwrap_or("\n")
+    }
+
+    /// Returns the index of the last assignment of the field `key`.
+    fn position(&self, key: &str) -> Option<usize> {
+        self.lines.iter().rposition(|line| line.key() == Some(key))
+    }
+
+    /// Returns the index of the first line of the comment block directly above the line at `index`.
+    ///
+    /// Returns `index` if the line has no comment block, including when the comment lines above it are the header of the file.
+    fn comment_start(&self, index: usize) -> usize {
+        self.lines[..index]
+            .iter()
+            .rposition(|line| !line.is_comment())
+            .map_or(index, |i| i + 1)
+    }
+}
+
+impl FromStr for OsReleaseDocument {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = strip_bom(s)
+            .split_inclusive('\n')
+            .map(|raw| {
+                let (raw, terminator) = if let Some(raw) = raw.strip_suffix("\r\n") {
+                    (raw, "\r\n")
+                } else if let Some(raw) = raw.strip_suffix('\n') {
+                    (raw, "\n")
+                } else {
+                    (raw, "")
+                };
+                DocumentLine {
+                    raw: Some(raw.to_owned()),
+                    line: raw.parse().unwrap_or_else(|e: Infallible| match e {}),
+                    terminator,
+                }
+            })
+            .collect();
+        Ok(Self { lines })
+    }
+}
+
+/// Formats the document as the contents of an os-release file, with the line terminator of each line.
+impl fmt::Display for OsReleaseDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            line.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_document() {
+        let input = "#  Header\n\n# About ID\n#\n  # more\nID=vendor # inline\nNAME=x\nID='dup'\n";
+        let mut document = OsReleaseDocument::from_str(input).unwrap();
+        assert_eq!(document.to_string(), input);
+        assert_eq!(document.get("ID"), Some("dup"));
+        assert_eq!(document.comment("ID"), None);
+        assert_eq!(document.comment("NAME"), None);
+
+        document.set_comment("ID", "Provenance\n\nnotes");
+        assert_eq!(
+            document.comment("ID").as_deref(),
+            Some("Provenance\n\nnotes")
+        );
+        document.set("ID", "vendor2");
+        assert_eq!(
+            document.comment("ID").as_deref(),
+            Some("Provenance\n\nnotes")
+        );
+        assert!(document
+            .to_string()
+            .ends_with("NAME=x\n# Provenance\n#\n# notes\nID=vendor2\n"));
+
+        document.remove("ID");
+        assert_eq!(document.to_string(), "#  Header\n\nNAME=x\n");
+        assert!(!document.set_comment("ID", "x"));
+
+        document.set("ID", "v");
+        document.set_comment("ID", "a");
+        document.set_comment("ID", "");
+        assert_eq!(document.comment("ID"), None);
+        let reparsed = OsReleaseDocument::from_str(&document.to_string()).unwrap();
+        assert_eq!(reparsed.to_string(), document.to_string());
+        assert_eq!(reparsed.to_os_release().id(), "v");
+    }
+
+    #[test]
+    fn test_document_set_invalid_key() {
+        let mut document = OsReleaseDocument::from_str("ID=vendor\n").unwrap();
+        for key in ["", "1ID", "VERSION ID", "ID=x", "NAME\nID"] {
+            assert!(!document.set(key, "x"), "{key:?}");
+        }
+        assert_eq!(document.to_string(), "ID=vendor\n");
+        assert!(document.set("_ID2", "x"));
+        assert_eq!(document.get("_ID2"), Some("x"));
+    }
+
+    #[test]
+    fn test_document_header() {
+        let input = "# Vendor OS\n# Copyright\nID=vendor\nNAME=x\n";
+        let mut document = OsReleaseDocument::from_str(input).unwrap();
+        assert_eq!(document.comment("ID"), None);
+
+        document.set_comment("ID", "Provenance");
+        assert_eq!(document.comment("ID").as_deref(), Some("Provenance"));
+        assert_eq!(
+            document.to_string(),
+            "# Vendor OS\n# Copyright\n\n# Provenance\nID=vendor\nNAME=x\n"
+        );
+
+        document.remove("ID");
+        assert_eq!(document.to_string(), "# Vendor OS\n# Copyright\n\nNAME=x\n");
+
+        let mut document = OsReleaseDocument::from_str(input).unwrap();
+        assert_eq!(document.remove("ID").as_deref(), Some("vendor"));
+        assert_eq!(document.to_string(), "# Vendor OS\n# Copyright\nNAME=x\n");
+
+        let mut document = OsReleaseDocument::from_str("ID=vendor\n").unwrap();
+        document.set_comment("ID", "Provenance");
+        assert_eq!(document.comment("ID").as_deref(), Some("Provenance"));
+        assert_eq!(document.to_string(), "\n# Provenance\nID=vendor\n");
+    }
+
+    #[test]
+    fn test_document_line_terminators() {
+        let input = "# Vendor\r\nID=vendor\r\nNAME=x\r\n";
+        let mut document = OsReleaseDocument::from_str(input).unwrap();
+        assert_eq!(document.to_string(), input);
+        document.set("NAME", "y");
+        document.set("VERSION_ID", "1");
+        document.set_comment("VERSION_ID", "added");
+        assert_eq!(
+            document.to_string(),
+            "# Vendor\r\nID=vendor\r\nNAME=y\r\n# added\r\nVERSION_ID=1\r\n"
+        );
+
+        let mut document = OsReleaseDocument::from_str("ID=vendor\nNAME=x").unwrap();
+        assert_eq!(document.to_string(), "ID=vendor\nNAME=x");
+        document.set("NAME", "y");
+        assert_eq!(document.to_string(), "ID=vendor\nNAME=y");
+        document.set("VERSION_ID", "1");
+        assert_eq!(document.to_string(), "ID=vendor\nNAME=y\nVERSION_ID=1\n");
+
+        let mut document = OsReleaseDocument::default();
+        assert!(document.set("ID", "vendor"));
+        assert_eq!(document.to_string(), "ID=vendor\n");
+    }
+}
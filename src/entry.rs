@@ -5,10 +5,23 @@ use chrono::NaiveDate;
 #[cfg(feature = "url")]
 use url::Url;
 
+use crate::syntax::{
+    from_ascii_boundary, parse_assignment, split_assignment, trim_quote, validate_line,
+    LineErrorKind,
+};
+pub(crate) use crate::syntax::{is_valid_key, trailing_comment_start};
+#[cfg(any(feature = "date", feature = "url"))]
+use crate::{FieldError, ValueError};
+
 /// An entry in the os-release file.
+///
+/// With the `serde` feature, this is serialized as an object with `key` and `value` fields.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OsReleaseEntry<'a> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
     key: Cow<'a, str>,
+    #[cfg_attr(feature = "serde", serde(borrow))]
     value: Cow<'a, str>,
 }
 
@@ -34,6 +47,16 @@ impl<'a> OsReleaseEntry<'a> {
         &self.value
     }
 
+    /// Converts the entry into one which owns its key and value.
+    pub(crate) fn into_owned(self) -> OsReleaseEntry<'static> {
+        OsReleaseEntry::new(self.key.into_owned(), self.value.into_owned())
+    }
+
+    /// Returns the key and value of the entry, without copying them.
+    pub(crate) fn into_parts(self) -> (Cow<'a, str>, Cow<'a, str>) {
+        (self.key, self.value)
+    }
+
     /// Returns the value of the entry as a list of strings.
     pub fn value_as_list(&self) -> impl Iterator<Item = &str> {
         self.value.split_whitespace()
@@ -42,24 +65,36 @@ impl<'a> OsReleaseEntry<'a> {
     /// Returns the value of the entry as a URL.
     #[cfg(feature = "url")]
     #[cfg_attr(docsrs, doc(cfg(feature = "url")))]
-    pub fn value_as_url(&self) -> Result<Url, url::ParseError> {
+    pub fn value_as_url(&self) -> Result<Url, FieldError> {
         Url::parse(&self.value)
+            .map_err(|e| FieldError::new(self.key(), self.value(), ValueError::Url(e)))
     }
 
     /// Returns the value of the entry as a date.
     #[cfg(feature = "date")]
     #[cfg_attr(docsrs, doc(cfg(feature = "date")))]
-    pub fn value_as_date(&self) -> Result<NaiveDate, chrono::ParseError> {
+    pub fn value_as_date(&self) -> Result<NaiveDate, FieldError> {
         NaiveDate::parse_from_str(&self.value, "%Y-%m-%d")
+            .map_err(|e| FieldError::new(self.key(), self.value(), ValueError::Date(e)))
     }
 }
 
 /// A line in the os-release file.
+///
+/// With the `serde` feature, this is serialized as `"empty"`, or an object `{"comment": ...}` or `{"entry": ...}`.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum OsReleaseLine<'a> {
-    /// An empty line or a comment.
+    /// An empty line, or a malformed line ignored by the lenient parser.
     Empty,
+    /// A comment, as written in the file, including the `#` and any leading whitespace.
+    ///
+    /// Formatting the line with [`Display`](std::fmt::Display) re-emits the comment verbatim.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Comment(Cow<'a, str>),
     /// An entry.
+    #[cfg_attr(feature = "serde", serde(borrow))]
     Entry(OsReleaseEntry<'a>),
 }
 
@@ -67,81 +102,256 @@ impl<'a> OsReleaseLine<'a> {
     /// Returns the [`OsReleaseEntry`], if any.
     pub fn into_entry(self) -> Option<OsReleaseEntry<'a>> {
         match self {
-            Self::Empty => None,
+            Self::Empty | Self::Comment(_) => None,
             Self::Entry(entry) => Some(entry),
         }
     }
+
+    pub(crate) fn into_owned(self) -> OsReleaseLine<'static> {
+        match self {
+            Self::Empty => OsReleaseLine::Empty,
+            Self::Comment(comment) => OsReleaseLine::Comment(comment.into_owned().into()),
+            Self::Entry(entry) => OsReleaseLine::Entry(entry.into_owned()),
+        }
+    }
+}
+
+impl OsReleaseLine<'static> {
+    /// Parse a line from the os-release file, rejecting malformed lines.
+    ///
+    /// Unlike [`FromStr::from_str()`], which assumes that the line is well-formed,
+    /// this function checks that the line is a valid shell-compatible variable assignment.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use etc_os_release::{LineErrorKind, OsReleaseLine};
+    ///
+    /// assert!(OsReleaseLine::parse_strict(r#"NAME="Fedora Linux""#).is_ok());
+    /// assert_eq!(
+    ///     OsReleaseLine::parse_strict("NAME=Fedora Linux"),
+    ///     Err(LineErrorKind::UnquotedSpecialCharacter(' ')),
+    /// );
+    /// ```
+    pub fn parse_strict(line: &str) -> Result<Self, LineErrorKind> {
+        validate_line(line)?;
+        Ok(Self::from_str(line).unwrap_or_else(|e| match e {}))
+    }
 }
 
 impl FromStr for OsReleaseLine<'static> {
     type Err = Infallible;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(parse_line(s).map_or(Self::Empty, Self::Entry))
+        Ok(classify_line(s).into_owned())
     }
 }
 
-/// Parse a line from the os-release file.
+/// Parse the lines of the os-release file, borrowing from `s`.
 ///
-/// Returns `None` if the line is empty or a comment.
-/// Otherwise, returns the key and value.
+/// One [`OsReleaseLine`] is yielded per line, including empty lines and comments,
+/// so that the results can be matched against the line numbers of the input.
+/// Keys and values are borrowed from `s` unless values have to be unescaped.
+///
+/// For simplicity, this function assumes that the file is well-formed.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::{parse_lines, OsReleaseLine};
+///
+/// let lines = parse_lines("# comment\nNAME=\"Fedora Linux\"\n").collect::<Vec<_>>();
+/// assert_eq!(lines.len(), 2);
+/// assert_eq!(lines[0], OsReleaseLine::Comment("# comment".into()));
+/// let OsReleaseLine::Entry(entry) = &lines[1] else { unreachable!() };
+/// assert_eq!((entry.key(), entry.value()), ("NAME", "Fedora Linux"));
+/// ```
+pub fn parse_lines(s: &str) -> impl Iterator<Item = OsReleaseLine<'_>> {
+    strip_bom(s).lines().map(classify_line)
+}
+
+/// Parse a line from the os-release file into an [`OsReleaseLine`], keeping comments.
 ///
 /// For simplicity, this function assumes that the file is well-formed.
-fn parse_line(line: &str) -> Option<OsReleaseEntry<'static>> {
-    if line.is_empty() || line.starts_with('#') {
-        return None;
+fn classify_line(line: &str) -> OsReleaseLine<'_> {
+    let comment = line.trim_end_matches('\r');
+    if comment.trim_start_matches([' ', '\t']).starts_with('#') {
+        return OsReleaseLine::Comment(comment.into());
     }
+    parse_line(line).map_or(OsReleaseLine::Empty, OsReleaseLine::Entry)
+}
 
-    let (key, value) = line.split_once('=')?;
+/// An error found in a line of the os-release file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, thiserror::Error)]
+#[error("line {line}: {kind}")]
+pub struct LineError {
+    line: usize,
+    #[source]
+    kind: LineErrorKind,
+}
 
-    let key = key.to_owned();
+impl LineError {
+    pub(crate) fn new(line: usize, kind: LineErrorKind) -> Self {
+        Self { line, kind }
+    }
 
-    let value = match trim_quote(value) {
-        // For Bourne shell compatibility, don't unescape single-quoted values.
-        (value, Some('\'')) => value.to_owned(),
-        // Unescape double-quoted values or unquoted values.
-        (value, _) => unescape(value),
-    };
+    /// Returns the 1-based line number of the malformed line.
+    pub fn line(&self) -> usize {
+        self.line
+    }
 
-    Some(OsReleaseEntry::new(key, value))
+    /// Returns the kind of the error.
+    pub fn kind(&self) -> &LineErrorKind {
+        &self.kind
+    }
 }
 
-/// Trim the outermost quotes from a string.
+/// Parse a line from the os-release file.
+///
+/// Returns `None` if the line is empty or a comment.
+/// Otherwise, returns the key and value.
 ///
-/// Returns the trimmed string and the quote character, if any.
+/// The key and value borrow from `line` unless the value has to be unescaped.
 ///
 /// For simplicity, this function assumes that the file is well-formed.
-fn trim_quote(value: &str) -> (&str, Option<char>) {
-    let quotes = &['"', '\''];
-    for &quote in quotes {
-        if let Some(value) = value.strip_prefix(quote) {
-            let value = value.strip_suffix(quote).unwrap_or(value);
-            return (value, Some(quote));
+pub(crate) fn parse_line(line: &str) -> Option<OsReleaseEntry<'_>> {
+    let (key, value) = parse_assignment(line)?;
+    Some(OsReleaseEntry::new(key, value))
+}
+
+/// Removes the comment at the end of an assignment line, if any.
+pub(crate) fn strip_trailing_comment(line: &str) -> &str {
+    match trailing_comment_start(line) {
+        Some(start) => &line[..start],
+        None => line,
+    }
+}
+
+/// Strips the UTF-8 byte order mark at the beginning of the file, which some Windows editors insert.
+pub(crate) fn strip_bom(s: &str) -> &str {
+    s.strip_prefix('\u{feff}').unwrap_or(s)
+}
+
+/// Splits a line of the os-release file into the key and the value, with the outermost quotes trimmed.
+///
+/// Returns `None` if the line is empty, a comment or not a variable assignment.
+/// Unlike [`parse_lines()`], escape sequences in the value are kept as they are,
+/// so that this can be evaluated at compile time.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::split_line;
+///
+/// const ENTRY: Option<(&str, &str)> = split_line(r#"NAME="Fedora \"Linux\"""#);
+/// assert_eq!(ENTRY, Some(("NAME", r#"Fedora \"Linux\""#)));
+/// assert_eq!(split_line("# comment"), None);
+/// ```
+pub const fn split_line(line: &str) -> Option<(&str, &str)> {
+    match split_assignment(line) {
+        Some((key, value)) => Some((key, trim_quote(value).0)),
+        None => None,
+    }
+}
+
+/// Returns the 1-based line number of the first structurally malformed line of an os-release file, if any.
+///
+/// A line is structurally well-formed if it is empty, a comment, or an assignment to a valid variable name
+/// whose value, if quoted, ends with the same quote.
+/// This is a subset of the checks of [`OsReleaseLine::parse_strict()`] which can be evaluated at compile time,
+/// to validate os-release files embedded in the binary.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::find_malformed_line;
+///
+/// const OS_RELEASE: &str = "NAME=\"Fedora Linux\"\nID=fedora\n";
+/// const _: () = assert!(find_malformed_line(OS_RELEASE).is_none());
+///
+/// assert_eq!(find_malformed_line("ID=fedora\nNAME=\"Fedora\n"), Some(2));
+/// assert_eq!(find_malformed_line("ID=fedora\n1D=fedora\n"), Some(2));
+/// ```
+pub const fn find_malformed_line(input: &str) -> Option<usize> {
+    let mut rest = input.as_bytes();
+    if let [0xef, 0xbb, 0xbf, tail @ ..] = rest {
+        rest = tail;
+    }
+    let mut line_number = 1;
+    while !rest.is_empty() {
+        let mut end = 0;
+        while end < rest.len() && rest[end] != b'\n' {
+            end += 1;
         }
+        let (mut line, next) = rest.split_at(end);
+        rest = match next.split_first() {
+            Some((_, next)) => next,
+            None => next,
+        };
+        while let Some((b'\r', init)) = line.split_last() {
+            line = init;
+        }
+        while let Some((b' ' | b'\t', tail)) = line.split_first() {
+            line = tail;
+        }
+
+        if !is_well_formed(from_ascii_boundary(line)) {
+            return Some(line_number);
+        }
+        line_number += 1;
     }
-    (value, None)
+    None
 }
 
-/// Unescape a string.
+/// Checks the structure of a line with leading whitespace trimmed.
+const fn is_well_formed(line: &str) -> bool {
+    if line.is_empty() || line.as_bytes()[0] == b'#' {
+        return true;
+    }
+    let Some((key, value)) = split_assignment(line) else {
+        return false;
+    };
+    if !is_valid_key(key) {
+        return false;
+    }
+
+    let mut value = value.as_bytes();
+    while let Some((b' ' | b'\t', init)) = value.split_last() {
+        value = init;
+    }
+    match value {
+        [quote @ (b'"' | b'\''), .., last] => *quote == *last,
+        [b'"' | b'\''] => false,
+        _ => true,
+    }
+}
+
+/// Quote a value for the os-release file, if needed.
 ///
-/// This function assumes that the os-release file is well-formed.
+/// Values consisting only of characters that are not special to the shell are not quoted.
+/// Other values are double-quoted, and the characters special in double quotes are escaped.
+pub(crate) fn quote(value: &str) -> Cow<'_, str> {
+    let is_plain = |c: char| c.is_ascii_alphanumeric() || "%+,-./:=@_".contains(c);
+    if !value.is_empty() && value.chars().all(is_plain) {
+        return value.into();
+    }
+    double_quote(value).into()
+}
+
+/// Quotes the value with double quotes, escaping the characters special in them.
 ///
-/// For simplicity, only simple unescaping is performed.
-fn unescape(value: &str) -> String {
-    let mut output = String::new();
-    let mut escaped = false;
+/// Line breaks cannot be represented in the line-based format, so they are replaced with spaces.
+pub(crate) fn double_quote(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('"');
     for c in value.chars() {
-        if escaped {
-            escaped = false;
-            output.push(c);
-            continue;
+        match c {
+            '"' | '\\' | '$' | '`' => output.extend(['\\', c]),
+            '\n' | '\r' => output.push(' '),
+            c => output.push(c),
         }
-        if c == '\\' {
-            escaped = true;
-            continue;
-        }
-        output.push(c);
     }
+    output.push('"');
     output
 }
 
@@ -172,5 +382,118 @@ mod test {
             parse_line(r#"A='B C\"\"'"#).unwrap(),
             entry("A", r#"B C\"\""#)
         );
+
+        // carriage return
+        assert_eq!(parse_line("A=B\r").unwrap(), entry("A", "B"));
+        assert_eq!(parse_line("A=\"B\"\r\r").unwrap(), entry("A", "B"));
+        assert!(parse_line("\r").is_none());
+
+        // blanks after the closing quote
+        assert_eq!(parse_line("A='B C' \t").unwrap(), entry("A", "B C"));
+        assert_eq!(parse_line("A=\"B\" \r").unwrap(), entry("A", "B"));
+        assert_eq!(parse_line(r"A=B\ ").unwrap(), entry("A", "B "));
+
+        // leading blanks
+        assert_eq!(parse_line("\t A=B").unwrap(), entry("A", "B"));
+        assert!(parse_line("  # A=B").is_none());
+    }
+
+    #[test]
+    fn test_find_malformed_line() {
+        assert_eq!(find_malformed_line(""), None);
+        assert_eq!(find_malformed_line("\n\n  # comment\n"), None);
+        assert_eq!(find_malformed_line("A=1\r\nB='2' \r\n  C=\"\"\n"), None);
+        assert_eq!(find_malformed_line("A=1\nB"), Some(2));
+        assert_eq!(find_malformed_line("A=1\n\nB-C=1\n"), Some(3));
+        assert_eq!(find_malformed_line("A=\"\n"), Some(1));
+        assert_eq!(find_malformed_line("A='1\"\n"), Some(1));
+        assert_eq!(find_malformed_line("\u{e9}=1\n"), Some(1));
+        assert_eq!(find_malformed_line("\u{feff}A=1\r\r\n"), None);
+
+        assert_eq!(find_malformed_line(crate::SPEC_EXAMPLE), None);
+    }
+
+    #[test]
+    fn test_split_line() {
+        assert_eq!(split_line("ID=fedora"), Some(("ID", "fedora")));
+        assert_eq!(split_line("ID='a=b'"), Some(("ID", "a=b")));
+        assert_eq!(split_line(r#"NAME="a\"b""#), Some(("NAME", r#"a\"b"#)));
+        assert_eq!(split_line("ID="), Some(("ID", "")));
+        assert_eq!(split_line("ID"), None);
+        assert_eq!(split_line(""), None);
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let lines = parse_lines("A=B\n\n# C\nD=\"E\\\"\"\r\n").collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1], OsReleaseLine::Empty);
+        assert_eq!(lines[2], OsReleaseLine::Comment("# C".into()));
+        let OsReleaseLine::Entry(entry) = &lines[0] else {
+            panic!("expected an entry");
+        };
+        assert!(matches!(entry.value, Cow::Borrowed("B")));
+        let OsReleaseLine::Entry(entry) = &lines[3] else {
+            panic!("expected an entry");
+        };
+        assert!(matches!(&entry.value, Cow::Owned(value) if value == "E\""));
+
+        let lines = parse_lines("\u{feff}VERSION_ID=40\r").collect::<Vec<_>>();
+        let OsReleaseLine::Entry(entry) = &lines[0] else {
+            panic!("expected an entry");
+        };
+        assert_eq!((entry.key(), entry.value()), ("VERSION_ID", "40"));
+    }
+
+    #[test]
+    fn test_strip_trailing_comment() {
+        assert_eq!(strip_trailing_comment("A=B # C"), "A=B");
+        assert_eq!(strip_trailing_comment(r#"A="B # C" # D"#), r#"A="B # C""#);
+        assert_eq!(strip_trailing_comment("A='B \\' # C"), "A='B \\'");
+        assert_eq!(strip_trailing_comment(r#"A="B \" # C""#), r#"A="B \" # C""#);
+        assert_eq!(strip_trailing_comment(r"A=B\ # C"), r"A=B\ # C");
+        assert_eq!(strip_trailing_comment("A=B#C"), "A=B#C");
+        assert_eq!(strip_trailing_comment("A=B C"), "A=B C");
+        assert_eq!(strip_trailing_comment("A= # C"), "A=");
+        assert_eq!(strip_trailing_comment("# A=B # C"), "# A=B # C");
+    }
+
+    #[test]
+    fn test_validate_line() {
+        use LineErrorKind::*;
+
+        assert_eq!(validate_line(""), Ok(()));
+        assert_eq!(validate_line("  # comment"), Ok(()));
+        assert_eq!(validate_line("A=B"), Ok(()));
+        assert_eq!(validate_line("A="), Ok(()));
+        assert_eq!(validate_line("_A1=22.04"), Ok(()));
+        assert_eq!(validate_line(r#"A="B C\"\$""#), Ok(()));
+        assert_eq!(validate_line(r#"A='B $C'"#), Ok(()));
+        assert_eq!(validate_line(r#"A=B\ C"#), Ok(()));
+        assert_eq!(validate_line("A=\"B\"\r"), Ok(()));
+        assert_eq!(validate_line(" \tA=\"B\" \t"), Ok(()));
+
+        assert_eq!(validate_line("A"), Err(MissingAssignment));
+        assert_eq!(validate_line("A B=C"), Err(InvalidKey("A B".into())));
+        assert_eq!(validate_line("1A=B"), Err(InvalidKey("1A".into())));
+        assert_eq!(validate_line("=B"), Err(InvalidKey("".into())));
+        assert_eq!(validate_line(r#"A="B"#), Err(UnterminatedQuote));
+        assert_eq!(validate_line(r#"A='B"#), Err(UnterminatedQuote));
+        assert_eq!(validate_line(r#"A="B\"#), Err(UnterminatedQuote));
+        assert_eq!(validate_line("A=B C"), Err(UnquotedSpecialCharacter(' ')));
+        assert_eq!(validate_line("A=$B"), Err(UnquotedSpecialCharacter('$')));
+        assert_eq!(
+            validate_line(r#"A="$B""#),
+            Err(UnescapedSpecialCharacter('$'))
+        );
+        assert_eq!(validate_line(r#"A="B"C"#), Err(TrailingCharacters));
+        // Only spaces and tabs are blanks, as in the shell.
+        assert_eq!(
+            validate_line("\u{a0}A=B"),
+            Err(InvalidKey("\u{a0}A".into()))
+        );
+        assert_eq!(validate_line("A=\"B\"\u{a0}"), Err(TrailingCharacters));
+        assert_eq!(validate_line("A=B # C"), Err(TrailingComment));
+        assert_eq!(validate_line("A='B C'\t#"), Err(TrailingComment));
     }
 }
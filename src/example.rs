@@ -0,0 +1,100 @@
+use std::str::FromStr;
+
+use crate::OsRelease;
+
+/// The example os-release file of [`os-release(5)`], as shipped by Fedora 32 Workstation Edition.
+///
+/// This is the sample used throughout the documentation of this crate.
+/// Use [`OsRelease::spec_example()`] to get it parsed.
+///
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html#Examples
+pub const SPEC_EXAMPLE: &str = r#"NAME=Fedora
+VERSION="32 (Workstation Edition)"
+ID=fedora
+VERSION_ID=32
+PRETTY_NAME="Fedora 32 (Workstation Edition)"
+ANSI_COLOR="0;38;2;60;110;180"
+LOGO=fedora-logo-icon
+CPE_NAME="cpe:/o:fedoraproject:fedora:32"
+HOME_URL="https://fedoraproject.org/"
+DOCUMENTATION_URL="https://docs.fedoraproject.org/en-US/fedora/f32/system-administrators-guide/"
+SUPPORT_URL="https://fedoraproject.org/wiki/Communicating_and_getting_help"
+BUG_REPORT_URL="https://bugzilla.redhat.com/"
+REDHAT_BUGZILLA_PRODUCT="Fedora"
+REDHAT_BUGZILLA_PRODUCT_VERSION=32
+REDHAT_SUPPORT_PRODUCT="Fedora"
+REDHAT_SUPPORT_PRODUCT_VERSION=32
+PRIVACY_POLICY_URL="https://fedoraproject.org/wiki/Legal:PrivacyPolicy"
+VARIANT="Workstation Edition"
+VARIANT_ID=workstation
+"#;
+
+/// Methods to get the example os-release file.
+impl OsRelease {
+    /// Returns the parsed [`SPEC_EXAMPLE`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::spec_example();
+    /// assert_eq!(os_release.id(), "fedora");
+    /// assert_eq!(os_release.pretty_name(), "Fedora 32 (Workstation Edition)");
+    /// ```
+    pub fn spec_example() -> Self {
+        Self::from_str(SPEC_EXAMPLE).unwrap_or_else(|e| match e {})
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_spec_example() {
+        let os_release = OsRelease::spec_example();
+        assert_eq!(os_release.len(), 19);
+        assert_eq!(os_release.name(), "Fedora");
+        assert_eq!(os_release.version(), Some("32 (Workstation Edition)"));
+        assert_eq!(os_release.id(), "fedora");
+        assert!(os_release.id_like().is_none());
+        assert_eq!(os_release.version_id(), Some("32"));
+        assert_eq!(os_release.pretty_name(), "Fedora 32 (Workstation Edition)");
+        assert_eq!(os_release.ansi_color(), Some("0;38;2;60;110;180"));
+        assert_eq!(os_release.logo(), Some("fedora-logo-icon"));
+        assert_eq!(
+            os_release.cpe_name(),
+            Some("cpe:/o:fedoraproject:fedora:32")
+        );
+        assert_eq!(os_release.variant(), Some("Workstation Edition"));
+        assert_eq!(os_release.variant_id(), Some("workstation"));
+        assert_eq!(
+            os_release.get_value("REDHAT_SUPPORT_PRODUCT"),
+            Some("Fedora")
+        );
+        assert_eq!(os_release.vendor_entries().count(), 4);
+
+        #[cfg(feature = "url")]
+        {
+            let url = |s: &str| Some(url::Url::parse(s).unwrap());
+            assert_eq!(
+                os_release.home_url().unwrap(),
+                url("https://fedoraproject.org/")
+            );
+            assert_eq!(
+                os_release.bug_report_url().unwrap(),
+                url("https://bugzilla.redhat.com/")
+            );
+            assert_eq!(
+                os_release.privacy_policy_url().unwrap(),
+                url("https://fedoraproject.org/wiki/Legal:PrivacyPolicy")
+            );
+            assert!(os_release.documentation_url().unwrap().is_some());
+            assert!(os_release.support_url().unwrap().is_some());
+        }
+
+        #[cfg(feature = "date")]
+        assert_eq!(os_release.support_end().unwrap(), None);
+    }
+}
@@ -0,0 +1,258 @@
+//! C API of this crate.
+//!
+//! The functions of this module have a stable C ABI, so that C/C++ projects and bindings for other languages
+//! can use this crate. The declarations are in `include/etc_os_release.h`, which is generated from this module
+//! with [cbindgen] (`cbindgen --output include/etc_os_release.h`); CI checks that it is up to date.
+//! To link against it, build this crate as a C library, e.g. with
+//! `cargo rustc --release --features ffi --crate-type cdylib`.
+//!
+//! [cbindgen]: https://github.com/mozilla/cbindgen
+//!
+//! # Examples
+//!
+//! ```c
+//! #include <stdio.h>
+//! #include <etc_os_release.h>
+//!
+//! int main(void) {
+//!     OsReleaseHandle *os_release;
+//!     int ret = os_release_open(&os_release);
+//!     if (ret != OS_RELEASE_OK) {
+//!         fprintf(stderr, "%s\n", os_release_strerror(ret));
+//!         return 1;
+//!     }
+//!     const char *id = os_release_get(os_release, "ID");
+//!     printf("%s\n", id != NULL ? id : "linux");
+//!     os_release_free(os_release);
+//!     return 0;
+//! }
+//! ```
+
+use std::{
+    collections::HashMap,
+    ffi::{c_char, c_int, CStr, CString},
+    ptr,
+};
+
+use crate::{Error, OsRelease};
+
+/// The function succeeded.
+pub const OS_RELEASE_OK: c_int = 0;
+/// No os-release file is found.
+pub const OS_RELEASE_ERR_NOT_FOUND: c_int = 1;
+/// The os-release file cannot be opened or read.
+pub const OS_RELEASE_ERR_IO: c_int = 2;
+/// The os-release file is malformed.
+pub const OS_RELEASE_ERR_MALFORMED: c_int = 3;
+/// An argument is a null pointer or not valid UTF-8.
+pub const OS_RELEASE_ERR_INVALID_ARGUMENT: c_int = 4;
+
+/// An opaque handle to a parsed os-release file.
+pub struct OsReleaseHandle {
+    values: HashMap<String, CString>,
+}
+
+impl OsReleaseHandle {
+    fn new(os_release: &OsRelease) -> Self {
+        let values = os_release
+            .entries()
+            .map(|entry| {
+                // C strings cannot contain NUL, so the value is truncated at the first one.
+                let value = entry.value().split('\0').next().unwrap_or_default();
+                let value = CString::new(value).unwrap();
+                (entry.key().to_owned(), value)
+            })
+            .collect();
+        Self { values }
+    }
+}
+
+fn error_code(error: &Error) -> c_int {
+    match error {
+        Error::NoOsRelease => OS_RELEASE_ERR_NOT_FOUND,
+        Error::Malformed { .. } => OS_RELEASE_ERR_MALFORMED,
+        _ => OS_RELEASE_ERR_IO,
+    }
+}
+
+/// Store the result in `*out` and return the error code.
+///
+/// # Safety
+///
+/// `out` must be a valid pointer to write to.
+unsafe fn store(result: Result<OsRelease, Error>, out: *mut *mut OsReleaseHandle) -> c_int {
+    match result {
+        Ok(os_release) => {
+            *out = Box::into_raw(Box::new(OsReleaseHandle::new(&os_release)));
+            OS_RELEASE_OK
+        }
+        Err(e) => {
+            *out = ptr::null_mut();
+            error_code(&e)
+        }
+    }
+}
+
+/// Convert a C string argument to a `&str`.
+///
+/// # Safety
+///
+/// `s` must be null or a valid pointer to a NUL-terminated string.
+unsafe fn to_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok()
+}
+
+/// Open the os-release file of the running system and parse it, as [`OsRelease::open()`].
+///
+/// On success, stores the handle in `*out` and returns [`OS_RELEASE_OK`].
+/// The handle must be released with [`os_release_free()`].
+/// On failure, stores a null pointer in `*out` and returns an error code.
+///
+/// # Safety
+///
+/// `out` must be a valid pointer to write to.
+#[no_mangle]
+pub unsafe extern "C" fn os_release_open(out: *mut *mut OsReleaseHandle) -> c_int {
+    if out.is_null() {
+        return OS_RELEASE_ERR_INVALID_ARGUMENT;
+    }
+    store(OsRelease::open(), out)
+}
+
+/// Open the os-release file of the operating system tree at `root` and parse it,
+/// as [`OsRelease::open_root()`].
+///
+/// The result is returned in the same way as [`os_release_open()`].
+///
+/// # Safety
+///
+/// `root` must be a valid pointer to a NUL-terminated string, and `out` must be a valid pointer to write to.
+#[no_mangle]
+pub unsafe extern "C" fn os_release_open_root(
+    root: *const c_char,
+    out: *mut *mut OsReleaseHandle,
+) -> c_int {
+    if out.is_null() {
+        return OS_RELEASE_ERR_INVALID_ARGUMENT;
+    }
+    let Some(root) = to_str(root) else {
+        *out = ptr::null_mut();
+        return OS_RELEASE_ERR_INVALID_ARGUMENT;
+    };
+    store(OsRelease::open_root(root), out)
+}
+
+/// Parse a string containing the contents of the os-release file.
+///
+/// The result is returned in the same way as [`os_release_open()`].
+///
+/// # Safety
+///
+/// `s` must be a valid pointer to a NUL-terminated string, and `out` must be a valid pointer to write to.
+#[no_mangle]
+pub unsafe extern "C" fn os_release_parse(
+    s: *const c_char,
+    out: *mut *mut OsReleaseHandle,
+) -> c_int {
+    if out.is_null() {
+        return OS_RELEASE_ERR_INVALID_ARGUMENT;
+    }
+    let Some(s) = to_str(s) else {
+        *out = ptr::null_mut();
+        return OS_RELEASE_ERR_INVALID_ARGUMENT;
+    };
+    store(Ok(s.parse().unwrap_or_else(|e| match e {})), out)
+}
+
+/// Returns the value of the field `key`, or a null pointer if it is not set.
+///
+/// The returned string is valid until the handle is released. Default values of fields are not applied.
+/// If the value contains a NUL character, the returned string ends before it.
+///
+/// # Safety
+///
+/// `os_release` must be a handle returned by this library and not yet released,
+/// and `key` must be a valid pointer to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn os_release_get(
+    os_release: *const OsReleaseHandle,
+    key: *const c_char,
+) -> *const c_char {
+    match (os_release.as_ref(), to_str(key)) {
+        (Some(os_release), Some(key)) => os_release
+            .values
+            .get(key)
+            .map_or(ptr::null(), |value| value.as_ptr()),
+        _ => ptr::null(),
+    }
+}
+
+/// Release a handle. Does nothing if `os_release` is a null pointer.
+///
+/// # Safety
+///
+/// `os_release` must be null or a handle returned by this library and not yet released.
+#[no_mangle]
+pub unsafe extern "C" fn os_release_free(os_release: *mut OsReleaseHandle) {
+    if !os_release.is_null() {
+        drop(Box::from_raw(os_release));
+    }
+}
+
+/// Returns a static string describing an error code.
+#[no_mangle]
+pub extern "C" fn os_release_strerror(code: c_int) -> *const c_char {
+    let message: &'static CStr = match code {
+        OS_RELEASE_OK => c"success",
+        OS_RELEASE_ERR_NOT_FOUND => c"os-release file not found",
+        OS_RELEASE_ERR_IO => c"failed to read os-release file",
+        OS_RELEASE_ERR_MALFORMED => c"malformed os-release file",
+        OS_RELEASE_ERR_INVALID_ARGUMENT => c"invalid argument",
+        _ => c"unknown error",
+    };
+    message.as_ptr()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ffi() {
+        unsafe {
+            let mut os_release = ptr::null_mut();
+            let ret = os_release_parse(
+                c"NAME=\"Fedora Linux\"\nID=fedora\n".as_ptr(),
+                &mut os_release,
+            );
+            assert_eq!(ret, OS_RELEASE_OK);
+
+            let name = os_release_get(os_release, c"NAME".as_ptr());
+            assert_eq!(CStr::from_ptr(name), c"Fedora Linux");
+            assert!(os_release_get(os_release, c"VERSION_ID".as_ptr()).is_null());
+            assert!(os_release_get(os_release, ptr::null()).is_null());
+            os_release_free(os_release);
+
+            let os_release = OsReleaseHandle::new(&"ID=a\0b\n".parse().unwrap());
+            assert_eq!(
+                CStr::from_ptr(os_release_get(&os_release, c"ID".as_ptr())),
+                c"a"
+            );
+
+            let mut os_release = ptr::null_mut();
+            let ret = os_release_open_root(c"/nonexistent".as_ptr(), &mut os_release);
+            assert_eq!(ret, OS_RELEASE_ERR_NOT_FOUND);
+            assert!(os_release.is_null());
+
+            let ret = os_release_parse(ptr::null(), &mut os_release);
+            assert_eq!(ret, OS_RELEASE_ERR_INVALID_ARGUMENT);
+            assert_eq!(
+                CStr::from_ptr(os_release_strerror(ret)),
+                c"invalid argument"
+            );
+        }
+    }
+}
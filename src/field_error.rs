@@ -0,0 +1,84 @@
+/// An error which can be returned when the value of a field cannot be converted to the requested type.
+///
+/// The error carries the key and the raw value of the field, so that it can be reported without looking them up again.
+/// The typed getters such as [`OsRelease::home_url()`](crate::OsRelease::home_url) use the default [`ValueError`],
+/// so that their signatures do not depend on the enabled features.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "url")]
+/// # {
+/// use std::str::FromStr;
+///
+/// use etc_os_release::{OsRelease, ValueError};
+///
+/// let os_release = OsRelease::from_str("HOME_URL=fedoraproject.org\n").unwrap();
+/// let err = os_release.home_url().unwrap_err();
+/// assert!(matches!(err.inner(), ValueError::Url(_)));
+/// assert_eq!(err.key(), "HOME_URL");
+/// assert_eq!(err.raw_value(), "fedoraproject.org");
+/// assert_eq!(
+///     err.to_string(),
+///     r#"invalid value "fedoraproject.org" of field `HOME_URL`"#,
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid value {raw_value:?} of field `{key}`")]
+pub struct FieldError<E = ValueError> {
+    key: String,
+    raw_value: String,
+    #[source]
+    source: E,
+}
+
+impl<E> FieldError<E> {
+    #[cfg_attr(not(any(feature = "date", feature = "url")), allow(dead_code))]
+    pub(crate) fn new(key: impl Into<String>, raw_value: impl Into<String>, source: E) -> Self {
+        Self {
+            key: key.into(),
+            raw_value: raw_value.into(),
+            source,
+        }
+    }
+
+    /// Returns the key of the field.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the value of the field as written in the os-release file.
+    pub fn raw_value(&self) -> &str {
+        &self.raw_value
+    }
+
+    /// Returns the underlying error of the conversion.
+    pub fn inner(&self) -> &E {
+        &self.source
+    }
+
+    /// Returns the underlying error of the conversion, discarding the context.
+    pub fn into_inner(self) -> E {
+        self.source
+    }
+}
+
+/// The reason why the value of a field could not be converted.
+///
+/// Each variant is available only with the feature of the corresponding type,
+/// and more variants may be added in the future.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ValueError {
+    /// The value is not a valid URL.
+    #[cfg(feature = "url")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "url")))]
+    #[error("invalid URL")]
+    Url(#[source] url::ParseError),
+    /// The value is not a valid date.
+    #[cfg(feature = "date")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "date")))]
+    #[error("invalid date")]
+    Date(#[source] chrono::ParseError),
+}
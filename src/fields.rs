@@ -1,20 +1,123 @@
+use std::{
+    borrow::Cow,
+    hash::{Hash, Hasher},
+    io::{self, IsTerminal as _},
+    ops::Index,
+    str::FromStr,
+    sync::Arc,
+};
+
 #[cfg(feature = "date")]
 use chrono::NaiveDate;
 #[cfg(feature = "url")]
 use url::Url;
 
-use crate::{OsRelease, OsReleaseEntry};
+use crate::{keys, Architecture, Entries, OsRelease, OsReleaseEntry, ReleaseType};
+#[cfg(any(feature = "date", feature = "url"))]
+use crate::{FieldError, ValueError};
 
 /// Methods to get any field in the os-release file.
 impl OsRelease {
     /// Returns the iterator over the fields in the os-release file.
-    pub fn entries(&self) -> impl Iterator<Item = OsReleaseEntry> {
-        self.fields.iter().map(|(k, v)| OsReleaseEntry::new(k, v))
+    pub fn entries(&self) -> Entries<'_> {
+        Entries::new(self)
+    }
+
+    /// Returns the iterator over the fields whose keys start with `prefix`.
+    ///
+    /// This is useful to collect distribution-specific extensions, such as `REDHAT_*` or `SUSE_*` fields.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// NAME=Fedora
+    /// REDHAT_BUGZILLA_PRODUCT="Fedora"
+    /// REDHAT_SUPPORT_PRODUCT="Fedora"
+    /// "#).unwrap();
+    ///
+    /// let keys = os_release.entries_with_prefix("REDHAT_").map(|e| e.key().to_owned()).collect::<Vec<_>>();
+    /// assert_eq!(keys, ["REDHAT_BUGZILLA_PRODUCT", "REDHAT_SUPPORT_PRODUCT"]);
+    /// ```
+    pub fn entries_with_prefix<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = OsReleaseEntry<'a>> + 'a {
+        self.entries()
+            .filter(move |entry| entry.key().starts_with(prefix))
+    }
+
+    /// Returns the iterator over the fields not defined by [`os-release(5)`].
+    ///
+    /// These are the extensions added by the distributor, such as `REDHAT_*`, `SUSE_*` or `UBUNTU_CODENAME` fields.
+    ///
+    /// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+    pub fn vendor_entries(&self) -> impl Iterator<Item = OsReleaseEntry<'_>> {
+        self.entries()
+            .filter(|entry| !keys::is_known_key(entry.key()))
+    }
+
+    /// Returns the number of fields in the os-release file.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Returns `true` if the os-release file has no fields.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Returns `true` if a field is set in the os-release file.
+    ///
+    /// Unlike the getters with defaults, such as [`Self::name()`], this distinguishes an absent field from a field set to the default.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.fields.contains_key(key)
     }
 
     /// Returns the value of a field in the os-release file.
     pub fn get_value(&self, key: &str) -> Option<&str> {
-        self.fields.get(key).map(String::as_str)
+        self.fields.get(key).map(AsRef::as_ref)
+    }
+
+    /// Returns the value of a field in the os-release file, falling back to its aliases.
+    ///
+    /// If `key` is not set, the fields that historically carried the same information are tried in order.
+    /// The following aliases are known:
+    ///
+    /// | Key                | Aliases                              |
+    /// |--------------------|--------------------------------------|
+    /// | `BUG_REPORT_URL`   | `SUPPORT_URL`                        |
+    /// | `VERSION_CODENAME` | `UBUNTU_CODENAME`, `DEBIAN_CODENAME` |
+    ///
+    /// For other keys, this is the same as [`Self::get_value()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// ID=ubuntu
+    /// UBUNTU_CODENAME=xenial
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(os_release.get_value("VERSION_CODENAME"), None);
+    /// assert_eq!(os_release.get_value_with_aliases("VERSION_CODENAME"), Some("xenial"));
+    /// ```
+    pub fn get_value_with_aliases(&self, key: &str) -> Option<&str> {
+        let aliases = KEY_ALIASES
+            .iter()
+            .find_map(|(k, aliases)| (*k == key).then_some(*aliases))
+            .unwrap_or_default();
+        std::iter::once(key)
+            .chain(aliases.iter().copied())
+            .find_map(|key| self.get_value(key))
     }
 
     /// Returns the value of a field in the os-release as a list of strings.
@@ -22,23 +125,150 @@ impl OsRelease {
         self.get_value(key).map(|value| value.split_whitespace())
     }
 
+    /// Returns the value of a field in the os-release file parsed as `T`.
+    ///
+    /// Returns `None` if the field is not set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str("SYSEXT_LEVEL=15\nVENDOR_BUILD=abc\n").unwrap();
+    /// assert_eq!(os_release.get_value_parse::<u32>("SYSEXT_LEVEL"), Some(Ok(15)));
+    /// assert!(os_release.get_value_parse::<u32>("VENDOR_BUILD").unwrap().is_err());
+    /// assert!(os_release.get_value_parse::<u32>("IMAGE_VERSION").is_none());
+    /// ```
+    pub fn get_value_parse<T: FromStr>(&self, key: &str) -> Option<Result<T, T::Err>> {
+        self.get_value(key).map(str::parse)
+    }
+
     /// Returns the value of a field in the os-release as a URL.
     #[cfg(feature = "url")]
     #[cfg_attr(docsrs, doc(cfg(feature = "url")))]
-    pub fn get_value_as_url(&self, key: &str) -> Result<Option<Url>, url::ParseError> {
-        self.get_value(key).map(Url::parse).transpose()
+    pub fn get_value_as_url(&self, key: &str) -> Result<Option<Url>, FieldError> {
+        self.get_value(key)
+            .map(|value| {
+                Url::parse(value).map_err(|e| FieldError::new(key, value, ValueError::Url(e)))
+            })
+            .transpose()
     }
 
     /// Returns the value of a field in the os-release as a date.
     #[cfg(feature = "date")]
     #[cfg_attr(docsrs, doc(cfg(feature = "date")))]
-    pub fn get_value_as_date(&self, key: &str) -> Result<Option<NaiveDate>, chrono::ParseError> {
+    pub fn get_value_as_date(&self, key: &str) -> Result<Option<NaiveDate>, FieldError> {
         self.get_value(key)
-            .map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+            .map(|value| {
+                NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .map_err(|e| FieldError::new(key, value, ValueError::Date(e)))
+            })
             .transpose()
     }
+
+    /// Returns the value of a field in the os-release as a date, accepting some variations of ISO 8601.
+    ///
+    /// In addition to `YYYY-MM-DD`, this accepts:
+    ///
+    /// * a date and time such as `YYYY-MM-DDTHH:MM:SSZ`, of which the time is ignored, and
+    /// * a year and month `YYYY-MM`, which is interpreted as the first day of the month.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use chrono::NaiveDate;
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str("SUPPORT_END=2025-06-30T23:59:59Z\n").unwrap();
+    /// assert!(os_release.support_end().is_err());
+    /// assert_eq!(
+    ///     os_release.get_value_as_date_lenient("SUPPORT_END").unwrap(),
+    ///     NaiveDate::from_ymd_opt(2025, 6, 30),
+    /// );
+    /// ```
+    #[cfg(feature = "date")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "date")))]
+    pub fn get_value_as_date_lenient(&self, key: &str) -> Result<Option<NaiveDate>, FieldError> {
+        self.get_value(key)
+            .map(|value| {
+                parse_date_lenient(value)
+                    .map_err(|e| FieldError::new(key, value, ValueError::Date(e)))
+            })
+            .transpose()
+    }
+}
+
+#[cfg(feature = "date")]
+pub(crate) fn parse_date_lenient(s: &str) -> Result<NaiveDate, chrono::ParseError> {
+    let s = s.trim();
+    let date = s
+        .split_once(['T', 't', ' '])
+        .map_or(s, |(date, _time)| date);
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .or_else(|e| NaiveDate::parse_from_str(&format!("{date}-01"), "%Y-%m-%d").map_err(|_| e))
+}
+
+/// Two `OsRelease`s are equal if they have the same fields with the same values, regardless of the order of the fields.
+impl PartialEq for OsRelease {
+    fn eq(&self, other: &Self) -> bool {
+        // Clones share the fields, so they can be compared without looking at the contents.
+        Arc::ptr_eq(&self.fields, &other.fields) || self.fields == other.fields
+    }
 }
 
+impl Eq for OsRelease {}
+
+/// The hash is independent of the order of the fields, to be consistent with [`PartialEq`].
+impl Hash for OsRelease {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut fields = self.fields.iter().collect::<Vec<_>>();
+        fields.sort_unstable();
+        fields.hash(state);
+    }
+}
+
+/// Returns the value of a field, as [`OsRelease::get_value()`].
+///
+/// # Panics
+///
+/// Panics if the field is not set.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::OsRelease;
+///
+/// let os_release = OsRelease::spec_example();
+/// assert_eq!(&os_release["ID"], "fedora");
+/// ```
+impl Index<&str> for OsRelease {
+    type Output = str;
+
+    fn index(&self, key: &str) -> &str {
+        self.get_value(key)
+            .unwrap_or_else(|| panic!("no field `{key}` in the os-release file"))
+    }
+}
+
+/// Returns the key as a `'static` string without allocating, if it is defined by os-release(5).
+pub(crate) fn intern_key(key: Cow<'_, str>) -> Cow<'static, str> {
+    match keys::ALL.iter().find(|&&k| k == key) {
+        Some(&k) => Cow::Borrowed(k),
+        None => Cow::Owned(key.into_owned()),
+    }
+}
+
+/// Keys and the keys that historically carried the same information, in order of preference.
+const KEY_ALIASES: &[(&str, &[&str])] = &[
+    ("BUG_REPORT_URL", &["SUPPORT_URL"]),
+    // Some Ubuntu- and Debian-based distributions only set `UBUNTU_CODENAME=` or `DEBIAN_CODENAME=`.
+    ("VERSION_CODENAME", &["UBUNTU_CODENAME", "DEBIAN_CODENAME"]),
+];
+
 /// Methods to get general information identifying the operating system.
 ///
 /// For more information, see the [General information identifying the operating system][detail] section of [`os-release(5)`].
@@ -87,6 +317,62 @@ impl OsRelease {
         self.get_value_as_list("ID_LIKE")
     }
 
+    /// Returns the iterator over [`Self::id()`] followed by each entry of [`Self::id_like()`], without duplicates.
+    ///
+    /// This is the order in which operating system identifiers should be tried when looking for a match,
+    /// from the most specific to the least specific.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// ID=linuxmint
+    /// ID_LIKE="ubuntu debian"
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(os_release.id_chain().collect::<Vec<_>>(), ["linuxmint", "ubuntu", "debian"]);
+    /// ```
+    pub fn id_chain(&self) -> impl Iterator<Item = &str> {
+        let mut seen = vec![];
+        std::iter::once(self.id())
+            .chain(self.id_like().into_iter().flatten())
+            .filter(move |id| {
+                if seen.contains(id) {
+                    return false;
+                }
+                seen.push(*id);
+                true
+            })
+    }
+
+    /// Returns `true` if the operating system is `id` or is closely related to it.
+    ///
+    /// Both [`Self::id()`] and [`Self::id_like()`] are checked, ignoring ASCII case.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// ID=ubuntu
+    /// ID_LIKE=debian
+    /// "#).unwrap();
+    ///
+    /// assert!(os_release.is_like("ubuntu"));
+    /// assert!(os_release.is_like("Debian"));
+    /// assert!(!os_release.is_like("fedora"));
+    /// ```
+    pub fn is_like(&self, id: &str) -> bool {
+        self.id_chain().any(|i| i.eq_ignore_ascii_case(id))
+    }
+
     /// Returns the pretty operating system name in a format suitable for presentation to the user.
     ///
     /// If not set in the os-release file, defaults to `Linux`.
@@ -139,6 +425,27 @@ impl OsRelease {
     }
 }
 
+/// `ID=` of operating systems which are image-based.
+const IMAGE_BASED_IDS: &[&str] = &[
+    "flatcar",
+    "bottlerocket",
+    "talos",
+    "steamos",
+    "endless",
+    "rhcos",
+];
+
+/// `VARIANT_ID=` of image-based variants of operating systems.
+const IMAGE_BASED_VARIANT_IDS: &[&str] = &[
+    "coreos",
+    "iot",
+    "silverblue",
+    "kinoite",
+    "sericea",
+    "onyx",
+    "atomic",
+];
+
 /// Methods to get information about the version of the operating system.
 ///
 /// For more information, see the [Information about the version of the operating system][detail] section of [`os-release(5)`].
@@ -184,6 +491,32 @@ impl OsRelease {
         self.get_value("VERSION_CODENAME")
     }
 
+    /// Returns the release code name, falling back to the distribution-specific code name fields.
+    ///
+    /// The fields are tried in the order `VERSION_CODENAME=`, `UBUNTU_CODENAME=` and `DEBIAN_CODENAME=`.
+    /// This is useful for generating package repository configurations, which need the code name even on
+    /// distributions that only set the distribution-specific fields.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// ID=pop
+    /// ID_LIKE="ubuntu debian"
+    /// UBUNTU_CODENAME=jammy
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(os_release.version_codename(), None);
+    /// assert_eq!(os_release.codename(), Some("jammy"));
+    /// ```
+    pub fn codename(&self) -> Option<&str> {
+        self.get_value_with_aliases("VERSION_CODENAME")
+    }
+
     /// Returns the string uniquely identifying the system image originally used as the installation base.
     ///
     /// For more information, see the [`BUILD_ID=`] section of [`os-release(5)`]
@@ -213,6 +546,77 @@ impl OsRelease {
     pub fn image_version(&self) -> Option<&str> {
         self.get_value("IMAGE_VERSION")
     }
+
+    /// Returns `true` if the operating system is image-based, updated as a whole rather than by installing packages.
+    ///
+    /// The operating system is considered image-based if any of the following holds:
+    ///
+    /// * [`Self::image_id()`] or [`Self::image_version()`] is set.
+    /// * `OSTREE_VERSION=` is set, as on Fedora Silverblue and other ostree-based systems.
+    /// * [`Self::id()`] or [`Self::variant_id()`] is a known image-based operating system or variant,
+    ///   such as Flatcar Container Linux or Fedora CoreOS.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// ID=fedora
+    /// VERSION_ID=40
+    /// VARIANT_ID=silverblue
+    /// OSTREE_VERSION='40.20240501.0'
+    /// "#).unwrap();
+    /// assert!(os_release.is_image_based());
+    ///
+    /// let os_release = OsRelease::from_str("ID=fedora\nVERSION_ID=40\n").unwrap();
+    /// assert!(!os_release.is_image_based());
+    /// ```
+    pub fn is_image_based(&self) -> bool {
+        self.image_id().is_some()
+            || self.image_version().is_some()
+            || self.contains_key("OSTREE_VERSION")
+            || IMAGE_BASED_IDS.contains(&self.id())
+            || self
+                .variant_id()
+                .is_some_and(|variant_id| IMAGE_BASED_VARIANT_IDS.contains(&variant_id))
+    }
+
+    /// Returns the type of the release, such as stable, long-term support or development.
+    ///
+    /// Update managers may use this field to adjust their behavior on pre-release operating systems.
+    ///
+    /// For more information, see the [`RELEASE_TYPE=`] section of [`os-release(5)`]
+    ///
+    /// [`RELEASE_TYPE=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#RELEASE_TYPE=
+    /// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+    pub fn release_type(&self) -> Option<ReleaseType> {
+        self.get_value("RELEASE_TYPE")
+            .map(|value| value.parse().unwrap_or_else(|e| match e {}))
+    }
+}
+
+/// Methods to get distribution-specific fields.
+///
+/// These fields are not defined by [`os-release(5)`], but are commonly set by some distributions.
+///
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+impl OsRelease {
+    /// Returns the code name of the Ubuntu release the operating system is based on.
+    ///
+    /// This field is set by Ubuntu and its derivatives.
+    pub fn ubuntu_codename(&self) -> Option<&str> {
+        self.get_value("UBUNTU_CODENAME")
+    }
+
+    /// Returns the code name of the Debian release the operating system is based on.
+    ///
+    /// This field is set by some Debian derivatives.
+    pub fn debian_codename(&self) -> Option<&str> {
+        self.get_value("DEBIAN_CODENAME")
+    }
 }
 
 /// Methods to get presentation information and links.
@@ -230,7 +634,7 @@ impl OsRelease {
     /// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
     #[cfg(feature = "url")]
     #[cfg_attr(docsrs, doc(cfg(feature = "url")))]
-    pub fn home_url(&self) -> Result<Option<Url>, url::ParseError> {
+    pub fn home_url(&self) -> Result<Option<Url>, FieldError> {
         self.get_value_as_url("HOME_URL")
     }
 
@@ -242,7 +646,7 @@ impl OsRelease {
     /// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
     #[cfg(feature = "url")]
     #[cfg_attr(docsrs, doc(cfg(feature = "url")))]
-    pub fn documentation_url(&self) -> Result<Option<Url>, url::ParseError> {
+    pub fn documentation_url(&self) -> Result<Option<Url>, FieldError> {
         self.get_value_as_url("DOCUMENTATION_URL")
     }
 
@@ -254,7 +658,7 @@ impl OsRelease {
     /// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
     #[cfg(feature = "url")]
     #[cfg_attr(docsrs, doc(cfg(feature = "url")))]
-    pub fn support_url(&self) -> Result<Option<Url>, url::ParseError> {
+    pub fn support_url(&self) -> Result<Option<Url>, FieldError> {
         self.get_value_as_url("SUPPORT_URL")
     }
 
@@ -266,7 +670,7 @@ impl OsRelease {
     /// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
     #[cfg(feature = "url")]
     #[cfg_attr(docsrs, doc(cfg(feature = "url")))]
-    pub fn bug_report_url(&self) -> Result<Option<Url>, url::ParseError> {
+    pub fn bug_report_url(&self) -> Result<Option<Url>, FieldError> {
         self.get_value_as_url("BUG_REPORT_URL")
     }
 
@@ -278,7 +682,7 @@ impl OsRelease {
     /// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
     #[cfg(feature = "url")]
     #[cfg_attr(docsrs, doc(cfg(feature = "url")))]
-    pub fn privacy_policy_url(&self) -> Result<Option<Url>, url::ParseError> {
+    pub fn privacy_policy_url(&self) -> Result<Option<Url>, FieldError> {
         self.get_value_as_url("PRIVACY_POLICY_URL")
     }
 
@@ -290,10 +694,19 @@ impl OsRelease {
     /// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
     #[cfg(feature = "date")]
     #[cfg_attr(docsrs, doc(cfg(feature = "date")))]
-    pub fn support_end(&self) -> Result<Option<NaiveDate>, chrono::ParseError> {
+    pub fn support_end(&self) -> Result<Option<NaiveDate>, FieldError> {
         self.get_value_as_date("SUPPORT_END")
     }
 
+    /// Returns the date at which support for this version of the OS ends, accepting some variations of the format seen in the wild.
+    ///
+    /// See [`Self::get_value_as_date_lenient()`] for the accepted formats.
+    #[cfg(feature = "date")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "date")))]
+    pub fn support_end_lenient(&self) -> Result<Option<NaiveDate>, FieldError> {
+        self.get_value_as_date_lenient("SUPPORT_END")
+    }
+
     /// Returns the logo string, specifying the name of an icon as defined by [freedesktop.org Icon Theme Specification][spec].
     ///
     /// For more information, see the [`LOGO=`] section of [`os-release(5)`]
@@ -315,6 +728,48 @@ impl OsRelease {
         self.get_value("ANSI_COLOR")
     }
 
+    /// Returns the pretty operating system name wrapped in the [`ANSI_COLOR=`] escape sequence.
+    ///
+    /// The escape sequence is only added if `ANSI_COLOR=` is set and the standard output is a terminal.
+    /// Otherwise, the plain [`Self::pretty_name()`] is returned.
+    ///
+    /// [`ANSI_COLOR=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#ANSI_COLOR=
+    pub fn colored_pretty_name(&self) -> Cow<'_, str> {
+        self.colored_pretty_name_if(io::stdout().is_terminal())
+    }
+
+    /// Returns the pretty operating system name wrapped in the [`ANSI_COLOR=`] escape sequence if `color` is `true`.
+    ///
+    /// If `color` is `false` or `ANSI_COLOR=` is not set, the plain [`Self::pretty_name()`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// PRETTY_NAME="Fedora 32 (Workstation Edition)"
+    /// ANSI_COLOR="0;38;2;60;110;180"
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(
+    ///     os_release.colored_pretty_name_if(true),
+    ///     "\x1b[0;38;2;60;110;180mFedora 32 (Workstation Edition)\x1b[0m",
+    /// );
+    /// assert_eq!(os_release.colored_pretty_name_if(false), "Fedora 32 (Workstation Edition)");
+    /// ```
+    ///
+    /// [`ANSI_COLOR=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#ANSI_COLOR=
+    pub fn colored_pretty_name_if(&self, color: bool) -> Cow<'_, str> {
+        let pretty_name = self.pretty_name();
+        match self.ansi_color() {
+            Some(ansi_color) if color => format!("\x1b[{ansi_color}m{pretty_name}\x1b[0m").into(),
+            _ => pretty_name.into(),
+        }
+    }
+
     /// Returns the name of the OS vendor.
     ///
     /// For more information, see the [`VENDOR_NAME=`] section of [`os-release(5)`]
@@ -333,7 +788,7 @@ impl OsRelease {
     /// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
     #[cfg(feature = "url")]
     #[cfg_attr(docsrs, doc(cfg(feature = "url")))]
-    pub fn vendor_url(&self) -> Result<Option<Url>, url::ParseError> {
+    pub fn vendor_url(&self) -> Result<Option<Url>, FieldError> {
         self.get_value_as_url("VENDOR_URL")
     }
 }
@@ -356,14 +811,15 @@ impl OsRelease {
         self.get_value("DEFAULT_HOSTNAME")
     }
 
-    /// Returns the string that specifies which CPU architecture the userspace binaries require.
+    /// Returns the CPU architecture the userspace binaries require.
     ///
     /// For more information, see the [`ARCHITECTURE=`] section of [`os-release(5)`]
     ///
     /// [`ARCHITECTURE=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#ARCHITECTURE=
     /// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
-    pub fn architecture(&self) -> Option<&str> {
+    pub fn architecture(&self) -> Option<Architecture> {
         self.get_value("ARCHITECTURE")
+            .map(|value| value.parse().unwrap_or_else(|e| match e {}))
     }
 
     /// Returns the lower-case string identifying the operating system extensions support level, to indicate which extension images are supported.
@@ -417,3 +873,90 @@ impl OsRelease {
         self.get_value_as_list("PORTABLE_PREFIXES")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[cfg(feature = "date")]
+    #[test]
+    fn test_parse_date_lenient() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(parse_date_lenient("2025-06-30").unwrap(), date(2025, 6, 30));
+        assert_eq!(
+            parse_date_lenient(" 2025-06-30 ").unwrap(),
+            date(2025, 6, 30)
+        );
+        assert_eq!(
+            parse_date_lenient("2025-06-30T12:00:00+09:00").unwrap(),
+            date(2025, 6, 30)
+        );
+        assert_eq!(
+            parse_date_lenient("2025-06-30 12:00:00").unwrap(),
+            date(2025, 6, 30)
+        );
+        assert_eq!(parse_date_lenient("2025-06").unwrap(), date(2025, 6, 1));
+        assert!(parse_date_lenient("2025").is_err());
+        assert!(parse_date_lenient("2025-13").is_err());
+        assert!(parse_date_lenient("June 2025").is_err());
+    }
+
+    #[test]
+    fn test_intern_key() {
+        assert!(matches!(intern_key("NAME".into()), Cow::Borrowed("NAME")));
+        assert!(matches!(
+            intern_key(String::from("VERSION_ID").into()),
+            Cow::Borrowed("VERSION_ID")
+        ));
+        assert!(matches!(
+            intern_key("REDHAT_SUPPORT_PRODUCT".into()),
+            Cow::Owned(key) if key == "REDHAT_SUPPORT_PRODUCT"
+        ));
+
+        let os_release = OsRelease::from_str("NAME=Fedora\nFOO=bar\n").unwrap();
+        let keys = os_release.fields.keys().collect::<Vec<_>>();
+        assert!(matches!(keys[0], Cow::Borrowed("NAME")));
+        assert!(matches!(keys[1], Cow::Owned(_)));
+    }
+
+    #[test]
+    fn test_eq_hash() {
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash(os_release: &OsRelease) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            os_release.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let a = OsRelease::from_str("ID=fedora\nVERSION_ID=40\n").unwrap();
+        let b = OsRelease::from_str("VERSION_ID=40\n# comment\nID=\"fedora\"\n").unwrap();
+        let c = OsRelease::from_str("ID=fedora\nVERSION_ID=41\n").unwrap();
+        let d = OsRelease::from_str("ID=fedora\n").unwrap();
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+        assert_ne!(a, c);
+        assert_ne!(a, d);
+        assert_ne!(d, a);
+    }
+
+    #[test]
+    #[should_panic(expected = "no field `VERSION_ID`")]
+    fn test_index_missing() {
+        let os_release = OsRelease::from_str("ID=arch\n").unwrap();
+        assert_eq!(&os_release["ID"], "arch");
+        let _ = &os_release["VERSION_ID"];
+    }
+
+    #[test]
+    fn test_clone_is_shared() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<OsRelease>();
+
+        let os_release = OsRelease::spec_example();
+        let clone = os_release.clone();
+        assert!(Arc::ptr_eq(&os_release.fields, &clone.fields));
+    }
+}
@@ -0,0 +1,199 @@
+//! Real-world os-release files of major distributions, for testing.
+//!
+//! The files are reproduced as shipped, including quoting, comments and empty values,
+//! so that code handling os-release files can be tested against what is actually found in the wild.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use std::str::FromStr;
+//!
+//! use etc_os_release::{fixtures, OsRelease};
+//!
+//! let os_release = OsRelease::from_str(fixtures::UBUNTU_22_04).unwrap();
+//! assert_eq!(os_release.id(), "ubuntu");
+//! assert_eq!(os_release.version_id(), Some("22.04"));
+//!
+//! for (name, contents) in fixtures::ALL {
+//!     let os_release = OsRelease::from_str(contents).unwrap();
+//!     assert!(os_release.get_value("ID").is_some(), "{name}");
+//! }
+//! ```
+
+/// Fedora Linux 40 Workstation Edition.
+pub const FEDORA_40: &str = r#"NAME="Fedora Linux"
+VERSION="40 (Workstation Edition)"
+ID=fedora
+VERSION_ID=40
+VERSION_CODENAME=""
+PLATFORM_ID="platform:f40"
+PRETTY_NAME="Fedora Linux 40 (Workstation Edition)"
+ANSI_COLOR="0;38;2;60;110;180"
+LOGO=fedora-logo-icon
+CPE_NAME="cpe:/o:fedoraproject:fedora:40"
+DEFAULT_HOSTNAME="fedora"
+HOME_URL="https://fedoraproject.org/"
+DOCUMENTATION_URL="https://docs.fedoraproject.org/en-US/fedora/f40/system-administrators-guide/"
+SUPPORT_URL="https://ask.fedoraproject.org/"
+BUG_REPORT_URL="https://bugzilla.redhat.com/"
+REDHAT_BUGZILLA_PRODUCT="Fedora"
+REDHAT_BUGZILLA_PRODUCT_VERSION=40
+REDHAT_SUPPORT_PRODUCT="Fedora"
+REDHAT_SUPPORT_PRODUCT_VERSION=40
+SUPPORT_END=2025-05-13
+VARIANT="Workstation Edition"
+VARIANT_ID=workstation
+"#;
+
+/// Red Hat Enterprise Linux 9.4.
+pub const RHEL_9: &str = r#"NAME="Red Hat Enterprise Linux"
+VERSION="9.4 (Plow)"
+ID="rhel"
+ID_LIKE="fedora"
+VERSION_ID="9.4"
+PLATFORM_ID="platform:el9"
+PRETTY_NAME="Red Hat Enterprise Linux 9.4 (Plow)"
+ANSI_COLOR="0;31"
+LOGO="fedora-logo-icon"
+CPE_NAME="cpe:/o:redhat:enterprise_linux:9::baseos"
+HOME_URL="https://www.redhat.com/"
+DOCUMENTATION_URL="https://access.redhat.com/documentation/en-us/red_hat_enterprise_linux/9"
+BUG_REPORT_URL="https://issues.redhat.com/"
+
+REDHAT_BUGZILLA_PRODUCT="Red Hat Enterprise Linux 9"
+REDHAT_BUGZILLA_PRODUCT_VERSION=9.4
+REDHAT_SUPPORT_PRODUCT="Red Hat Enterprise Linux"
+REDHAT_SUPPORT_PRODUCT_VERSION="9.4"
+"#;
+
+/// Debian GNU/Linux 12 (bookworm).
+pub const DEBIAN_12: &str = r#"PRETTY_NAME="Debian GNU/Linux 12 (bookworm)"
+NAME="Debian GNU/Linux"
+VERSION_ID="12"
+VERSION="12 (bookworm)"
+VERSION_CODENAME=bookworm
+ID=debian
+HOME_URL="https://www.debian.org/"
+SUPPORT_URL="https://www.debian.org/support"
+BUG_REPORT_URL="https://bugs.debian.org/"
+"#;
+
+/// Ubuntu 22.04 LTS (Jammy Jellyfish).
+pub const UBUNTU_22_04: &str = r#"PRETTY_NAME="Ubuntu 22.04.4 LTS"
+NAME="Ubuntu"
+VERSION_ID="22.04"
+VERSION="22.04.4 LTS (Jammy Jellyfish)"
+VERSION_CODENAME=jammy
+ID=ubuntu
+ID_LIKE=debian
+HOME_URL="https://www.ubuntu.com/"
+SUPPORT_URL="https://help.ubuntu.com/"
+BUG_REPORT_URL="https://bugs.launchpad.net/ubuntu/"
+PRIVACY_POLICY_URL="https://www.ubuntu.com/legal/terms-and-policies/privacy-policy"
+UBUNTU_CODENAME=jammy
+"#;
+
+/// Ubuntu 24.04 LTS (Noble Numbat).
+pub const UBUNTU_24_04: &str = r#"PRETTY_NAME="Ubuntu 24.04.1 LTS"
+NAME="Ubuntu"
+VERSION_ID="24.04"
+VERSION="24.04.1 LTS (Noble Numbat)"
+VERSION_CODENAME=noble
+ID=ubuntu
+ID_LIKE=debian
+HOME_URL="https://www.ubuntu.com/"
+SUPPORT_URL="https://help.ubuntu.com/"
+BUG_REPORT_URL="https://bugs.launchpad.net/ubuntu/"
+PRIVACY_POLICY_URL="https://www.ubuntu.com/legal/terms-and-policies/privacy-policy"
+UBUNTU_CODENAME=noble
+LOGO=ubuntu-logo
+"#;
+
+/// Alpine Linux 3.19.
+pub const ALPINE_3_19: &str = r#"NAME="Alpine Linux"
+ID=alpine
+VERSION_ID=3.19.1
+PRETTY_NAME="Alpine Linux v3.19"
+HOME_URL="https://alpinelinux.org/"
+BUG_REPORT_URL="https://gitlab.alpinelinux.org/alpine/aports/-/issues"
+"#;
+
+/// Arch Linux, a rolling release without `VERSION_ID=`.
+pub const ARCH: &str = r#"NAME="Arch Linux"
+PRETTY_NAME="Arch Linux"
+ID=arch
+BUILD_ID=rolling
+ANSI_COLOR="38;2;23;147;209"
+HOME_URL="https://archlinux.org/"
+DOCUMENTATION_URL="https://wiki.archlinux.org/"
+SUPPORT_URL="https://bbs.archlinux.org/"
+BUG_REPORT_URL="https://gitlab.archlinux.org/groups/archlinux/-/issues"
+PRIVACY_POLICY_URL="https://terms.archlinux.org/docs/privacy-policy/"
+LOGO=archlinux-logo
+"#;
+
+/// openSUSE Tumbleweed, a rolling release with comments.
+pub const OPENSUSE_TUMBLEWEED: &str = r#"NAME="openSUSE Tumbleweed"
+# VERSION="20240901"
+ID="opensuse-tumbleweed"
+ID_LIKE="opensuse suse"
+VERSION_ID="20240901"
+PRETTY_NAME="openSUSE Tumbleweed"
+ANSI_COLOR="0;32"
+# CPE 2.3 format, boo#1217921
+CPE_NAME="cpe:2.3:o:opensuse:tumbleweed:20240901:*:*:*:*:*:*:*"
+BUG_REPORT_URL="https://bugzilla.opensuse.org"
+SUPPORT_URL="https://bugs.opensuse.org"
+HOME_URL="https://www.opensuse.org"
+DOCUMENTATION_URL="https://en.opensuse.org/Portal:Tumbleweed"
+LOGO="distributor-logo-Tumbleweed"
+"#;
+
+/// NixOS 24.05 (Uakari), with empty `IMAGE_ID=` and `IMAGE_VERSION=`.
+pub const NIXOS: &str = r#"ANSI_COLOR="1;34"
+BUG_REPORT_URL="https://github.com/NixOS/nixpkgs/issues"
+BUILD_ID="24.05.5518.ecbc1ca8ffd6"
+DOCUMENTATION_URL="https://nixos.org/learn.html"
+HOME_URL="https://nixos.org/"
+ID=nixos
+IMAGE_ID=""
+IMAGE_VERSION=""
+LOGO="nix-snowflake"
+NAME=NixOS
+PRETTY_NAME="NixOS 24.05 (Uakari)"
+SUPPORT_END="2024-12-31"
+SUPPORT_URL="https://nixos.org/community.html"
+VERSION="24.05 (Uakari)"
+VERSION_CODENAME=uakari
+VERSION_ID="24.05"
+"#;
+
+/// All fixtures of this module, with their names.
+pub const ALL: &[(&str, &str)] = &[
+    ("FEDORA_40", FEDORA_40),
+    ("RHEL_9", RHEL_9),
+    ("DEBIAN_12", DEBIAN_12),
+    ("UBUNTU_22_04", UBUNTU_22_04),
+    ("UBUNTU_24_04", UBUNTU_24_04),
+    ("ALPINE_3_19", ALPINE_3_19),
+    ("ARCH", ARCH),
+    ("OPENSUSE_TUMBLEWEED", OPENSUSE_TUMBLEWEED),
+    ("NIXOS", NIXOS),
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{OsRelease, ParseOptions};
+
+    #[test]
+    fn test_fixtures() {
+        let options = ParseOptions::new().strict(true);
+        for (name, contents) in ALL {
+            let os_release = OsRelease::from_str_with(contents, &options)
+                .unwrap_or_else(|e| panic!("{name}: {e}"));
+            assert!(os_release.get_value("ID").is_some(), "{name}");
+            assert!(os_release.get_value("PRETTY_NAME").is_some(), "{name}");
+        }
+    }
+}
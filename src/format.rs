@@ -0,0 +1,465 @@
+use std::fmt::{self, Write as _};
+
+use crate::{entry, keys, OsRelease, OsReleaseEntry, OsReleaseLine};
+
+/// Formats the fields as the contents of an os-release file.
+///
+/// Each field is written on its own line as `KEY=value`, quoting and escaping the value if needed.
+/// Line breaks in values cannot be represented and are written as spaces.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::str::FromStr;
+///
+/// use etc_os_release::OsRelease;
+///
+/// let os_release = OsRelease::from_str(r#"
+/// NAME=Fedora
+/// PRETTY_NAME="Fedora 32 (Workstation Edition)"
+/// "#).unwrap();
+///
+/// assert_eq!(
+///     os_release.to_string(),
+///     "NAME=Fedora\nPRETTY_NAME=\"Fedora 32 (Workstation Edition)\"\n",
+/// );
+/// ```
+impl fmt::Display for OsRelease {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display_redacted(&[]).fmt(f)
+    }
+}
+
+/// Formats the entry as a line of an os-release file, without the line terminator.
+///
+/// The value is quoted and escaped in the same way as in the [`Display`](fmt::Display) implementation of [`OsRelease`],
+/// so that parsing the line yields the same entry.
+/// The only exception is values containing line breaks, which cannot be represented in a single line:
+/// each `\n` and `\r` is written as a space.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::OsReleaseEntry;
+///
+/// assert_eq!(OsReleaseEntry::new("ID", "fedora").to_string(), "ID=fedora");
+/// assert_eq!(
+///     OsReleaseEntry::new("NAME", r#"Vendor's "OS""#).to_string(),
+///     r#"NAME="Vendor's \"OS\"""#,
+/// );
+/// ```
+impl fmt::Display for OsReleaseEntry<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.key(), entry::quote(self.value()))
+    }
+}
+
+/// Formats the line as a line of an os-release file, without the line terminator.
+///
+/// Comments are written verbatim, and [`OsReleaseLine::Empty`] is formatted as an empty line,
+/// so that per-line rewrite tools keep the documentation and the line numbers.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::{parse_lines, OsReleaseEntry, OsReleaseLine};
+///
+/// let input = "# comment\nVERSION_ID=39\n";
+/// let output = parse_lines(input)
+///     .map(|line| match line {
+///         OsReleaseLine::Entry(entry) if entry.key() == "VERSION_ID" => {
+///             format!("{}\n", OsReleaseEntry::new("VERSION_ID", "40"))
+///         }
+///         line => format!("{line}\n"),
+///     })
+///     .collect::<String>();
+/// assert_eq!(output, "# comment\nVERSION_ID=40\n");
+/// ```
+impl fmt::Display for OsReleaseLine<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => Ok(()),
+            Self::Comment(comment) => f.write_str(comment),
+            Self::Entry(entry) => entry.fmt(f),
+        }
+    }
+}
+
+/// The value written in place of redacted values.
+const REDACTED: &str = "***";
+
+/// A wrapper to format an `OsRelease` with some values redacted.
+///
+/// See [`OsRelease::display_redacted()`].
+#[derive(Debug, Clone, Copy)]
+pub struct Redacted<'a> {
+    os_release: &'a OsRelease,
+    keys: &'a [&'a str],
+}
+
+impl fmt::Display for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in self.os_release.entries() {
+            let value = if self.keys.contains(&entry.key()) {
+                REDACTED
+            } else {
+                entry.value()
+            };
+            writeln!(f, "{}={}", entry.key(), entry::quote(value))?;
+        }
+        Ok(())
+    }
+}
+
+/// A field whose value changed when an os-release file was parsed, formatted and parsed again.
+///
+/// See [`OsRelease::roundtrip_check()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RoundtripDivergence {
+    key: String,
+    original: Option<String>,
+    reparsed: Option<String>,
+}
+
+impl RoundtripDivergence {
+    /// Returns the key of the field.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the value of the field parsed from the original input.
+    pub fn original(&self) -> Option<&str> {
+        self.original.as_deref()
+    }
+
+    /// Returns the value of the field parsed from the formatted output.
+    pub fn reparsed(&self) -> Option<&str> {
+        self.reparsed.as_deref()
+    }
+}
+
+/// Methods to format the os-release file.
+impl OsRelease {
+    /// Returns a wrapper to format the fields as an os-release file, replacing the values of `keys` with `***`.
+    ///
+    /// The formatted output has the same structure as the [`Display`](fmt::Display) output,
+    /// so that support bundles can include the shape of the file without leaking sensitive values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// NAME=Fedora
+    /// IMAGE_ID=customer-appliance
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(
+    ///     os_release.display_redacted(&["IMAGE_ID"]).to_string(),
+    ///     "NAME=Fedora\nIMAGE_ID=\"***\"\n",
+    /// );
+    /// ```
+    pub fn display_redacted<'a>(&'a self, keys: &'a [&'a str]) -> Redacted<'a> {
+        Redacted {
+            os_release: self,
+            keys,
+        }
+    }
+
+    /// Formats the fields as an os-release file in a canonical form.
+    ///
+    /// The fields defined by os-release(5) are written first in the order of the man page,
+    /// followed by the other fields sorted by key, and all values are double-quoted.
+    /// Files with the same fields are formatted identically regardless of their original order and quoting,
+    /// so generated files are reproducible.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// VENDOR_BUILD=7
+    /// VERSION_ID=40
+    /// ANSI_COLOR='0;38;2;60;110;180'
+    /// ID=fedora
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(
+    ///     os_release.to_canonical_string(),
+    ///     "ID=\"fedora\"\nVERSION_ID=\"40\"\nANSI_COLOR=\"0;38;2;60;110;180\"\nVENDOR_BUILD=\"7\"\n",
+    /// );
+    /// ```
+    pub fn to_canonical_string(&self) -> String {
+        let mut others = self
+            .fields
+            .iter()
+            .filter(|(key, _)| !keys::is_known_key(key))
+            .collect::<Vec<_>>();
+        others.sort_unstable_by_key(|(key, _)| *key);
+
+        let spec = keys::ALL
+            .iter()
+            .filter_map(|&key| Some((key, self.get_value(key)?)));
+        let others = others
+            .into_iter()
+            .map(|(key, value)| (key.as_ref(), value.as_ref()));
+
+        let mut output = String::new();
+        for (key, value) in spec.chain(others) {
+            writeln!(output, "{key}={}", entry::double_quote(value)).unwrap();
+        }
+        output
+    }
+
+    /// Formats the fields as `export` commands of a POSIX shell, in the order of the file.
+    ///
+    /// Each field is written on its own line as `export KEY='value'`, with single quotes in the value escaped as `'\''`,
+    /// so the output can be safely `eval`ed by shell scripts or read by dotenv-style consumers.
+    /// Fields whose keys are not valid shell variable names are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// NAME=Fedora
+    /// PRETTY_NAME="Fedora's \$HOME"
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(
+    ///     os_release.to_shell_exports(),
+    ///     "export NAME='Fedora'\nexport PRETTY_NAME='Fedora'\\''s $HOME'\n",
+    /// );
+    /// ```
+    pub fn to_shell_exports(&self) -> String {
+        let mut output = String::new();
+        for entry in self
+            .entries()
+            .filter(|entry| entry::is_valid_key(entry.key()))
+        {
+            let value = entry.value().replace('\'', r"'\''");
+            writeln!(output, "export {}='{value}'", entry.key()).unwrap();
+        }
+        output
+    }
+
+    /// Returns a stable 64-bit fingerprint of the fields.
+    ///
+    /// This is the 64-bit FNV-1a hash of the fields written as `KEY=VALUE` lines sorted by key,
+    /// with `\`, `=` and newlines escaped with a backslash (a newline as `\n`).
+    /// So it does not depend on the order and quoting of the fields, and is the same across platforms and versions of this crate.
+    /// Unlike [`Self::to_canonical_string()`], the format does not depend on the keys known to this crate.
+    /// Caching layers can store it to detect changes of the operating system cheaply.
+    ///
+    /// The fingerprint is not a cryptographic hash, and must not be used to detect tampering.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let a = OsRelease::from_str("ID=fedora\nVERSION_ID=40\n").unwrap();
+    /// let b = OsRelease::from_str("VERSION_ID='40'\nID=\"fedora\"\n").unwrap();
+    /// let c = OsRelease::from_str("ID=fedora\nVERSION_ID=41\n").unwrap();
+    /// assert_eq!(a.fingerprint(), b.fingerprint());
+    /// assert_ne!(a.fingerprint(), c.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        // The format is frozen: changing it changes the fingerprints stored by users.
+        fn escaped(s: &str) -> impl Iterator<Item = u8> + '_ {
+            s.bytes()
+                .flat_map(|b| match b {
+                    b'\\' => [Some(b'\\'), Some(b'\\')],
+                    b'=' => [Some(b'\\'), Some(b'=')],
+                    b'\n' => [Some(b'\\'), Some(b'n')],
+                    b => [Some(b), None],
+                })
+                .flatten()
+        }
+
+        let mut fields = self.fields.iter().collect::<Vec<_>>();
+        fields.sort_unstable_by_key(|(key, _)| *key);
+        fields
+            .into_iter()
+            .flat_map(|(key, value)| {
+                escaped(key)
+                    .chain([b'='])
+                    .chain(escaped(value))
+                    .chain([b'\n'])
+            })
+            .fold(OFFSET_BASIS, |hash, b| {
+                (hash ^ u64::from(b)).wrapping_mul(PRIME)
+            })
+    }
+
+    /// Checks that the given os-release file survives a parse/format cycle unchanged.
+    ///
+    /// The input is parsed, formatted with the [`Display`](fmt::Display) implementation, and parsed again.
+    /// Returns the fields whose values differ between the two parse results,
+    /// or an empty list if the values are preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use etc_os_release::OsRelease;
+    ///
+    /// assert!(OsRelease::roundtrip_check(r#"NAME="Fedora \"Linux\"""#).is_empty());
+    /// ```
+    pub fn roundtrip_check(input: &str) -> Vec<RoundtripDivergence> {
+        let original = input.lines().collect::<Self>();
+        let output = original.to_string();
+        let reparsed = output.lines().collect::<Self>();
+
+        let added = reparsed
+            .fields
+            .keys()
+            .filter(|key| !original.fields.contains_key(key.as_ref()));
+        original
+            .fields
+            .keys()
+            .chain(added)
+            .filter_map(|key| {
+                let original = original.get_value(key);
+                let reparsed = reparsed.get_value(key);
+                (original != reparsed).then(|| RoundtripDivergence {
+                    key: key.to_string(),
+                    original: original.map(str::to_owned),
+                    reparsed: reparsed.map(str::to_owned),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_check() {
+        let inputs = [
+            "A=B",
+            "A=",
+            r#"A="B C""#,
+            r#"A='B C'"#,
+            r#"A="\"\\\$\`""#,
+            r#"A='\"'"#,
+            "A=\"B\tC\"",
+            "A=\"B # C\"",
+            "A=\"B;C\"\nB=22.04\nC=cpe:/o:fedoraproject:fedora:32",
+            "A=B\nA=C",
+        ];
+        for input in inputs {
+            assert_eq!(OsRelease::roundtrip_check(input), vec![], "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_display_line_breaks() {
+        let entry = OsReleaseEntry::new("NAME", "a\nb\r\nc");
+        assert_eq!(entry.to_string(), r#"NAME="a b  c""#);
+
+        let os_release = OsRelease::from_iter([entry, OsReleaseEntry::new("ID", "x")]);
+        let output = os_release.to_string();
+        assert_eq!(output.lines().count(), 2);
+        assert!(OsRelease::roundtrip_check(&output).is_empty());
+        assert_eq!(OsRelease::from_str(&output).unwrap().name(), "a b  c");
+        assert_eq!(os_release.to_canonical_string().lines().count(), 2);
+        assert_eq!(
+            os_release
+                .display_redacted(&["ID"])
+                .to_string()
+                .lines()
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_display_entry() {
+        for value in ["", "fedora", "a b", "'\"$`\\", "x=y", "caf\u{e9}"] {
+            let line = OsReleaseEntry::new("KEY", value).to_string();
+            let entry = entry::parse_line(&line).unwrap();
+            assert_eq!((entry.key(), entry.value()), ("KEY", value), "{line}");
+            assert!(OsReleaseLine::parse_strict(&line).is_ok(), "{line}");
+        }
+        assert_eq!(OsReleaseLine::Empty.to_string(), "");
+
+        let input = "  # comment # with \"quotes\"\r\n\nID=fedora\n";
+        let output = crate::parse_lines(input)
+            .map(|line| format!("{line}\n"))
+            .collect::<String>();
+        assert_eq!(output, "  # comment # with \"quotes\"\n\nID=fedora\n");
+    }
+
+    #[test]
+    fn test_to_canonical_string() {
+        let a = "B=1\nNAME=x\nA='$y'\nID=z\n".lines().collect::<OsRelease>();
+        let b = "ID=\"z\"\nA=\"\\$y\"\nNAME='x'\nB=\"1\"\n"
+            .lines()
+            .collect::<OsRelease>();
+        let canonical = a.to_canonical_string();
+        assert_eq!(canonical, "NAME=\"x\"\nID=\"z\"\nA=\"\\$y\"\nB=\"1\"\n");
+        assert_eq!(b.to_canonical_string(), canonical);
+
+        let reparsed = canonical.lines().collect::<OsRelease>();
+        assert_eq!(reparsed, a);
+    }
+
+    #[test]
+    fn test_to_shell_exports() {
+        let os_release = "A=\nB='\\'\nC=\"`x` $(y)\"\n-C=z\n"
+            .lines()
+            .collect::<OsRelease>();
+        assert_eq!(
+            os_release.to_shell_exports(),
+            "export A=''\nexport B='\\'\nexport C='`x` $(y)'\n"
+        );
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        // The fingerprint must not change across versions of this crate.
+        assert_eq!(
+            OsRelease::from_str("").unwrap().fingerprint(),
+            0xcbf2_9ce4_8422_2325
+        );
+        assert_eq!(
+            OsRelease::from_str("ID=fedora").unwrap().fingerprint(),
+            0xc7b4_15ea_9144_8ba0
+        );
+
+        // Non-spec keys, quoting and escaped characters, independent of the order of the fields
+        let golden = 0x5282_fbe8_e1f3_b2aa;
+        let mut a = OsRelease::from_str("VENDOR_BUILD='a=b\\c'\nID=\"fedora\"\n").unwrap();
+        a.extend([OsReleaseEntry::new("EXPERIMENT", "x\ny")]);
+        let b = OsRelease::from_iter([
+            OsReleaseEntry::new("EXPERIMENT", "x\ny"),
+            OsReleaseEntry::new("ID", "fedora"),
+            OsReleaseEntry::new("VENDOR_BUILD", "a=b\\c"),
+        ]);
+        assert_eq!(a.fingerprint(), golden);
+        assert_eq!(b.fingerprint(), golden);
+        assert_ne!(
+            OsRelease::from_iter([OsReleaseEntry::new("A", "1\nB=2")]).fingerprint(),
+            OsRelease::from_iter([OsReleaseEntry::new("A", "1"), OsReleaseEntry::new("B", "2")])
+                .fingerprint()
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use std::error::Error as StdError;
+
+#[cfg(feature = "date")]
+use chrono::NaiveDate;
+#[cfg(feature = "url")]
+use url::Url;
+
+#[cfg(feature = "date")]
+use crate::fields;
+use crate::OsRelease;
+
+/// A type which can be built from the fields of an [`OsRelease`].
+///
+/// With the `derive` feature, this can be derived for structs with named fields.
+/// Each field is read from the key of its name in upper case, or the key given by `#[os_release(rename = "KEY")]`,
+/// and converted with [`FromOsReleaseField`].
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use std::str::FromStr;
+///
+/// use etc_os_release::{FromOsRelease, OsRelease};
+///
+/// #[derive(FromOsRelease)]
+/// struct MyOs {
+///     id: String,
+///     version_id: Option<String>,
+///     #[os_release(rename = "SYSEXT_LEVEL")]
+///     level: Option<u32>,
+/// }
+///
+/// let os_release = OsRelease::from_str("ID=fedora\nSYSEXT_LEVEL=2\n").unwrap();
+/// let my_os = MyOs::from_os_release(&os_release).unwrap();
+/// assert_eq!(my_os.id, "fedora");
+/// assert_eq!(my_os.version_id, None);
+/// assert_eq!(my_os.level, Some(2));
+///
+/// let os_release = OsRelease::from_str("VERSION_ID=40\n").unwrap();
+/// assert_eq!(
+///     MyOs::from_os_release(&os_release).err().unwrap().to_string(),
+///     "missing field `ID`",
+/// );
+/// # }
+/// ```
+pub trait FromOsRelease: Sized {
+    /// Builds the value from the fields of the os-release file.
+    fn from_os_release(os_release: &OsRelease) -> Result<Self, FromOsReleaseError>;
+}
+
+/// Derives [`FromOsRelease`] for a struct with named fields.
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use etc_os_release_derive::FromOsRelease;
+
+/// A type which can be converted from the value of a field in the os-release file.
+///
+/// `Option<T>` accepts unset fields, and the other types return [`FromOsReleaseError::Missing`] for them.
+pub trait FromOsReleaseField: Sized {
+    /// Converts the value of the field `key`, or `None` if the field is not set.
+    fn from_field(key: &str, value: Option<&str>) -> Result<Self, FromOsReleaseError>;
+}
+
+/// An error which can be returned when building a value with [`FromOsRelease`].
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum FromOsReleaseError {
+    /// A required field is not set.
+    #[error("missing field `{0}`")]
+    Missing(String),
+    /// The value of a field could not be converted.
+    #[error("invalid value {raw_value:?} of field `{key}`")]
+    Invalid {
+        /// The key of the field.
+        key: String,
+        /// The value of the field as written in the os-release file.
+        raw_value: String,
+        /// The underlying error of the conversion.
+        #[source]
+        source: Box<dyn StdError + Send + Sync>,
+    },
+}
+
+fn require<'a>(key: &str, value: Option<&'a str>) -> Result<&'a str, FromOsReleaseError> {
+    value.ok_or_else(|| FromOsReleaseError::Missing(key.to_owned()))
+}
+
+fn invalid(
+    key: &str,
+    value: &str,
+    err: impl Into<Box<dyn StdError + Send + Sync>>,
+) -> FromOsReleaseError {
+    FromOsReleaseError::Invalid {
+        key: key.to_owned(),
+        raw_value: value.to_owned(),
+        source: err.into(),
+    }
+}
+
+impl<T: FromOsReleaseField> FromOsReleaseField for Option<T> {
+    fn from_field(key: &str, value: Option<&str>) -> Result<Self, FromOsReleaseError> {
+        value
+            .map(|value| T::from_field(key, Some(value)))
+            .transpose()
+    }
+}
+
+impl FromOsReleaseField for String {
+    fn from_field(key: &str, value: Option<&str>) -> Result<Self, FromOsReleaseError> {
+        require(key, value).map(str::to_owned)
+    }
+}
+
+/// Splits the value into a space-separated list.
+impl FromOsReleaseField for Vec<String> {
+    fn from_field(key: &str, value: Option<&str>) -> Result<Self, FromOsReleaseError> {
+        let value = require(key, value)?;
+        Ok(value.split_whitespace().map(str::to_owned).collect())
+    }
+}
+
+macro_rules! impl_from_str {
+    ($($ty:ty),*) => {
+        $(
+            impl FromOsReleaseField for $ty {
+                fn from_field(key: &str, value: Option<&str>) -> Result<Self, FromOsReleaseError> {
+                    let value = require(key, value)?;
+                    value.parse().map_err(|e| invalid(key, value, e))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_str!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+#[cfg(feature = "date")]
+#[cfg_attr(docsrs, doc(cfg(feature = "date")))]
+/// Parses the value leniently, as [`OsRelease::get_value_as_date_lenient()`].
+impl FromOsReleaseField for NaiveDate {
+    fn from_field(key: &str, value: Option<&str>) -> Result<Self, FromOsReleaseError> {
+        let value = require(key, value)?;
+        fields::parse_date_lenient(value).map_err(|e| invalid(key, value, e))
+    }
+}
+
+#[cfg(feature = "url")]
+#[cfg_attr(docsrs, doc(cfg(feature = "url")))]
+impl FromOsReleaseField for Url {
+    fn from_field(key: &str, value: Option<&str>) -> Result<Self, FromOsReleaseError> {
+        let value = require(key, value)?;
+        Url::parse(value).map_err(|e| invalid(key, value, e))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_field() {
+        assert_eq!(String::from_field("ID", Some("fedora")).unwrap(), "fedora");
+        assert!(matches!(
+            String::from_field("ID", None),
+            Err(FromOsReleaseError::Missing(key)) if key == "ID"
+        ));
+        assert_eq!(Option::<String>::from_field("ID", None).unwrap(), None);
+        assert_eq!(
+            Vec::<String>::from_field("ID_LIKE", Some("rhel centos")).unwrap(),
+            ["rhel", "centos"]
+        );
+
+        assert_eq!(u32::from_field("SYSEXT_LEVEL", Some("15")).unwrap(), 15);
+        let err = Option::<u32>::from_field("SYSEXT_LEVEL", Some("x")).unwrap_err();
+        assert!(matches!(
+            &err,
+            FromOsReleaseError::Invalid { key, raw_value, .. } if key == "SYSEXT_LEVEL" && raw_value == "x"
+        ));
+        assert_eq!(
+            err.to_string(),
+            r#"invalid value "x" of field `SYSEXT_LEVEL`"#
+        );
+    }
+
+    #[cfg(feature = "date")]
+    #[test]
+    fn test_from_field_date() {
+        let date = NaiveDate::from_ymd_opt(2025, 5, 1).unwrap();
+        for value in ["2025-05-01", "2025-05-01T00:00:00Z", "2025-05"] {
+            assert_eq!(
+                NaiveDate::from_field("SUPPORT_END", Some(value)).unwrap(),
+                date
+            );
+        }
+        assert!(NaiveDate::from_field("SUPPORT_END", Some("x")).is_err());
+    }
+}
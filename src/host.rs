@@ -0,0 +1,109 @@
+use std::path::Path;
+
+use crate::{Error, OsRelease, ParseOptions};
+
+/// The result of comparing two operating systems, such as a container and its host.
+///
+/// See [`OsRelease::compare()`] and [`OsRelease::compare_host_guest()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OsComparison {
+    same_distro: bool,
+    same_version: bool,
+}
+
+impl OsComparison {
+    /// Returns `true` if both operating systems have the same [`OsRelease::id()`].
+    pub fn same_distro(&self) -> bool {
+        self.same_distro
+    }
+
+    /// Returns `true` if both operating systems are the same distribution with the same [`OsRelease::version_id()`].
+    ///
+    /// Two distributions without `VERSION_ID=` (e.g. rolling releases) are considered to have the same version.
+    pub fn same_version(&self) -> bool {
+        self.same_version
+    }
+}
+
+/// Methods to compare operating systems.
+impl OsRelease {
+    /// Compares the identity of this operating system with another one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let host = OsRelease::from_str("ID=fedora\nVERSION_ID=40\n").unwrap();
+    /// let guest = OsRelease::from_str("ID=fedora\nVERSION_ID=41\n").unwrap();
+    ///
+    /// let comparison = guest.compare(&host);
+    /// assert!(comparison.same_distro());
+    /// assert!(!comparison.same_version());
+    /// ```
+    pub fn compare(&self, other: &Self) -> OsComparison {
+        let same_distro = self.id() == other.id();
+        OsComparison {
+            same_distro,
+            same_version: same_distro && self.version_id() == other.version_id(),
+        }
+    }
+
+    /// Compares the os-release file of the running container with the one of its host.
+    ///
+    /// The host's os-release file is read from `/run/host/os-release` or `/run/host/usr/lib/os-release`,
+    /// which are provided by container managers such as systemd-nspawn, Flatpak, toolbox and distrobox.
+    /// The container's os-release file is read as in [`Self::open()`].
+    ///
+    /// Returns [`Error::NoOsRelease`] if either file is not found.
+    pub fn compare_host_guest() -> Result<OsComparison, Error> {
+        let options = ParseOptions::new();
+        let host_path = host_os_release_path().ok_or(Error::NoOsRelease)?;
+        let host = Self::open_path_with(host_path, &options)?;
+        let guest = Self::open_with(&options)?;
+        Ok(guest.compare(&host))
+    }
+}
+
+/// Methods to read the os-release file of the host from inside containers.
+impl OsRelease {
+    /// Open the os-release file of the host and parse it.
+    ///
+    /// When running in a container, the host's os-release file is read from `/run/host/os-release` or
+    /// `/run/host/usr/lib/os-release`, which are provided by container managers such as systemd-nspawn, Flatpak,
+    /// toolbox and distrobox.
+    /// If neither exists, the process is assumed to run on the host, and the os-release file is read as in [`Self::open()`].
+    pub fn open_host() -> Result<Self, Error> {
+        match host_os_release_path() {
+            Some(path) => Self::open_path_with(path, &ParseOptions::new()),
+            None => Self::open(),
+        }
+    }
+
+    /// Returns `true` if the os-release file read by [`Self::open()`] likely belongs to a container rather than the host.
+    ///
+    /// This is a hint based on the files container managers create: the host's os-release file in `/run/host`,
+    /// `/run/systemd/container` (systemd-nspawn and others), `/run/.containerenv` (Podman) and `/.dockerenv` (Docker).
+    pub fn is_container_os_release() -> bool {
+        host_os_release_path().is_some()
+            || [
+                Path::new("/run/systemd/container"),
+                Path::new("/run/.containerenv"),
+                Path::new("/.dockerenv"),
+            ]
+            .into_iter()
+            .any(|path| path.exists())
+    }
+}
+
+/// Find the os-release file of the host, as exposed to containers.
+fn host_os_release_path() -> Option<&'static Path> {
+    [
+        Path::new("/run/host/os-release"),
+        Path::new("/run/host/usr/lib/os-release"),
+    ]
+    .into_iter()
+    .find(|path| path.exists())
+}
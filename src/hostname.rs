@@ -0,0 +1,223 @@
+use crate::OsRelease;
+
+/// Operating system properties exposed by [`systemd-hostnamed.service(8)`].
+///
+/// These are the `OperatingSystem*` properties of the `org.freedesktop.hostname1` D-Bus interface.
+/// With the `dbus` feature, `OsRelease::from_dbus()` queries them from the system bus.
+/// Otherwise, query the properties with the D-Bus library of your choice,
+/// and convert them with [`OsRelease::from_hostname_properties()`].
+/// This allows sandboxed applications which cannot read `/etc` to identify the operating system.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::{HostnameProperties, OsRelease};
+///
+/// // e.g. values of `OperatingSystemPrettyName`, `OperatingSystemCPEName`, `HomeURL` and `OperatingSystemSupportEnd`
+/// let properties = HostnameProperties::new()
+///     .pretty_name("Fedora Linux 40 (Workstation Edition)")
+///     .cpe_name("cpe:/o:fedoraproject:fedora:40")
+///     .home_url("https://fedoraproject.org/")
+///     .support_end_usec(1_747_094_400_000_000);
+/// let os_release = OsRelease::from_hostname_properties(&properties);
+///
+/// assert_eq!(os_release.pretty_name(), "Fedora Linux 40 (Workstation Edition)");
+/// assert_eq!(os_release.get_value("SUPPORT_END"), Some("2025-05-13"));
+/// ```
+///
+/// [`systemd-hostnamed.service(8)`]: https://www.freedesktop.org/software/systemd/man/systemd-hostnamed.service.html
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HostnameProperties {
+    pretty_name: Option<String>,
+    cpe_name: Option<String>,
+    home_url: Option<String>,
+    support_end_usec: Option<u64>,
+}
+
+impl HostnameProperties {
+    /// Creates an empty set of properties.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value of `OperatingSystemPrettyName`.
+    ///
+    /// Empty strings are ignored, as hostnamed reports unset fields as empty strings.
+    pub fn pretty_name(mut self, pretty_name: impl Into<String>) -> Self {
+        self.pretty_name = non_empty(pretty_name.into());
+        self
+    }
+
+    /// Sets the value of `OperatingSystemCPEName`.
+    ///
+    /// Empty strings are ignored, as hostnamed reports unset fields as empty strings.
+    pub fn cpe_name(mut self, cpe_name: impl Into<String>) -> Self {
+        self.cpe_name = non_empty(cpe_name.into());
+        self
+    }
+
+    /// Sets the value of `HomeURL`.
+    ///
+    /// Empty strings are ignored, as hostnamed reports unset fields as empty strings.
+    pub fn home_url(mut self, home_url: impl Into<String>) -> Self {
+        self.home_url = non_empty(home_url.into());
+        self
+    }
+
+    /// Sets the value of `OperatingSystemSupportEnd`, in microseconds since the UNIX epoch.
+    ///
+    /// `0` and `u64::MAX` are ignored, as hostnamed reports an unset `SUPPORT_END=` as either of them.
+    pub fn support_end_usec(mut self, usec: u64) -> Self {
+        self.support_end_usec = Some(usec).filter(|&usec| usec != 0 && usec != u64::MAX);
+        self
+    }
+}
+
+fn non_empty(s: String) -> Option<String> {
+    Some(s).filter(|s| !s.is_empty())
+}
+
+/// Methods to construct an `OsRelease` from the properties of systemd-hostnamed.
+impl OsRelease {
+    /// Creates an `OsRelease` from the properties of `org.freedesktop.hostname1`.
+    ///
+    /// The following fields are set, if the corresponding property is set:
+    ///
+    /// * `PRETTY_NAME=` from `OperatingSystemPrettyName`
+    /// * `CPE_NAME=` from `OperatingSystemCPEName`
+    /// * `HOME_URL=` from `HomeURL`
+    /// * `SUPPORT_END=` from `OperatingSystemSupportEnd`, as a date in UTC
+    ///
+    /// Other fields, such as `ID=`, are not exposed by hostnamed, so their getters return the defaults.
+    pub fn from_hostname_properties(properties: &HostnameProperties) -> Self {
+        crate::platform::from_optional_fields([
+            ("PRETTY_NAME", properties.pretty_name.clone()),
+            ("CPE_NAME", properties.cpe_name.clone()),
+            ("HOME_URL", properties.home_url.clone()),
+            ("SUPPORT_END", properties.support_end_usec.map(format_date)),
+        ])
+    }
+}
+
+/// Methods to serialize an `OsRelease` in the format of hostnamectl.
+#[cfg(feature = "json")]
+impl OsRelease {
+    /// Returns the operating system fields in the same format as `hostnamectl --json=short`.
+    ///
+    /// The following keys are included, with `null` for unset fields:
+    ///
+    /// * `OperatingSystemPrettyName` from `PRETTY_NAME=`
+    /// * `OperatingSystemCPEName` from `CPE_NAME=`
+    /// * `OperatingSystemHomeURL` from `HOME_URL=`
+    /// * `OperatingSystemSupportEnd` from `SUPPORT_END=`, in microseconds since the UNIX epoch
+    /// * `OperatingSystemReleaseData`, all fields as `KEY=VALUE` strings
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// NAME="Fedora Linux"
+    /// ID=fedora
+    /// PRETTY_NAME="Fedora Linux 40 (Workstation Edition)"
+    /// SUPPORT_END=2025-05-13
+    /// "#).unwrap();
+    /// let json = os_release.to_hostnamectl_json();
+    ///
+    /// assert_eq!(json["OperatingSystemPrettyName"], "Fedora Linux 40 (Workstation Edition)");
+    /// assert_eq!(json["OperatingSystemCPEName"], serde_json::Value::Null);
+    /// assert_eq!(json["OperatingSystemSupportEnd"], 1_747_094_400_000_000u64);
+    /// assert_eq!(json["OperatingSystemReleaseData"][1], "ID=fedora");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn to_hostnamectl_json(&self) -> serde_json::Value {
+        let release_data = self
+            .entries()
+            .map(|entry| format!("{}={}", entry.key(), entry.value()))
+            .collect::<Vec<_>>();
+        serde_json::json!({
+            "OperatingSystemPrettyName": self.get_value("PRETTY_NAME"),
+            "OperatingSystemCPEName": self.get_value("CPE_NAME"),
+            "OperatingSystemHomeURL": self.get_value("HOME_URL"),
+            "OperatingSystemSupportEnd": self.get_value("SUPPORT_END").and_then(parse_date),
+            "OperatingSystemReleaseData": release_data,
+        })
+    }
+}
+
+/// Parse a `YYYY-MM-DD` date as a timestamp in microseconds since the UNIX epoch.
+#[cfg(feature = "json")]
+fn parse_date(s: &str) -> Option<u64> {
+    const USEC_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000;
+
+    let mut parts = s.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // Convert a civil date to days since the epoch (http://howardhinnant.github.io/date_algorithms.html#days_from_civil)
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    u64::try_from(days.checked_mul(USEC_PER_DAY)?).ok()
+}
+
+/// Format a timestamp in microseconds since the UNIX epoch as a `YYYY-MM-DD` date in UTC.
+fn format_date(usec: u64) -> String {
+    const USEC_PER_DAY: u64 = 24 * 60 * 60 * 1_000_000;
+
+    // Convert days since the epoch to a civil date (http://howardhinnant.github.io/date_algorithms.html#civil_from_days)
+    let z = (usec / USEC_PER_DAY) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_date() {
+        const USEC_PER_DAY: u64 = 24 * 60 * 60 * 1_000_000;
+        assert_eq!(format_date(1), "1970-01-01");
+        assert_eq!(format_date(USEC_PER_DAY - 1), "1970-01-01");
+        assert_eq!(format_date(USEC_PER_DAY), "1970-01-02");
+        assert_eq!(format_date(951_782_400 * 1_000_000), "2000-02-29");
+        assert_eq!(format_date(1_709_251_199 * 1_000_000), "2024-02-29");
+        assert_eq!(format_date(1_893_456_000 * 1_000_000), "2030-01-01");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_parse_date() {
+        for date in [
+            "1970-01-01",
+            "2000-02-29",
+            "2024-02-29",
+            "2024-03-01",
+            "2030-01-01",
+        ] {
+            assert_eq!(format_date(parse_date(date).unwrap()), date);
+        }
+        assert_eq!(parse_date("1969-12-31"), None);
+        assert_eq!(parse_date("2024-13-01"), None);
+        assert_eq!(parse_date("2024-01"), None);
+    }
+}
@@ -0,0 +1,124 @@
+use std::path::{Path, PathBuf};
+
+use crate::{construct, Error, OsRelease};
+
+/// The identity of the operating system tree, found by [`OsRelease::identify_root()`].
+#[derive(Debug, Clone)]
+pub enum OsIdentity {
+    /// The operating system is identified by its os-release file.
+    Identified(OsRelease),
+    /// The operating system has no os-release file.
+    Unidentified(NoOsIdentity),
+}
+
+/// The classification of an operating system tree without os-release file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NoOsIdentity {
+    /// The tree has no `/etc` directory, such as a `FROM scratch` container image.
+    Scratch,
+    /// The tree has an `/etc` directory, but no release file, such as a distroless container image.
+    Distroless,
+    /// The tree has a distribution-specific release file, such as `/etc/redhat-release`, but no os-release file.
+    ///
+    /// The path is relative to the root of the tree.
+    LegacyReleaseFile(PathBuf),
+}
+
+/// Distribution-specific release files predating os-release.
+const LEGACY_RELEASE_FILES: &[&str] = &[
+    "etc/lsb-release",
+    "etc/redhat-release",
+    "etc/SuSE-release",
+    "etc/debian_version",
+    "etc/alpine-release",
+    "etc/arch-release",
+    "etc/gentoo-release",
+    "etc/slackware-version",
+];
+
+/// Methods to identify operating system trees.
+impl OsRelease {
+    /// Identifies the operating system tree at `root`.
+    ///
+    /// If the tree has an os-release file, it is parsed as in [`Self::open_root()`].
+    /// Otherwise, the tree is classified by the other files it contains, instead of returning [`Error::NoOsRelease`].
+    /// This is useful for scanners to label scratch and distroless container images correctly.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use etc_os_release::{NoOsIdentity, OsIdentity, OsRelease};
+    ///
+    /// match OsRelease::identify_root("/path/to/unpacked/image")? {
+    ///     OsIdentity::Identified(os_release) => println!("{}", os_release.pretty_name()),
+    ///     OsIdentity::Unidentified(NoOsIdentity::Scratch) => println!("scratch image"),
+    ///     OsIdentity::Unidentified(other) => println!("unknown OS: {other:?}"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn identify_root(root: impl AsRef<Path>) -> Result<OsIdentity, Error> {
+        let root = root.as_ref();
+        match Self::open_root(root) {
+            Ok(os_release) => return Ok(OsIdentity::Identified(os_release)),
+            Err(Error::NoOsRelease) => {}
+            Err(err) => return Err(err),
+        }
+
+        let exists =
+            |path: &Path| construct::resolve_in_root(root, path).is_some_and(|p| p.exists());
+        let identity = if !exists(Path::new("etc")) {
+            NoOsIdentity::Scratch
+        } else if let Some(path) = LEGACY_RELEASE_FILES
+            .iter()
+            .map(Path::new)
+            .find(|path| exists(path))
+        {
+            NoOsIdentity::LegacyReleaseFile(path.to_owned())
+        } else {
+            NoOsIdentity::Distroless
+        };
+        Ok(OsIdentity::Unidentified(identity))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env, fs};
+
+    use super::*;
+
+    #[test]
+    fn test_identify_root() {
+        let root = env::temp_dir().join(format!("etc-os-release-identity-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+
+        let identify = || match OsRelease::identify_root(&root).unwrap() {
+            OsIdentity::Identified(os_release) => Ok(os_release.id().to_owned()),
+            OsIdentity::Unidentified(identity) => Err(identity),
+        };
+
+        assert_eq!(identify(), Err(NoOsIdentity::Scratch));
+
+        fs::create_dir_all(root.join("etc")).unwrap();
+        assert_eq!(identify(), Err(NoOsIdentity::Distroless));
+
+        fs::write(root.join("etc/debian_version"), "12.5\n").unwrap();
+        assert_eq!(
+            identify(),
+            Err(NoOsIdentity::LegacyReleaseFile("etc/debian_version".into()))
+        );
+
+        // absolute symlinks are resolved relative to the root
+        fs::create_dir_all(root.join("usr/lib")).unwrap();
+        fs::write(root.join("usr/lib/os-release"), "ID=debian\n").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink("/usr/lib/os-release", root.join("etc/os-release")).unwrap();
+        assert_eq!(identify(), Ok("debian".to_owned()));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,224 @@
+use std::{borrow::Cow, iter::FusedIterator, sync::Arc};
+
+use crate::{fields::intern_key, map, OsRelease, OsReleaseEntry};
+
+/// An iterator over the entries of an [`OsRelease`], in the order of the file.
+///
+/// This is returned by [`OsRelease::entries()`] and `<&OsRelease as IntoIterator>::into_iter()`.
+#[derive(Debug, Clone)]
+pub struct Entries<'a> {
+    iter: map::Iter<'a, Cow<'static, str>, Box<str>>,
+}
+
+impl<'a> Entries<'a> {
+    pub(crate) fn new(os_release: &'a OsRelease) -> Self {
+        Self {
+            iter: os_release.fields.iter(),
+        }
+    }
+}
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = OsReleaseEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|(k, v)| OsReleaseEntry::new(k.as_ref(), v.as_ref()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Entries<'_> {}
+impl FusedIterator for Entries<'_> {}
+
+/// An owning iterator over the entries of an [`OsRelease`], in the order of the file.
+///
+/// This is returned by `<OsRelease as IntoIterator>::into_iter()`.
+#[derive(Debug)]
+pub struct IntoEntries {
+    iter: map::IntoIter<Cow<'static, str>, Box<str>>,
+}
+
+impl Iterator for IntoEntries {
+    type Item = OsReleaseEntry<'static>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|(k, v)| OsReleaseEntry::new(k, String::from(v)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl ExactSizeIterator for IntoEntries {}
+impl FusedIterator for IntoEntries {}
+
+impl<'a> IntoIterator for &'a OsRelease {
+    type Item = OsReleaseEntry<'a>;
+    type IntoIter = Entries<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Entries::new(self)
+    }
+}
+
+impl IntoIterator for OsRelease {
+    type Item = OsReleaseEntry<'static>;
+    type IntoIter = IntoEntries;
+
+    /// Returns the entries, without copying them unless the fields are shared with a clone.
+    fn into_iter(self) -> Self::IntoIter {
+        let fields = Arc::try_unwrap(self.fields).unwrap_or_else(|fields| (*fields).clone());
+        IntoEntries {
+            iter: fields.into_iter(),
+        }
+    }
+}
+
+/// Adds the entries to the `OsRelease`.
+///
+/// If a key is already set, its value is replaced, keeping the position of the field.
+impl<'a> Extend<OsReleaseEntry<'a>> for OsRelease {
+    fn extend<T: IntoIterator<Item = OsReleaseEntry<'a>>>(&mut self, iter: T) {
+        let fields = Arc::make_mut(&mut self.fields);
+        for entry in iter {
+            let (key, value) = entry.into_parts();
+            fields.insert(intern_key(key), value.into());
+        }
+    }
+}
+
+/// Adds the keys and values to the `OsRelease`.
+///
+/// If a key is already set, its value is replaced, keeping the position of the field.
+impl Extend<(String, String)> for OsRelease {
+    fn extend<T: IntoIterator<Item = (String, String)>>(&mut self, iter: T) {
+        self.extend(
+            iter.into_iter()
+                .map(|(key, value)| OsReleaseEntry::new(key, value)),
+        );
+    }
+}
+
+/// Methods to remove fields.
+impl OsRelease {
+    /// Retains only the fields for which `f` returns `true`, preserving their order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let mut os_release = OsRelease::spec_example();
+    /// os_release.retain(|key, _| !key.starts_with("REDHAT_"));
+    /// assert_eq!(os_release.entries_with_prefix("REDHAT_").count(), 0);
+    /// assert_eq!(os_release.id(), "fedora");
+    /// ```
+    pub fn retain(&mut self, mut f: impl FnMut(&str, &str) -> bool) {
+        Arc::make_mut(&mut self.fields).retain(|key, value| f(key, value));
+    }
+
+    /// Removes the fields for which `f` returns `true`, and returns them in the order of the file.
+    ///
+    /// Unlike `Vec::extract_if()`, the fields are removed even if the returned iterator is not consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let mut os_release = OsRelease::spec_example();
+    /// let removed = os_release.extract_if(|key, _| key.ends_with("_URL")).collect::<Vec<_>>();
+    /// assert_eq!(removed.len(), 5);
+    /// assert_eq!(removed[0].key(), "HOME_URL");
+    /// assert!(os_release.get_value("HOME_URL").is_none());
+    /// ```
+    pub fn extract_if(
+        &mut self,
+        mut f: impl FnMut(&str, &str) -> bool,
+    ) -> impl Iterator<Item = OsReleaseEntry<'static>> {
+        let mut removed = vec![];
+        Arc::make_mut(&mut self.fields).retain(|key, value| {
+            if !f(key, value) {
+                return true;
+            }
+            removed.push(OsReleaseEntry::new(key.clone(), String::from(&**value)));
+            false
+        });
+        removed.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_into_iter() {
+        let os_release = OsRelease::from_str("NAME=Fedora\nID=fedora\n").unwrap();
+        let keys = (&os_release)
+            .into_iter()
+            .map(|e| e.key().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, ["NAME", "ID"]);
+        assert_eq!(os_release.entries().len(), 2);
+
+        let clone = os_release.clone();
+        let entries = os_release.into_iter().collect::<Vec<_>>();
+        assert_eq!(entries[1], OsReleaseEntry::new("ID", "fedora"));
+        assert_eq!(clone.into_iter().count(), 2);
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut os_release = OsRelease::from_str("NAME=Fedora\nID=fedora\n").unwrap();
+        let clone = os_release.clone();
+        os_release.extend([OsReleaseEntry::new("VERSION_ID", "40")]);
+        os_release.extend([("NAME".to_owned(), "Fedora Linux".to_owned())]);
+
+        let entries = os_release
+            .entries()
+            .map(|e| (e.key().to_owned(), e.value().to_owned()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            [
+                ("NAME".to_owned(), "Fedora Linux".to_owned()),
+                ("ID".to_owned(), "fedora".to_owned()),
+                ("VERSION_ID".to_owned(), "40".to_owned()),
+            ]
+        );
+        // clones are not affected
+        assert_eq!(clone.name(), "Fedora");
+        assert_eq!(clone.len(), 2);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut os_release = OsRelease::from_str("A=1\nB=2\nC=3\nD=4\n").unwrap();
+        let clone = os_release.clone();
+        os_release.retain(|key, value| key != "B" && value != "4");
+        let keys = os_release
+            .entries()
+            .map(|e| e.key().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, ["A", "C"]);
+        assert_eq!(clone.len(), 4);
+
+        let removed = os_release
+            .extract_if(|key, _| key == "C")
+            .collect::<Vec<_>>();
+        assert_eq!(removed, [OsReleaseEntry::new("C", "3")]);
+        assert_eq!(os_release.len(), 1);
+        assert_eq!(os_release.extract_if(|_, _| false).count(), 0);
+    }
+}
@@ -0,0 +1,334 @@
+//! Keys of the fields defined by [`os-release(5)`].
+//!
+//! These constants can be passed to [`OsRelease::get_value()`] and similar methods
+//! to avoid typos in string literals.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use etc_os_release::{keys, OsRelease};
+//!
+//! let os_release = OsRelease::spec_example();
+//! assert_eq!(os_release.get_value(keys::VERSION_ID), Some("32"));
+//! assert!(keys::ALL.contains(&keys::SUPPORT_END));
+//! ```
+//!
+//! [`OsRelease::get_value()`]: crate::OsRelease::get_value
+//! [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+
+/// The string identifying the operating system, without a version component.
+///
+/// For more information, see the [`NAME=`] section of [`os-release(5)`].
+///
+/// [`NAME=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#NAME=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const NAME: &str = "NAME";
+
+/// The lower-case string identifying the operating system, excluding any version information.
+///
+/// For more information, see the [`ID=`] section of [`os-release(5)`].
+///
+/// [`ID=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#ID=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const ID: &str = "ID";
+
+/// The space-separated list of operating system identifiers closely related to the local operating system.
+///
+/// For more information, see the [`ID_LIKE=`] section of [`os-release(5)`].
+///
+/// [`ID_LIKE=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#ID_LIKE=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const ID_LIKE: &str = "ID_LIKE";
+
+/// The pretty operating system name in a format suitable for presentation to the user.
+///
+/// For more information, see the [`PRETTY_NAME=`] section of [`os-release(5)`].
+///
+/// [`PRETTY_NAME=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#PRETTY_NAME=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const PRETTY_NAME: &str = "PRETTY_NAME";
+
+/// The CPE name for the operating system.
+///
+/// For more information, see the [`CPE_NAME=`] section of [`os-release(5)`].
+///
+/// [`CPE_NAME=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#CPE_NAME=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const CPE_NAME: &str = "CPE_NAME";
+
+/// The string identifying a specific variant or edition of the operating system.
+///
+/// For more information, see the [`VARIANT=`] section of [`os-release(5)`].
+///
+/// [`VARIANT=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#VARIANT=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const VARIANT: &str = "VARIANT";
+
+/// The lower-case string identifying a specific variant or edition of the operating system.
+///
+/// For more information, see the [`VARIANT_ID=`] section of [`os-release(5)`].
+///
+/// [`VARIANT_ID=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#VARIANT_ID=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const VARIANT_ID: &str = "VARIANT_ID";
+
+/// The string identifying the operating system version, excluding any OS name information.
+///
+/// For more information, see the [`VERSION=`] section of [`os-release(5)`].
+///
+/// [`VERSION=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#VERSION=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const VERSION: &str = "VERSION";
+
+/// The lower-case string identifying the operating system version, excluding any OS name information.
+///
+/// For more information, see the [`VERSION_ID=`] section of [`os-release(5)`].
+///
+/// [`VERSION_ID=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#VERSION_ID=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const VERSION_ID: &str = "VERSION_ID";
+
+/// The lower-case string identifying the operating system release code name.
+///
+/// For more information, see the [`VERSION_CODENAME=`] section of [`os-release(5)`].
+///
+/// [`VERSION_CODENAME=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#VERSION_CODENAME=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const VERSION_CODENAME: &str = "VERSION_CODENAME";
+
+/// The string uniquely identifying the system image originally used as the installation base.
+///
+/// For more information, see the [`BUILD_ID=`] section of [`os-release(5)`].
+///
+/// [`BUILD_ID=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#BUILD_ID=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const BUILD_ID: &str = "BUILD_ID";
+
+/// The lower-case string identifying a specific image of the operating system.
+///
+/// For more information, see the [`IMAGE_ID=`] section of [`os-release(5)`].
+///
+/// [`IMAGE_ID=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#IMAGE_ID=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const IMAGE_ID: &str = "IMAGE_ID";
+
+/// The lower-case string identifying the OS image version.
+///
+/// For more information, see the [`IMAGE_VERSION=`] section of [`os-release(5)`].
+///
+/// [`IMAGE_VERSION=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#IMAGE_VERSION=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const IMAGE_VERSION: &str = "IMAGE_VERSION";
+
+/// The release type of the operating system.
+///
+/// For more information, see the [`RELEASE_TYPE=`] section of [`os-release(5)`].
+///
+/// [`RELEASE_TYPE=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#RELEASE_TYPE=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const RELEASE_TYPE: &str = "RELEASE_TYPE";
+
+/// A human-presentable description of what makes this build of the OS experimental.
+///
+/// For more information, see the [`EXPERIMENT=`] section of [`os-release(5)`].
+///
+/// [`EXPERIMENT=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#EXPERIMENT=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const EXPERIMENT: &str = "EXPERIMENT";
+
+/// The page with more information about the experiment of this build of the OS.
+///
+/// For more information, see the [`EXPERIMENT_URL=`] section of [`os-release(5)`].
+///
+/// [`EXPERIMENT_URL=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#EXPERIMENT_URL=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const EXPERIMENT_URL: &str = "EXPERIMENT_URL";
+
+/// The homepage of the operating system.
+///
+/// For more information, see the [`HOME_URL=`] section of [`os-release(5)`].
+///
+/// [`HOME_URL=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#HOME_URL=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const HOME_URL: &str = "HOME_URL";
+
+/// The main documentation page of the operating system.
+///
+/// For more information, see the [`DOCUMENTATION_URL=`] section of [`os-release(5)`].
+///
+/// [`DOCUMENTATION_URL=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#DOCUMENTATION_URL=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const DOCUMENTATION_URL: &str = "DOCUMENTATION_URL";
+
+/// The main support page for the operating system.
+///
+/// For more information, see the [`SUPPORT_URL=`] section of [`os-release(5)`].
+///
+/// [`SUPPORT_URL=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#SUPPORT_URL=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const SUPPORT_URL: &str = "SUPPORT_URL";
+
+/// The main bug reporting page for the operating system.
+///
+/// For more information, see the [`BUG_REPORT_URL=`] section of [`os-release(5)`].
+///
+/// [`BUG_REPORT_URL=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#BUG_REPORT_URL=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const BUG_REPORT_URL: &str = "BUG_REPORT_URL";
+
+/// The main privacy policy page for the operating system.
+///
+/// For more information, see the [`PRIVACY_POLICY_URL=`] section of [`os-release(5)`].
+///
+/// [`PRIVACY_POLICY_URL=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#PRIVACY_POLICY_URL=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const PRIVACY_POLICY_URL: &str = "PRIVACY_POLICY_URL";
+
+/// The date at which support for this version of the OS ends.
+///
+/// For more information, see the [`SUPPORT_END=`] section of [`os-release(5)`].
+///
+/// [`SUPPORT_END=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#SUPPORT_END=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const SUPPORT_END: &str = "SUPPORT_END";
+
+/// The name of an icon of the operating system.
+///
+/// For more information, see the [`LOGO=`] section of [`os-release(5)`].
+///
+/// [`LOGO=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#LOGO=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const LOGO: &str = "LOGO";
+
+/// The suggested presentation color when showing the OS name on the console.
+///
+/// For more information, see the [`ANSI_COLOR=`] section of [`os-release(5)`].
+///
+/// [`ANSI_COLOR=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#ANSI_COLOR=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const ANSI_COLOR: &str = "ANSI_COLOR";
+
+/// The name of the OS vendor.
+///
+/// For more information, see the [`VENDOR_NAME=`] section of [`os-release(5)`].
+///
+/// [`VENDOR_NAME=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#VENDOR_NAME=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const VENDOR_NAME: &str = "VENDOR_NAME";
+
+/// The homepage of the OS vendor.
+///
+/// For more information, see the [`VENDOR_URL=`] section of [`os-release(5)`].
+///
+/// [`VENDOR_URL=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#VENDOR_URL=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const VENDOR_URL: &str = "VENDOR_URL";
+
+/// The default hostname if the hostname is not configured.
+///
+/// For more information, see the [`DEFAULT_HOSTNAME=`] section of [`os-release(5)`].
+///
+/// [`DEFAULT_HOSTNAME=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#DEFAULT_HOSTNAME=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const DEFAULT_HOSTNAME: &str = "DEFAULT_HOSTNAME";
+
+/// The CPU architecture the OS is built for.
+///
+/// For more information, see the [`ARCHITECTURE=`] section of [`os-release(5)`].
+///
+/// [`ARCHITECTURE=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#ARCHITECTURE=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const ARCHITECTURE: &str = "ARCHITECTURE";
+
+/// The version compatibility level of system extension images.
+///
+/// For more information, see the [`SYSEXT_LEVEL=`] section of [`os-release(5)`].
+///
+/// [`SYSEXT_LEVEL=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#SYSEXT_LEVEL=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const SYSEXT_LEVEL: &str = "SYSEXT_LEVEL";
+
+/// The version compatibility level of configuration extension images.
+///
+/// For more information, see the [`CONFEXT_LEVEL=`] section of [`os-release(5)`].
+///
+/// [`CONFEXT_LEVEL=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#CONFEXT_LEVEL=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const CONFEXT_LEVEL: &str = "CONFEXT_LEVEL";
+
+/// The environments in which system extension images are applicable.
+///
+/// For more information, see the [`SYSEXT_SCOPE=`] section of [`os-release(5)`].
+///
+/// [`SYSEXT_SCOPE=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#SYSEXT_SCOPE=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const SYSEXT_SCOPE: &str = "SYSEXT_SCOPE";
+
+/// The environments in which configuration extension images are applicable.
+///
+/// For more information, see the [`CONFEXT_SCOPE=`] section of [`os-release(5)`].
+///
+/// [`CONFEXT_SCOPE=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#CONFEXT_SCOPE=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const CONFEXT_SCOPE: &str = "CONFEXT_SCOPE";
+
+/// The prefixes of portable service images.
+///
+/// For more information, see the [`PORTABLE_PREFIXES=`] section of [`os-release(5)`].
+///
+/// [`PORTABLE_PREFIXES=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#PORTABLE_PREFIXES=
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+pub const PORTABLE_PREFIXES: &str = "PORTABLE_PREFIXES";
+
+/// All keys defined by os-release(5), in the order of the man page.
+pub const ALL: &[&str] = &[
+    NAME,
+    ID,
+    ID_LIKE,
+    PRETTY_NAME,
+    CPE_NAME,
+    VARIANT,
+    VARIANT_ID,
+    VERSION,
+    VERSION_ID,
+    VERSION_CODENAME,
+    BUILD_ID,
+    IMAGE_ID,
+    IMAGE_VERSION,
+    RELEASE_TYPE,
+    EXPERIMENT,
+    EXPERIMENT_URL,
+    HOME_URL,
+    DOCUMENTATION_URL,
+    SUPPORT_URL,
+    BUG_REPORT_URL,
+    PRIVACY_POLICY_URL,
+    SUPPORT_END,
+    LOGO,
+    ANSI_COLOR,
+    VENDOR_NAME,
+    VENDOR_URL,
+    DEFAULT_HOSTNAME,
+    ARCHITECTURE,
+    SYSEXT_LEVEL,
+    CONFEXT_LEVEL,
+    SYSEXT_SCOPE,
+    CONFEXT_SCOPE,
+    PORTABLE_PREFIXES,
+];
+
+/// Returns `true` if the key is defined by os-release(5).
+///
+/// See [`FieldInfo`](crate::FieldInfo) for the metadata of the fields.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::keys;
+///
+/// assert!(keys::is_known_key("VERSION_ID"));
+/// assert!(!keys::is_known_key("UBUNTU_CODENAME"));
+/// ```
+pub fn is_known_key(key: &str) -> bool {
+    ALL.contains(&key)
+}
@@ -70,16 +70,117 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![warn(missing_docs, unreachable_pub)]
 
-use indexmap::IndexMap;
+use std::{borrow::Cow, sync::Arc};
 
+use crate::map::Map;
+
+#[cfg(feature = "include")]
+pub use crate::macros::include_os_release;
+#[cfg(feature = "uname")]
+pub use crate::system_info::SystemInfo;
+#[cfg(feature = "deb")]
+pub use crate::version::compare_deb_versions;
+#[cfg(feature = "rpm")]
+pub use crate::version::compare_rpm_versions;
+#[cfg(all(target_os = "linux", feature = "watch"))]
+pub use crate::watch::OsReleaseWatcher;
 pub use crate::{
-    construct::Error,
-    entry::{OsReleaseEntry, OsReleaseLine},
+    architecture::Architecture,
+    cache::OsReleaseCache,
+    completeness::Completeness,
+    construct::{Error, ParseOptions},
+    debian::DebianRelease,
+    distro::{KnownDistro, OsFamily, PackageManager},
+    document::OsReleaseDocument,
+    entry::{
+        find_malformed_line, parse_lines, split_line, LineError, OsReleaseEntry, OsReleaseLine,
+    },
+    example::SPEC_EXAMPLE,
+    field_error::{FieldError, ValueError},
+    format::{Redacted, RoundtripDivergence},
+    from_os_release::{FromOsRelease, FromOsReleaseError, FromOsReleaseField},
+    host::OsComparison,
+    hostname::HostnameProperties,
+    identity::{NoOsIdentity, OsIdentity},
+    iter::{Entries, IntoEntries},
+    matcher::{MatcherError, MatcherErrorKind, OsMatcher},
+    metrics::ParseMetrics,
+    multimap::OsReleaseMultiMap,
+    obsolete::ObsoleteField,
+    parser::OsReleaseParser,
+    product::ProductMetadata,
+    raw::RawOsRelease,
+    registry::{FieldCategory, FieldInfo, ValueType},
+    release_type::ReleaseType,
+    rhel::RhelVersion,
+    scope::Scope,
+    syntax::LineErrorKind,
+    version::{compare_versions, RelaxedVersion},
 };
 
+mod architecture;
+mod cache;
+mod completeness;
 mod construct;
+mod convert;
+mod current;
+#[cfg(all(unix, feature = "dbus"))]
+mod dbus;
+mod debian;
+mod distro;
+mod document;
 mod entry;
+mod example;
+#[cfg(feature = "ffi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ffi")))]
+pub mod ffi;
+mod field_error;
 mod fields;
+#[cfg(feature = "fixtures")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fixtures")))]
+pub mod fixtures;
+mod format;
+mod from_os_release;
+mod host;
+mod hostname;
+mod identity;
+mod iter;
+pub mod keys;
+#[cfg(feature = "logo")]
+mod logo;
+mod macros;
+mod map;
+mod matcher;
+mod metrics;
+#[cfg(all(unix, feature = "mmap"))]
+mod mmap;
+mod multimap;
+mod obsolete;
+mod parser;
+mod platform;
+mod product;
+mod raw;
+mod registry;
+mod release_type;
+mod rhel;
+mod scope;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod summary;
+#[cfg(feature = "date")]
+mod support;
+mod syntax;
+#[cfg(feature = "uname")]
+mod system_info;
+#[cfg(feature = "tar")]
+mod tar;
+#[cfg(all(unix, any(feature = "uname", feature = "bsd")))]
+mod uname;
+#[cfg(all(unix, feature = "varlink"))]
+mod varlink;
+mod version;
+#[cfg(all(target_os = "linux", feature = "watch"))]
+mod watch;
 
 /// The parsed contents of the os-release file.
 ///
@@ -99,6 +200,11 @@ mod fields;
 /// In this case, [`Self::version()`] and [`Self::version_id()`] may be `None`.
 /// Application should not rely on these fields to be set.
 ///
+/// # Sharing
+///
+/// The fields are reference-counted, so cloning an `OsRelease` is cheap and does not copy the strings.
+/// `OsRelease` is `Send` and `Sync`, so the clones can be handed to other threads and tasks.
+///
 /// # Examples
 ///
 /// Open the os-release file and print the OS name and version:
@@ -147,6 +253,9 @@ mod fields;
 /// ```
 #[derive(Debug, Clone)]
 pub struct OsRelease {
-    // Use `IndexMap` for reserving insertion order.
-    fields: IndexMap<String, String>,
+    // Use `Map` for reserving insertion order.
+    // Keys defined by os-release(5) are interned, so that they are not allocated for each file.
+    // Values are never modified, so `Box<str>` is used to save the capacity of `String`.
+    // The map is shared between clones, and copied on write.
+    fields: Arc<Map<Cow<'static, str>, Box<str>>>,
 }
@@ -0,0 +1,150 @@
+use std::{
+    env,
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::OsRelease;
+
+/// Methods to resolve the logo of the operating system.
+impl OsRelease {
+    /// Returns the path to the image file of the icon named by [`Self::logo()`].
+    ///
+    /// This is a best-effort lookup in the `hicolor` fallback theme only, falling back to `/usr/share/pixmaps`.
+    /// The icon base directories of the [freedesktop.org Icon Theme Specification][spec] are searched,
+    /// but the user's icon theme is ignored, and `index.theme` is not read,
+    /// so `Inherits=` and the `Directories=` list are not honored: every `<size>/<context>/` directory is searched.
+    /// When the icon is available in several sizes, scalable images are preferred over the largest fixed-size image.
+    /// Use an icon theme library such as [freedesktop-icons] for a lookup that follows the specification.
+    ///
+    /// Returns `None` if `LOGO=` is not set or no image file is found.
+    ///
+    /// [spec]: https://standards.freedesktop.org/icon-theme-spec/latest
+    /// [freedesktop-icons]: https://crates.io/crates/freedesktop-icons
+    #[cfg_attr(docsrs, doc(cfg(feature = "logo")))]
+    pub fn resolve_logo(&self) -> Option<PathBuf> {
+        resolve_icon(self.logo()?, &base_dirs(), Path::new("/usr/share/pixmaps"))
+    }
+}
+
+/// File extensions of icon images, in order of preference.
+const EXTENSIONS: &[&str] = &["svg", "png", "xpm"];
+
+/// Look up an icon by name in the `hicolor` theme of the icon base directories, falling back to `pixmaps`.
+fn resolve_icon(name: &str, base_dirs: &[PathBuf], pixmaps: &Path) -> Option<PathBuf> {
+    // Icon names are not paths.
+    if name.is_empty() || name.contains('/') {
+        return None;
+    }
+
+    base_dirs
+        .iter()
+        .find_map(|base| resolve_in_theme(&base.join("hicolor"), name))
+        .or_else(|| find_image(pixmaps, name))
+}
+
+/// Returns the icon base directories, in order of precedence.
+fn base_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![];
+    if let Some(home) = env::var_os("HOME") {
+        dirs.push(Path::new(&home).join(".icons"));
+    }
+    if let Some(data_home) = env::var_os("XDG_DATA_HOME").filter(|s| !s.is_empty()) {
+        dirs.push(Path::new(&data_home).join("icons"));
+    } else if let Some(home) = env::var_os("HOME") {
+        dirs.push(Path::new(&home).join(".local/share/icons"));
+    }
+    let data_dirs = env::var_os("XDG_DATA_DIRS")
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| OsString::from("/usr/local/share:/usr/share"));
+    dirs.extend(env::split_paths(&data_dirs).map(|dir| dir.join("icons")));
+    dirs
+}
+
+/// Look up an icon in the `<theme>/<size>/<context>/` directories of a theme.
+fn resolve_in_theme(theme: &Path, name: &str) -> Option<PathBuf> {
+    let mut best: Option<(u32, PathBuf)> = None;
+    for size_dir in fs::read_dir(theme).ok()?.flatten() {
+        let size = match size_dir.file_name().to_str() {
+            Some("scalable") => u32::MAX,
+            Some(size) => match size.split_once('x').and_then(|(w, _)| w.parse().ok()) {
+                Some(size) => size,
+                None => continue,
+            },
+            None => continue,
+        };
+        if best.as_ref().is_some_and(|(best, _)| *best >= size) {
+            continue;
+        }
+        let Ok(contexts) = fs::read_dir(size_dir.path()) else {
+            continue;
+        };
+        if let Some(path) = contexts
+            .flatten()
+            .find_map(|context| find_image(&context.path(), name))
+        {
+            best = Some((size, path));
+        }
+    }
+    best.map(|(_, path)| path)
+}
+
+/// Find an image file named `name` with one of the known extensions in `dir`.
+fn find_image(dir: &Path, name: &str) -> Option<PathBuf> {
+    EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!("{name}.{ext}")))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_resolve_icon() {
+        let root = env::temp_dir().join(format!("etc-os-release-logo-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let touch = |path: &str| {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        };
+        touch("user/hicolor/48x48/apps/sized.png");
+        touch("user/hicolor/256x256/apps/sized.png");
+        touch("user/hicolor/64x64/apps/sized.png");
+        touch("user/hicolor/index.theme");
+        touch("system/hicolor/512x512/apps/scalable.png");
+        touch("system/hicolor/scalable/apps/scalable.svg");
+        touch("system/hicolor/32x32/apps/ext.xpm");
+        touch("system/hicolor/32x32/apps/ext.png");
+        touch("system/hicolor/48x48/apps/sized.png");
+        touch("pixmaps/fallback.png");
+
+        let base_dirs = [root.join("user"), root.join("system")];
+        let pixmaps = root.join("pixmaps");
+        let resolve = |name| {
+            resolve_icon(name, &base_dirs, &pixmaps)
+                .map(|path| path.strip_prefix(&root).unwrap().to_owned())
+        };
+        assert_eq!(
+            resolve("sized"),
+            Some("user/hicolor/256x256/apps/sized.png".into())
+        );
+        assert_eq!(
+            resolve("scalable"),
+            Some("system/hicolor/scalable/apps/scalable.svg".into())
+        );
+        assert_eq!(
+            resolve("ext"),
+            Some("system/hicolor/32x32/apps/ext.png".into())
+        );
+        assert_eq!(resolve("fallback"), Some("pixmaps/fallback.png".into()));
+        assert_eq!(resolve("missing"), None);
+        assert_eq!(resolve("apps/sized"), None);
+        assert_eq!(resolve(""), None);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,66 @@
+/// Declares lazily-parsed `OsRelease` statics from embedded strings.
+///
+/// Each static is a [`LazyLock<OsRelease>`](std::sync::LazyLock), which is parsed on first access
+/// and dereferences to `&'static OsRelease`.
+/// This is useful in test suites and benchmarks that share os-release samples.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::{static_os_release, OsRelease};
+///
+/// static_os_release! {
+///     /// Fedora Linux 41.
+///     pub FEDORA_41 = "NAME=\"Fedora Linux\"\nID=fedora\nVERSION_ID=41\n";
+///     ALPINE_3_20 = "NAME=\"Alpine Linux\"\nID=alpine\nVERSION_ID=3.20.0\n";
+/// }
+///
+/// let os_release: &'static OsRelease = &FEDORA_41;
+/// assert_eq!(os_release.id(), "fedora");
+/// assert_eq!(os_release.version_id(), Some("41"));
+/// ```
+#[macro_export]
+macro_rules! static_os_release {
+    ($($(#[$attr:meta])* $vis:vis $name:ident = $input:expr;)*) => {
+        $(
+            $(#[$attr])*
+            $vis static $name: ::std::sync::LazyLock<$crate::OsRelease> =
+                ::std::sync::LazyLock::new(|| {
+                    <$crate::OsRelease as ::std::str::FromStr>::from_str($input)
+                        .unwrap_or_else(|e| match e {})
+                });
+        )*
+    };
+}
+
+/// Parses an os-release file at compile time and expands to an [`OsRelease`](crate::OsRelease) of its fields.
+///
+/// The path is relative to the directory of the `Cargo.toml` of the crate using the macro, that is, `CARGO_MANIFEST_DIR`.
+/// The file is parsed strictly, as [`OsReleaseLine::parse_strict()`](crate::OsReleaseLine::parse_strict) does,
+/// and compilation fails if the file cannot be read or has a malformed line.
+/// The crate is rebuilt when the file changes.
+///
+/// Only the parsing is done at compile time: the expression builds a new `OsRelease` from the embedded fields
+/// each time it is evaluated, since `OsRelease` cannot be constructed in a `const` context.
+/// Store it in a [`LazyLock`](std::sync::LazyLock) to build it only once.
+///
+/// This is useful for appliance builds where the identity of the target operating system is fixed.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::{include_os_release, OsRelease};
+///
+/// let os_release: OsRelease = include_os_release!("tests/data/os-release");
+/// assert_eq!(os_release.id(), "fedora");
+/// assert_eq!(os_release, OsRelease::spec_example());
+/// ```
+///
+/// Malformed files are rejected at compile time:
+///
+/// ```rust,compile_fail
+/// let os_release = etc_os_release::include_os_release!("Cargo.toml");
+/// ```
+#[cfg(feature = "include")]
+#[cfg_attr(docsrs, doc(cfg(feature = "include")))]
+pub use etc_os_release_derive::include_os_release;
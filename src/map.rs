@@ -0,0 +1,156 @@
+/// The map of fields, preserving insertion order.
+///
+/// With the `indexmap` feature (enabled by default), this is `IndexMap`.
+/// Otherwise, it is a `Vec` of key-value pairs searched linearly,
+/// which is fast enough as os-release files have only a few dozen fields.
+#[cfg(feature = "indexmap")]
+pub(crate) type Map<K, V> = indexmap::IndexMap<K, V>;
+
+/// The map of fields, preserving insertion order.
+#[cfg(not(feature = "indexmap"))]
+pub(crate) type Map<K, V> = VecMap<K, V>;
+
+#[cfg(feature = "indexmap")]
+pub(crate) type Iter<'a, K, V> = indexmap::map::Iter<'a, K, V>;
+#[cfg(feature = "indexmap")]
+pub(crate) type IntoIter<K, V> = indexmap::map::IntoIter<K, V>;
+
+#[cfg(not(feature = "indexmap"))]
+pub(crate) type Iter<'a, K, V> = vec_map::Iter<'a, K, V>;
+#[cfg(not(feature = "indexmap"))]
+pub(crate) type IntoIter<K, V> = std::vec::IntoIter<(K, V)>;
+
+/// A map backed by a `Vec`, with the subset of the API of `IndexMap` used by this crate.
+#[cfg(any(test, not(feature = "indexmap")))]
+#[derive(Debug, Clone)]
+pub(crate) struct VecMap<K, V> {
+    entries: Vec<(K, V)>,
+}
+
+#[cfg(any(test, not(feature = "indexmap")))]
+#[allow(dead_code)]
+mod vec_map {
+    use std::{borrow::Borrow, slice};
+
+    use super::VecMap;
+
+    /// An iterator over the entries of a `VecMap`.
+    #[derive(Debug, Clone)]
+    pub(crate) struct Iter<'a, K, V>(slice::Iter<'a, (K, V)>);
+
+    impl<'a, K, V> Iterator for Iter<'a, K, V> {
+        type Item = (&'a K, &'a V);
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next().map(|(k, v)| (k, v))
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.0.size_hint()
+        }
+    }
+
+    impl<K: Borrow<str>, V> VecMap<K, V> {
+        fn position(&self, key: &str) -> Option<usize> {
+            self.entries.iter().position(|(k, _)| k.borrow() == key)
+        }
+
+        pub(crate) fn len(&self) -> usize {
+            self.entries.len()
+        }
+
+        pub(crate) fn is_empty(&self) -> bool {
+            self.entries.is_empty()
+        }
+
+        pub(crate) fn contains_key(&self, key: &str) -> bool {
+            self.position(key).is_some()
+        }
+
+        pub(crate) fn get(&self, key: &str) -> Option<&V> {
+            self.position(key).map(|i| &self.entries[i].1)
+        }
+
+        pub(crate) fn iter(&self) -> Iter<'_, K, V> {
+            Iter(self.entries.iter())
+        }
+
+        pub(crate) fn keys(&self) -> impl Iterator<Item = &K> {
+            self.entries.iter().map(|(k, _)| k)
+        }
+
+        pub(crate) fn retain(&mut self, mut f: impl FnMut(&K, &mut V) -> bool) {
+            self.entries.retain_mut(|(k, v)| f(k, v));
+        }
+
+        /// Inserts a value, keeping the position of the key if it is already present.
+        pub(crate) fn insert(&mut self, key: K, value: V) -> Option<V> {
+            match self.position(key.borrow()) {
+                Some(i) => Some(std::mem::replace(&mut self.entries[i].1, value)),
+                None => {
+                    self.entries.push((key, value));
+                    None
+                }
+            }
+        }
+    }
+
+    impl<K, V> IntoIterator for VecMap<K, V> {
+        type Item = (K, V);
+        type IntoIter = std::vec::IntoIter<(K, V)>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.entries.into_iter()
+        }
+    }
+
+    impl<K, V> Default for VecMap<K, V> {
+        fn default() -> Self {
+            Self { entries: vec![] }
+        }
+    }
+
+    impl<K: Borrow<str>, V> FromIterator<(K, V)> for VecMap<K, V> {
+        fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+            let mut map = Self::default();
+            for (key, value) in iter {
+                map.insert(key, value);
+            }
+            map
+        }
+    }
+
+    /// Compares the maps regardless of the order, as `IndexMap` does.
+    impl<K: Borrow<str>, V: PartialEq> PartialEq for VecMap<K, V> {
+        fn eq(&self, other: &Self) -> bool {
+            self.len() == other.len()
+                && self
+                    .iter()
+                    .all(|(k, v)| other.get(k.borrow()).is_some_and(|w| v == w))
+        }
+    }
+
+    impl<K: Borrow<str>, V: Eq> Eq for VecMap<K, V> {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_vec_map() {
+        let map = [("A", 1), ("B", 2), ("A", 3)]
+            .into_iter()
+            .collect::<VecMap<_, _>>();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get("A"), Some(&3));
+        assert!(map.contains_key("B"));
+        assert!(!map.contains_key("C"));
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), ["A", "B"]);
+
+        let reordered = [("B", 2), ("A", 3)].into_iter().collect::<VecMap<_, _>>();
+        assert_eq!(map, reordered);
+        let different = [("A", 3)].into_iter().collect::<VecMap<_, _>>();
+        assert_ne!(map, different);
+    }
+}
@@ -0,0 +1,428 @@
+use std::{cmp::Ordering, str::FromStr};
+
+use crate::{compare_versions, keys, OsRelease};
+
+/// A declarative requirement on the operating system, evaluated against an [`OsRelease`].
+///
+/// A matcher is parsed from an expression combining predicates with `&&`, `||`, `!` and parentheses.
+/// `&&` binds tighter than `||`. The following predicates are supported:
+///
+/// | Predicate             | Matches when                                                              |
+/// |-----------------------|---------------------------------------------------------------------------|
+/// | `fedora`              | `ID=` is `fedora`                                                         |
+/// | `fedora>=38`          | `ID=` is `fedora` and `VERSION_ID=` is `38` or newer                      |
+/// | `id_like:debian`      | [`OsRelease::is_like()`] returns `true` for `debian`                      |
+/// | `variant_id:server`   | `VARIANT_ID=` is `server` (any field can be used, named in lowercase)     |
+/// | `variant_id!=server`  | `VARIANT_ID=` is not `server` (only known fields, with `==` and `!=`)     |
+/// | `version_id>=12`      | `VERSION_ID=` is `12` or newer                                            |
+///
+/// Versions are compared using [`compare_versions()`], with the operators `==`, `!=`, `<`, `<=`, `>` and `>=`.
+/// The fields that can be compared as versions without an `ID=` are `version_id`, `image_version`, `sysext_level`
+/// and `confext_level`.
+/// A name before an operator is taken as a field if it is a [known key](keys::is_known_key) in lowercase,
+/// and as an `ID=` otherwise. Ordering operators on other known fields are rejected.
+/// Parentheses and `!` can be nested at most 64 levels deep.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::str::FromStr;
+///
+/// use etc_os_release::{OsMatcher, OsRelease};
+///
+/// let matcher: OsMatcher = "fedora>=38 || (id_like:debian && version_id>=12)".parse()?;
+///
+/// let os_release = OsRelease::from_str("ID=fedora\nVERSION_ID=40\n").unwrap();
+/// assert!(matcher.matches(&os_release));
+///
+/// let os_release = OsRelease::from_str("ID=ubuntu\nID_LIKE=debian\nVERSION_ID=22.04\n").unwrap();
+/// assert!(matcher.matches(&os_release));
+///
+/// let os_release = OsRelease::from_str("ID=debian\nVERSION_ID=11\n").unwrap();
+/// assert!(!matcher.matches(&os_release));
+/// # Ok::<(), etc_os_release::MatcherError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OsMatcher {
+    expr: Expr,
+}
+
+impl OsMatcher {
+    /// Parses a matcher expression.
+    pub fn parse(s: &str) -> Result<Self, MatcherError> {
+        let mut parser = Parser {
+            input: s,
+            pos: 0,
+            depth: 0,
+        };
+        let expr = parser.parse_or()?;
+        parser.skip_whitespace();
+        match parser.peek() {
+            Some(c) => Err(parser.error(MatcherErrorKind::UnexpectedCharacter(c))),
+            None => Ok(Self { expr }),
+        }
+    }
+
+    /// Returns `true` if the operating system satisfies the requirement.
+    pub fn matches(&self, os_release: &OsRelease) -> bool {
+        self.expr.eval(os_release)
+    }
+}
+
+impl FromStr for OsMatcher {
+    type Err = MatcherError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// An error found while parsing an [`OsMatcher`] expression.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, thiserror::Error)]
+#[error("offset {offset}: {kind}")]
+pub struct MatcherError {
+    offset: usize,
+    #[source]
+    kind: MatcherErrorKind,
+}
+
+impl MatcherError {
+    /// Returns the byte offset in the expression where the error was found.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the kind of the error.
+    pub fn kind(&self) -> &MatcherErrorKind {
+        &self.kind
+    }
+}
+
+/// The kind of error found in an [`OsMatcher`] expression.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, thiserror::Error)]
+#[non_exhaustive]
+pub enum MatcherErrorKind {
+    /// The expression ends where a predicate or `)` is expected.
+    #[error("unexpected end of expression")]
+    UnexpectedEnd,
+    /// A character that cannot appear at this position.
+    #[error("unexpected character {0:?}")]
+    UnexpectedCharacter(char),
+    /// A `:` or comparison operator is not followed by a value.
+    #[error("missing value")]
+    MissingValue,
+    /// An ordering operator is applied to a field which cannot be compared as a version.
+    #[error("field {0:?} cannot be compared as a version")]
+    NotAVersionField(String),
+    /// Parentheses and `!` are nested too deeply.
+    #[error("expression nested too deeply")]
+    TooDeep,
+}
+
+/// The maximum nesting depth of parentheses and `!`.
+const MAX_DEPTH: usize = 64;
+
+/// Fields that can be compared as versions without an `ID=`.
+const VERSION_KEYS: &[&str] = &[
+    "version_id",
+    "image_version",
+    "sysext_level",
+    "confext_level",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Or(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Id(String, Option<(Op, String)>),
+    Like(String),
+    Field(String, String),
+    Version(String, Op, String),
+}
+
+impl Expr {
+    fn eval(&self, os_release: &OsRelease) -> bool {
+        match self {
+            Self::Or(a, b) => a.eval(os_release) || b.eval(os_release),
+            Self::And(a, b) => a.eval(os_release) && b.eval(os_release),
+            Self::Not(a) => !a.eval(os_release),
+            Self::Id(id, version) => {
+                os_release.id() == id
+                    && match version {
+                        Some((op, version)) => os_release
+                            .compare_version_id(version)
+                            .is_some_and(|ord| op.matches(ord)),
+                        None => true,
+                    }
+            }
+            Self::Like(id) => os_release.is_like(id),
+            Self::Field(key, value) => field(os_release, key) == Some(value),
+            Self::Version(key, op, version) => field(os_release, key)
+                .is_some_and(|value| op.matches(compare_versions(value, version))),
+        }
+    }
+}
+
+/// Returns the value of the field named by a lowercase key.
+fn field<'a>(os_release: &'a OsRelease, key: &str) -> Option<&'a str> {
+    match key {
+        "id" => Some(os_release.id()),
+        _ => os_release.get_value(&key.to_ascii_uppercase()),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Op {
+    fn matches(self, ord: Ordering) -> bool {
+        match self {
+            Self::Eq => ord.is_eq(),
+            Self::Ne => ord.is_ne(),
+            Self::Lt => ord.is_lt(),
+            Self::Le => ord.is_le(),
+            Self::Gt => ord.is_gt(),
+            Self::Ge => ord.is_ge(),
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn error(&self, kind: MatcherErrorKind) -> MatcherError {
+        MatcherError {
+            offset: self.pos,
+            kind,
+        }
+    }
+
+    fn unexpected(&self) -> MatcherError {
+        match self.peek() {
+            Some(c) => self.error(MatcherErrorKind::UnexpectedCharacter(c)),
+            None => self.error(MatcherErrorKind::UnexpectedEnd),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &str {
+        let start = self.pos;
+        let len = self.rest().find(|c| !f(c)).unwrap_or(self.rest().len());
+        self.pos += len;
+        &self.input[start..self.pos]
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, MatcherError> {
+        let mut expr = self.parse_and()?;
+        while self.eat("||") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, MatcherError> {
+        let mut expr = self.parse_unary()?;
+        while self.eat("&&") {
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, MatcherError> {
+        self.skip_whitespace();
+        let nested = self.rest().starts_with(['!', '(']);
+        if nested {
+            if self.depth == MAX_DEPTH {
+                return Err(self.error(MatcherErrorKind::TooDeep));
+            }
+            self.depth += 1;
+        }
+        let expr = self.parse_nested();
+        if nested {
+            self.depth -= 1;
+        }
+        expr
+    }
+
+    fn parse_nested(&mut self) -> Result<Expr, MatcherError> {
+        if self.eat("!") {
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.eat("(") {
+            let expr = self.parse_or()?;
+            if !self.eat(")") {
+                return Err(self.unexpected());
+            }
+            return Ok(expr);
+        }
+        self.parse_predicate()
+    }
+
+    fn parse_predicate(&mut self) -> Result<Expr, MatcherError> {
+        self.skip_whitespace();
+        let name = self
+            .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+            .to_owned();
+        if name.is_empty() {
+            return Err(self.unexpected());
+        }
+
+        if self.eat(":") {
+            let value = self.parse_value()?;
+            return Ok(match name.as_str() {
+                "id_like" => Expr::Like(value),
+                _ => Expr::Field(name, value),
+            });
+        }
+
+        self.skip_whitespace();
+        let op_pos = self.pos;
+        let Some(op) = self.parse_op() else {
+            return Ok(Expr::Id(name, None));
+        };
+        let value = self.parse_value()?;
+        if VERSION_KEYS.contains(&name.as_str()) {
+            return Ok(Expr::Version(name, op, value));
+        }
+        if !keys::is_known_key(&name.to_ascii_uppercase()) {
+            return Ok(Expr::Id(name, Some((op, value))));
+        }
+        match op {
+            Op::Eq => Ok(Expr::Field(name, value)),
+            Op::Ne => Ok(Expr::Not(Box::new(Expr::Field(name, value)))),
+            _ => Err(MatcherError {
+                offset: op_pos,
+                kind: MatcherErrorKind::NotAVersionField(name),
+            }),
+        }
+    }
+
+    fn parse_op(&mut self) -> Option<Op> {
+        // longer operators first
+        let ops = [
+            ("==", Op::Eq),
+            ("!=", Op::Ne),
+            ("<=", Op::Le),
+            (">=", Op::Ge),
+            ("<", Op::Lt),
+            (">", Op::Gt),
+        ];
+        ops.into_iter()
+            .find_map(|(token, op)| self.eat(token).then_some(op))
+    }
+
+    fn parse_value(&mut self) -> Result<String, MatcherError> {
+        self.skip_whitespace();
+        let value = self.take_while(|c| !c.is_whitespace() && !"()&|!<>=".contains(c));
+        if value.is_empty() {
+            return Err(self.error(MatcherErrorKind::MissingValue));
+        }
+        Ok(value.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_matcher() {
+        let fedora = OsRelease::from_str("ID=fedora\nVERSION_ID=38\nVARIANT_ID=server\n").unwrap();
+        let ubuntu = OsRelease::from_str("ID=ubuntu\nID_LIKE=debian\nVERSION_ID=22.04\n").unwrap();
+        let arch = OsRelease::from_str("ID=arch\n").unwrap();
+
+        let cases: &[(&str, [bool; 3])] = &[
+            ("fedora", [true, false, false]),
+            ("fedora>=38", [true, false, false]),
+            ("fedora>38", [false, false, false]),
+            ("fedora != 39", [true, false, false]),
+            ("id_like:debian", [false, true, false]),
+            ("id:arch", [false, false, true]),
+            ("variant_id:server", [true, false, false]),
+            ("variant_id==server", [true, false, false]),
+            ("variant_id != server", [false, true, true]),
+            ("id==ubuntu", [false, true, false]),
+            ("version_id<23", [false, true, false]),
+            ("version_id>=1", [true, true, false]),
+            ("!version_id>=1", [false, false, true]),
+            ("fedora || arch", [true, false, true]),
+            ("fedora && arch", [false, false, false]),
+            ("arch || fedora && version_id>99", [false, false, true]),
+            ("(arch || fedora) && version_id>99", [false, false, false]),
+            (
+                "fedora>=38 || (id_like:debian && version_id>=22.04)",
+                [true, true, false],
+            ),
+        ];
+        for (spec, expected) in cases {
+            let matcher = OsMatcher::parse(spec).unwrap();
+            let actual = [&fedora, &ubuntu, &arch].map(|os| matcher.matches(os));
+            assert_eq!(&actual, expected, "{spec}");
+        }
+    }
+
+    #[test]
+    fn test_matcher_error() {
+        let error = |spec: &str| {
+            let e = OsMatcher::parse(spec).unwrap_err();
+            (e.offset(), e.kind().clone())
+        };
+        assert_eq!(error(""), (0, MatcherErrorKind::UnexpectedEnd));
+        assert_eq!(error("(fedora"), (7, MatcherErrorKind::UnexpectedEnd));
+        assert_eq!(error("fedora &&"), (9, MatcherErrorKind::UnexpectedEnd));
+        assert_eq!(error("fedora>="), (8, MatcherErrorKind::MissingValue));
+        assert_eq!(error("id_like:"), (8, MatcherErrorKind::MissingValue));
+        assert_eq!(
+            error("fedora arch"),
+            (7, MatcherErrorKind::UnexpectedCharacter('a'))
+        );
+        assert_eq!(
+            error("fedora & arch"),
+            (7, MatcherErrorKind::UnexpectedCharacter('&'))
+        );
+        assert_eq!(
+            error("variant_id >= server"),
+            (11, MatcherErrorKind::NotAVersionField("variant_id".into()))
+        );
+        assert_eq!(
+            error(&"!".repeat(64)),
+            (64, MatcherErrorKind::UnexpectedEnd)
+        );
+        assert_eq!(error(&"!".repeat(65)), (64, MatcherErrorKind::TooDeep));
+        assert_eq!(error(&"(".repeat(100_000)), (64, MatcherErrorKind::TooDeep));
+    }
+}
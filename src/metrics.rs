@@ -0,0 +1,127 @@
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use crate::{Error, OsRelease, ParseOptions};
+
+/// Statistics collected while parsing an os-release file.
+///
+/// See [`OsRelease::from_reader_with_metrics()`] and [`OsReleaseParser::metrics()`](crate::OsReleaseParser::metrics).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ParseMetrics {
+    pub(crate) bytes: u64,
+    pub(crate) lines: usize,
+    pub(crate) entries: usize,
+    pub(crate) duration: Duration,
+}
+
+impl ParseMetrics {
+    /// Returns the number of bytes read.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Returns the number of lines read, including empty lines and comments.
+    pub fn lines(&self) -> usize {
+        self.lines
+    }
+
+    /// Returns the number of fields in the parsed `OsRelease`, or the number of entries yielded by `OsReleaseParser`.
+    ///
+    /// For an `OsRelease`, this may be less than the number of assignments in the file, as duplicated keys are merged.
+    pub fn entries(&self) -> usize {
+        self.entries
+    }
+
+    /// Returns the time spent reading and parsing.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+/// Methods to construct an `OsRelease` with metrics.
+impl OsRelease {
+    /// Parse the os-release file from a reader with the given options, collecting metrics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use etc_os_release::{OsRelease, ParseOptions};
+    ///
+    /// let input = "# comment\nNAME=Fedora\nID=fedora\n";
+    /// let (os_release, metrics) =
+    ///     OsRelease::from_reader_with_metrics(input.as_bytes(), &ParseOptions::new())?;
+    ///
+    /// assert_eq!(os_release.id(), "fedora");
+    /// assert_eq!(metrics.bytes(), input.len() as u64);
+    /// assert_eq!(metrics.lines(), 3);
+    /// assert_eq!(metrics.entries(), 2);
+    /// # Ok::<(), etc_os_release::Error>(())
+    /// ```
+    pub fn from_reader_with_metrics(
+        reader: impl io::Read,
+        options: &ParseOptions,
+    ) -> Result<(Self, ParseMetrics), Error> {
+        let start = Instant::now();
+        let mut reader = CountingReader {
+            inner: reader,
+            bytes: 0,
+            newlines: 0,
+            last: None,
+        };
+        let os_release = Self::from_reader_with(&mut reader, options)?;
+        let lines = reader.newlines + usize::from(reader.last.is_some_and(|b| b != b'\n'));
+        let metrics = ParseMetrics {
+            bytes: reader.bytes,
+            lines,
+            entries: os_release.fields.len(),
+            duration: start.elapsed(),
+        };
+        Ok((os_release, metrics))
+    }
+}
+
+/// A reader that counts the bytes and lines read through it.
+struct CountingReader<R> {
+    inner: R,
+    bytes: u64,
+    newlines: usize,
+    last: Option<u8>,
+}
+
+impl<R: io::Read> io::Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        let buf = &buf[..n];
+        self.bytes += n as u64;
+        self.newlines += buf.iter().filter(|&&b| b == b'\n').count();
+        self.last = buf.last().copied().or(self.last);
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_metrics() {
+        let options = ParseOptions::new();
+        for (input, lines) in [
+            ("A=1\r\nB=2\r\n", 2),
+            ("A=1\nB=2", 2),
+            ("A=1\r\n\r\nA=2", 3),
+            ("", 0),
+        ] {
+            let (_, metrics) =
+                OsRelease::from_reader_with_metrics(input.as_bytes(), &options).unwrap();
+            assert_eq!(metrics.bytes(), input.len() as u64, "{input:?}");
+            assert_eq!(metrics.lines(), lines, "{input:?}");
+        }
+
+        let (_, metrics) =
+            OsRelease::from_reader_with_metrics("A=1\r\n\r\nA=2".as_bytes(), &options).unwrap();
+        assert_eq!(metrics.entries(), 1);
+    }
+}
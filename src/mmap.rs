@@ -0,0 +1,140 @@
+use std::{
+    fs::File,
+    io, ops,
+    os::fd::AsRawFd,
+    path::Path,
+    ptr::{self, NonNull},
+    slice,
+};
+
+use crate::{construct::root_os_release_path, Error, OsRelease, ParseOptions};
+
+/// Methods to open an os-release file by memory mapping.
+#[cfg_attr(docsrs, doc(cfg(all(unix, feature = "mmap"))))]
+impl OsRelease {
+    /// Open the os-release file at `path` by memory mapping it, and parse it.
+    ///
+    /// The mapped file is parsed in place without copying it into a buffer,
+    /// which is useful for scanners that parse thousands of os-release files.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated until this function returns.
+    /// Otherwise the parser may read changing bytes, which is undefined behavior,
+    /// or the process may be killed by `SIGBUS`.
+    /// This holds e.g. for files of unpacked images which are not being written.
+    pub unsafe fn open_mmap(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|err| Error::Open {
+            path: path.to_owned(),
+            err,
+        })?;
+        // SAFETY: the caller guarantees that the file is not modified while the mapping is read.
+        let map = unsafe { Mmap::new(&file) }.map_err(|err| Error::read(err).with_path(path))?;
+        Self::from_bytes_with(&map, &ParseOptions::new()).map_err(|e| e.with_path(path))
+    }
+
+    /// Open the os-release file of the operating system tree at `root` by memory mapping it, and parse it.
+    ///
+    /// The file is searched for in the same way as [`Self::open_root()`], and parsed as in [`Self::open_mmap()`].
+    ///
+    /// # Safety
+    ///
+    /// The same as [`Self::open_mmap()`]: the file found must not be modified or truncated until this function returns.
+    pub unsafe fn open_root_mmap(root: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = root_os_release_path(root.as_ref()).ok_or(Error::NoOsRelease)?;
+        // SAFETY: the caller guarantees that the file is not modified.
+        unsafe { Self::open_mmap(path) }
+    }
+}
+
+/// A read-only memory mapping of a whole file.
+struct Mmap {
+    ptr: NonNull<u8>,
+    len: usize,
+}
+
+impl Mmap {
+    /// Maps the whole `file`.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while the mapping is read.
+    unsafe fn new(file: &File) -> io::Result<Self> {
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if len == 0 {
+            // `mmap()` rejects empty mappings.
+            return Ok(Self {
+                ptr: NonNull::dangling(),
+                len,
+            });
+        }
+        // SAFETY: a new mapping is created, so no existing memory is affected.
+        let ptr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        let ptr = NonNull::new(ptr.cast()).ok_or_else(io::Error::last_os_error)?;
+        Ok(Self { ptr, len })
+    }
+}
+
+impl ops::Deref for Mmap {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: `ptr` is valid for reads of `len` bytes until unmapped, or dangling with `len == 0`,
+        // and the bytes are not modified as guaranteed by the caller of `Mmap::new()`.
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        if self.len > 0 {
+            // SAFETY: `ptr` and `len` are those of a mapping created by `mmap()`, and no references to it remain.
+            unsafe { libc::munmap(self.ptr.as_ptr().cast(), self.len) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env, fs};
+
+    use super::*;
+
+    #[test]
+    fn test_open_mmap() {
+        let root = env::temp_dir().join(format!("etc-os-release-mmap-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("usr/lib")).unwrap();
+        fs::write(root.join("usr/lib/os-release"), crate::SPEC_EXAMPLE).unwrap();
+
+        // SAFETY: the files in `root` are only modified between the calls.
+        let os_release = unsafe { OsRelease::open_root_mmap(&root) }.unwrap();
+        assert!(os_release.entries().eq(OsRelease::spec_example().entries()));
+
+        fs::write(root.join("usr/lib/os-release"), "").unwrap();
+        assert!(unsafe { OsRelease::open_root_mmap(&root) }
+            .unwrap()
+            .is_empty());
+
+        assert!(matches!(
+            unsafe { OsRelease::open_root_mmap(root.join("nonexistent")) },
+            Err(Error::NoOsRelease)
+        ));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
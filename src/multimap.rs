@@ -0,0 +1,179 @@
+use std::{convert::Infallible, io, str::FromStr};
+
+use crate::{
+    construct::{decode_with, parse_strict_lines, read_limited},
+    entry::{parse_line, strip_bom, strip_trailing_comment},
+    Error, OsRelease, OsReleaseEntry, ParseOptions,
+};
+
+/// An os-release file which keeps every assignment, including repeated assignments of the same key.
+///
+/// [`OsRelease`] keeps only the last assignment of each key, as the shell does.
+/// This type keeps all of them in the order of the file, with their line numbers,
+/// so that auditing tools can report exactly what the file contains.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::str::FromStr;
+///
+/// use etc_os_release::OsReleaseMultiMap;
+///
+/// let multimap = OsReleaseMultiMap::from_str("ID=fedora\nVERSION_ID=39\n\nVERSION_ID=40\n").unwrap();
+/// assert_eq!(multimap.get_all("VERSION_ID").collect::<Vec<_>>(), ["39", "40"]);
+/// assert_eq!(multimap.get("VERSION_ID"), Some("40"));
+/// assert_eq!(multimap.duplicate_keys().collect::<Vec<_>>(), ["VERSION_ID"]);
+///
+/// let lines = multimap.iter().map(|(line, entry)| (line, entry.key())).collect::<Vec<_>>();
+/// assert_eq!(lines, [(1, "ID"), (2, "VERSION_ID"), (4, "VERSION_ID")]);
+///
+/// let os_release = multimap.to_os_release();
+/// assert_eq!(os_release.version_id(), Some("40"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OsReleaseMultiMap {
+    entries: Vec<(usize, OsReleaseEntry<'static>)>,
+}
+
+impl OsReleaseMultiMap {
+    /// Parse the os-release file from a reader.
+    ///
+    /// For simplicity, this function assumes that the file is well-formed.
+    pub fn from_reader(reader: impl io::Read) -> Result<Self, Error> {
+        Self::from_reader_with(reader, &ParseOptions::new())
+    }
+
+    /// Parse the os-release file from a reader with the given options.
+    ///
+    /// The options are applied as in [`OsRelease::from_reader_with()`],
+    /// except that [`ParseOptions::empty_as_unset()`] drops only the empty assignments themselves.
+    pub fn from_reader_with(reader: impl io::Read, options: &ParseOptions) -> Result<Self, Error> {
+        let buf = read_limited(reader, options)?;
+        Self::from_str_with(decode_with(&buf, options)?, options)
+    }
+
+    /// Parse a string containing the contents of the os-release file with the given options.
+    ///
+    /// See [`Self::from_reader_with()`].
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self, Error> {
+        let strip = options.is_strip_trailing_comments();
+        let lines = strip_bom(s).lines().map(|line| {
+            if strip {
+                strip_trailing_comment(line)
+            } else {
+                line
+            }
+        });
+        let mut entries = if options.is_strict() {
+            parse_strict_lines(lines, options)?
+        } else {
+            parse_lenient_lines(lines)
+        };
+        if options.is_empty_as_unset() {
+            entries.retain(|(_, entry)| !entry.value().is_empty());
+        }
+        Ok(Self { entries })
+    }
+
+    /// Returns an iterator over all assignments and their 1-based line numbers, in the order of the file.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &OsReleaseEntry<'static>)> {
+        self.entries.iter().map(|(line, entry)| (*line, entry))
+    }
+
+    /// Returns the values of all assignments of the field `key`, in the order of the file.
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> {
+        self.entries
+            .iter()
+            .filter(move |(_, entry)| entry.key() == key)
+            .map(|(_, entry)| entry.value())
+    }
+
+    /// Returns the value of the last assignment of the field `key`, which is the one [`OsRelease`] keeps.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(_, entry)| entry.key() == key)
+            .map(|(_, entry)| entry.value())
+    }
+
+    /// Returns the keys assigned more than once, in the order of their first assignment.
+    pub fn duplicate_keys(&self) -> impl Iterator<Item = &str> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(i, (_, entry))| {
+                let key = entry.key();
+                let mut previous = self.entries[..*i].iter().map(|(_, e)| e.key());
+                let mut following = self.entries[i + 1..].iter().map(|(_, e)| e.key());
+                !previous.any(|k| k == key) && following.any(|k| k == key)
+            })
+            .map(|(_, (_, entry))| entry.key())
+    }
+
+    /// Returns the number of assignments.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no assignments.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Converts to an [`OsRelease`], keeping the last assignment of each key.
+    pub fn to_os_release(&self) -> OsRelease {
+        self.entries
+            .iter()
+            .map(|(_, entry)| OsReleaseEntry::new(entry.key(), entry.value()))
+            .collect()
+    }
+}
+
+impl FromStr for OsReleaseMultiMap {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entries = parse_lenient_lines(strip_bom(s).lines());
+        Ok(Self { entries })
+    }
+}
+
+/// Parse the lines, ignoring malformed lines, returning the entries with their 1-based line numbers.
+fn parse_lenient_lines<'a>(
+    lines: impl IntoIterator<Item = &'a str>,
+) -> Vec<(usize, OsReleaseEntry<'static>)> {
+    lines
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i + 1, parse_line(line)?.into_owned())))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_multimap() {
+        let input = "\u{feff}A=1\r\nB=2\n# A=3\nA=\nA=4\nB=5\nC=6\n";
+        let multimap = OsReleaseMultiMap::from_str(input).unwrap();
+        assert_eq!(multimap.len(), 6);
+        assert_eq!(multimap.get_all("A").collect::<Vec<_>>(), ["1", "", "4"]);
+        assert_eq!(multimap.get("C"), Some("6"));
+        assert_eq!(multimap.get("D"), None);
+        assert_eq!(multimap.duplicate_keys().collect::<Vec<_>>(), ["A", "B"]);
+        assert_eq!(
+            multimap.to_os_release(),
+            OsRelease::from_str(input).unwrap()
+        );
+
+        let options = ParseOptions::new().empty_as_unset(true);
+        let multimap = OsReleaseMultiMap::from_str_with(input, &options).unwrap();
+        assert_eq!(multimap.get_all("A").collect::<Vec<_>>(), ["1", "4"]);
+
+        let options = ParseOptions::new().strict(true);
+        let err = OsReleaseMultiMap::from_reader_with("A=1\nA B\n".as_bytes(), &options);
+        assert!(matches!(err, Err(Error::Malformed { errors, .. }) if errors[0].line() == 2));
+    }
+}
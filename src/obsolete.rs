@@ -0,0 +1,89 @@
+use crate::{keys, OsRelease};
+
+/// A field superseded by a field defined by os-release(5).
+///
+/// See [`OsRelease::obsolete_fields()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ObsoleteField<'a> {
+    key: &'a str,
+    value: &'a str,
+    replacement: &'static str,
+    replacement_set: bool,
+}
+
+impl<'a> ObsoleteField<'a> {
+    /// Returns the key of the obsolete field.
+    pub fn key(&self) -> &'a str {
+        self.key
+    }
+
+    /// Returns the value of the obsolete field.
+    pub fn value(&self) -> &'a str {
+        self.value
+    }
+
+    /// Returns the key of the field to use instead.
+    pub fn replacement(&self) -> &'static str {
+        self.replacement
+    }
+
+    /// Returns `true` if the replacement is already set, so that the obsolete field can simply be removed.
+    pub fn is_replacement_set(&self) -> bool {
+        self.replacement_set
+    }
+}
+
+/// Obsolete keys and the keys that supersede them.
+///
+/// `UBUNTU_CODENAME=` and `DEBIAN_CODENAME=` are not listed: derivatives set them to the code name of the release
+/// they are based on, which differs from their own `VERSION_CODENAME=`.
+const OBSOLETE_KEYS: &[(&str, &str)] = &[
+    // Fields of `/etc/lsb-release`, sometimes copied into the os-release file.
+    ("DISTRIB_ID", keys::ID),
+    ("DISTRIB_RELEASE", keys::VERSION_ID),
+    ("DISTRIB_CODENAME", keys::VERSION_CODENAME),
+    ("DISTRIB_DESCRIPTION", keys::PRETTY_NAME),
+];
+
+/// Methods to find obsolete usage.
+impl OsRelease {
+    /// Returns the fields superseded by fields defined by os-release(5), in the order of the file.
+    ///
+    /// These are fields of `/etc/lsb-release` with standardized replacements.
+    /// Distribution maintainers can use this to modernize their os-release files.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// ID=ubuntu
+    /// VERSION_CODENAME=noble
+    /// UBUNTU_CODENAME=noble
+    /// DISTRIB_CODENAME=noble
+    /// DISTRIB_RELEASE=24.04
+    /// "#).unwrap();
+    ///
+    /// let obsolete = os_release.obsolete_fields().collect::<Vec<_>>();
+    /// assert_eq!(obsolete.len(), 2);
+    /// assert_eq!(obsolete[0].key(), "DISTRIB_CODENAME");
+    /// assert_eq!(obsolete[0].replacement(), "VERSION_CODENAME");
+    /// assert!(obsolete[0].is_replacement_set());
+    /// assert_eq!(obsolete[1].replacement(), "VERSION_ID");
+    /// assert!(!obsolete[1].is_replacement_set());
+    /// ```
+    pub fn obsolete_fields(&self) -> impl Iterator<Item = ObsoleteField<'_>> {
+        self.fields.iter().filter_map(|(key, value)| {
+            let (_, replacement) = OBSOLETE_KEYS.iter().find(|(k, _)| k == key)?;
+            Some(ObsoleteField {
+                key,
+                value,
+                replacement,
+                replacement_set: self.contains_key(replacement),
+            })
+        })
+    }
+}
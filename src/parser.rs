@@ -0,0 +1,220 @@
+use std::{
+    io::{self, BufRead, BufReader, Read},
+    time::Instant,
+};
+
+use crate::{
+    entry::{parse_line, strip_trailing_comment, LineError, OsReleaseLine},
+    Error, OsReleaseEntry, ParseMetrics, ParseOptions,
+};
+
+/// A lazy parser of the os-release file, yielding entries one by one.
+///
+/// Unlike [`OsRelease::from_reader()`](crate::OsRelease::from_reader), no map of all fields is built,
+/// and the input is read only as far as the entries are consumed.
+/// This is useful when only a few fields are needed.
+///
+/// The parser stops after the first error.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::{OsReleaseParser, SPEC_EXAMPLE};
+///
+/// let id = OsReleaseParser::new(SPEC_EXAMPLE.as_bytes())
+///     .filter_map(Result::ok)
+///     .find(|entry| entry.key() == "ID");
+/// assert_eq!(id.unwrap().value(), "fedora");
+/// ```
+#[derive(Debug)]
+pub struct OsReleaseParser<R> {
+    reader: BufReader<R>,
+    options: ParseOptions,
+    line: String,
+    metrics: ParseMetrics,
+    done: bool,
+}
+
+impl<R: io::Read> OsReleaseParser<R> {
+    /// Creates a parser reading from `reader`.
+    ///
+    /// For simplicity, the parser assumes that the file is well-formed.
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, &ParseOptions::new())
+    }
+
+    /// Creates a parser reading from `reader` with the given options.
+    ///
+    /// In strict mode, [`Error::Malformed`] is yielded for the first malformed line.
+    /// [`Error::TooLarge`] is yielded once more than [`ParseOptions::max_size()`] bytes have been read.
+    /// With [`ParseOptions::empty_as_unset()`], entries with empty values are skipped.
+    pub fn with_options(reader: R, options: &ParseOptions) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            options: options.clone(),
+            line: String::new(),
+            metrics: ParseMetrics::default(),
+            done: false,
+        }
+    }
+
+    /// Returns the statistics of the input consumed so far.
+    ///
+    /// [`ParseMetrics::entries()`] is the number of entries yielded, and
+    /// [`ParseMetrics::duration()`] is the time spent in [`Iterator::next()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use etc_os_release::OsReleaseParser;
+    ///
+    /// let mut parser = OsReleaseParser::new("ID=fedora\r\n\r\nVERSION_ID=40".as_bytes());
+    /// assert!(parser.by_ref().all(|entry| entry.is_ok()));
+    ///
+    /// let metrics = parser.metrics();
+    /// assert_eq!(metrics.bytes(), 26);
+    /// assert_eq!(metrics.lines(), 3);
+    /// assert_eq!(metrics.entries(), 2);
+    /// ```
+    pub fn metrics(&self) -> ParseMetrics {
+        self.metrics
+    }
+
+    fn next_line(&mut self) -> Result<bool, Error> {
+        self.line.clear();
+        let max_size = self.options.get_max_size();
+        let limit = max_size
+            .saturating_sub(self.metrics.bytes)
+            .saturating_add(1);
+        let n = (&mut self.reader)
+            .take(limit)
+            .read_line(&mut self.line)
+            .map_err(|err| Error::Read {
+                path: None,
+                line: Some(self.metrics.lines + 1),
+                err,
+            })?;
+        if n == 0 {
+            return Ok(false);
+        }
+        self.metrics.bytes += n as u64;
+        if self.metrics.bytes > max_size {
+            return Err(Error::TooLarge { max_size });
+        }
+        if self.metrics.lines == 0 && self.line.starts_with('\u{feff}') {
+            self.line.drain(..'\u{feff}'.len_utf8());
+        }
+        self.metrics.lines += 1;
+        // Strip the line terminator in the same way as `str::lines()`.
+        if self.line.ends_with('\n') {
+            self.line.pop();
+            if self.line.ends_with('\r') {
+                self.line.pop();
+            }
+        }
+        Ok(true)
+    }
+
+    fn parse_next(&mut self) -> Option<Result<OsReleaseEntry<'static>, Error>> {
+        while !self.done {
+            match self.next_line() {
+                Ok(true) => {}
+                Ok(false) => {
+                    self.done = true;
+                    return None;
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+
+            let line = if self.options.is_strip_trailing_comments() {
+                strip_trailing_comment(&self.line)
+            } else {
+                &self.line
+            };
+            let entry = if self.options.is_strict() {
+                match OsReleaseLine::parse_strict(line) {
+                    Ok(line) => line.into_entry(),
+                    Err(kind) => {
+                        self.done = true;
+                        let errors = vec![LineError::new(self.metrics.lines, kind)];
+                        return Some(Err(Error::Malformed { errors, omitted: 0 }));
+                    }
+                }
+            } else {
+                parse_line(line)
+            };
+            match entry {
+                Some(entry) if self.options.is_empty_as_unset() && entry.value().is_empty() => {}
+                Some(entry) => return Some(Ok(entry.into_owned())),
+                None => {}
+            }
+        }
+        None
+    }
+}
+
+impl<R: io::Read> Iterator for OsReleaseParser<R> {
+    type Item = Result<OsReleaseEntry<'static>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = Instant::now();
+        let item = self.parse_next();
+        self.metrics.duration += start.elapsed();
+        if let Some(Ok(_)) = item {
+            self.metrics.entries += 1;
+        }
+        item
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parser() {
+        let input = "# comment\r\nNAME=\"Fedora Linux\"\r\n\nID=fedora\nVERSION ID=40\nA=B";
+        let entries = OsReleaseParser::new(input.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let keys = entries.iter().map(|e| e.key()).collect::<Vec<_>>();
+        assert_eq!(keys, ["NAME", "ID", "VERSION ID", "A"]);
+        assert_eq!(entries[0].value(), "Fedora Linux");
+
+        let mut parser = OsReleaseParser::new(input.as_bytes());
+        assert!(parser.by_ref().all(|entry| entry.is_ok()));
+        let metrics = parser.metrics();
+        assert_eq!(metrics.bytes(), input.len() as u64);
+        assert_eq!(metrics.lines(), 6);
+        assert_eq!(metrics.entries(), 4);
+
+        let options = ParseOptions::new().strict(true);
+        let mut parser = OsReleaseParser::with_options(input.as_bytes(), &options);
+        assert_eq!(parser.next().unwrap().unwrap().key(), "NAME");
+        assert_eq!(parser.next().unwrap().unwrap().key(), "ID");
+        let Some(Err(Error::Malformed { errors, .. })) = parser.next() else {
+            panic!("expected an error");
+        };
+        assert_eq!(errors[0].line(), 5);
+        assert!(parser.next().is_none());
+
+        let options = ParseOptions::new().max_size(45);
+        let mut parser = OsReleaseParser::with_options(input.as_bytes(), &options);
+        assert_eq!(parser.next().unwrap().unwrap().key(), "NAME");
+        assert_eq!(parser.next().unwrap().unwrap().key(), "ID");
+        assert!(matches!(
+            parser.next(),
+            Some(Err(Error::TooLarge { max_size: 45 }))
+        ));
+        assert!(parser.next().is_none());
+
+        let options = ParseOptions::new().empty_as_unset(true);
+        let keys = OsReleaseParser::with_options("A=\nB=1\nC=''\n".as_bytes(), &options)
+            .map(|e| e.unwrap().key().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, ["B"]);
+    }
+}
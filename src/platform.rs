@@ -0,0 +1,90 @@
+use crate::{Error, OsRelease, OsReleaseEntry};
+
+#[cfg(feature = "android")]
+mod android;
+#[cfg(feature = "bsd")]
+mod bsd;
+#[cfg(feature = "macos")]
+mod macos;
+#[cfg(feature = "windows")]
+mod windows;
+
+/// Methods to construct an `OsRelease` on platforms without the os-release file.
+impl OsRelease {
+    /// Synthesize an `OsRelease` from platform-specific information.
+    ///
+    /// This is used by [`Self::open()`] when no os-release file is found,
+    /// so that cross-platform applications can use one API to identify the operating system.
+    /// The following platforms are supported, each behind a feature of the same name:
+    ///
+    /// * `android`: from the system properties `ro.build.version.release`, `ro.build.id` and others
+    ///   (`ID=android`, `NAME=`, `VERSION_ID=`, `VERSION=`, `BUILD_ID=` and `PRETTY_NAME=`,
+    ///   plus `ANDROID_SDK=`, `ANDROID_SECURITY_PATCH=`, `ANDROID_PRODUCT_MANUFACTURER=` and `ANDROID_PRODUCT_MODEL=`)
+    /// * `bsd`: on FreeBSD, OpenBSD, NetBSD and DragonFly BSD, from `uname()` and `freebsd-version`
+    ///   (`ID=` such as `openbsd`, `NAME=`, `VERSION_ID=`, `VERSION=`, `PRETTY_NAME=` and `HOME_URL=`)
+    /// * `macos`: from `/System/Library/CoreServices/SystemVersion.plist`
+    ///   (`ID=macos`, `NAME=`, `VERSION_ID=`, `VERSION=`, `BUILD_ID=` and `PRETTY_NAME=`)
+    /// * `windows`: from `RtlGetVersion()` and the `HKLM\SOFTWARE\Microsoft\Windows NT\CurrentVersion` registry key
+    ///   (`ID=windows`, `NAME=`, `VERSION_ID=` as `<major>.<minor>.<build>`, `VERSION=`, `BUILD_ID=` and `PRETTY_NAME=`)
+    ///
+    /// Returns [`Error::NoOsRelease`] on other platforms.
+    pub fn from_platform() -> Result<Self, Error> {
+        open_platform()
+    }
+}
+
+/// Build an `OsRelease` from the fields synthesized by a provider, skipping those without a value.
+pub(crate) fn from_optional_fields(
+    fields: impl IntoIterator<Item = (&'static str, Option<String>)>,
+) -> OsRelease {
+    fields
+        .into_iter()
+        .filter_map(|(key, value)| Some(OsReleaseEntry::new(key, value?)))
+        .collect()
+}
+
+#[cfg(all(feature = "android", target_os = "android"))]
+fn open_platform() -> Result<OsRelease, Error> {
+    android::open()
+}
+
+#[cfg(all(
+    feature = "bsd",
+    any(
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "dragonfly",
+    ),
+))]
+fn open_platform() -> Result<OsRelease, Error> {
+    bsd::open()
+}
+
+#[cfg(all(feature = "macos", target_os = "macos"))]
+fn open_platform() -> Result<OsRelease, Error> {
+    macos::open()
+}
+
+#[cfg(all(feature = "windows", windows))]
+fn open_platform() -> Result<OsRelease, Error> {
+    windows::open()
+}
+
+#[cfg(not(any(
+    all(feature = "android", target_os = "android"),
+    all(
+        feature = "bsd",
+        any(
+            target_os = "freebsd",
+            target_os = "openbsd",
+            target_os = "netbsd",
+            target_os = "dragonfly",
+        ),
+    ),
+    all(feature = "macos", target_os = "macos"),
+    all(feature = "windows", windows),
+)))]
+fn open_platform() -> Result<OsRelease, Error> {
+    Err(Error::NoOsRelease)
+}
@@ -0,0 +1,96 @@
+#![cfg_attr(not(target_os = "android"), allow(dead_code))]
+
+use crate::OsRelease;
+
+#[cfg(target_os = "android")]
+pub(super) fn open() -> Result<OsRelease, crate::Error> {
+    from_properties(sys::property).ok_or(crate::Error::NoOsRelease)
+}
+
+/// Synthesize an `OsRelease` from the system properties returned by `property`.
+fn from_properties(property: impl Fn(&str) -> Option<String>) -> Option<OsRelease> {
+    let release = property("ro.build.version.release")?;
+    let sdk = property("ro.build.version.sdk");
+    let version = match &sdk {
+        Some(sdk) => format!("{release} (API level {sdk})"),
+        None => release.clone(),
+    };
+    Some(super::from_optional_fields([
+        ("NAME", Some("Android".to_owned())),
+        ("ID", Some("android".to_owned())),
+        ("VERSION", Some(version)),
+        ("VERSION_ID", Some(release.clone())),
+        ("BUILD_ID", property("ro.build.id")),
+        ("PRETTY_NAME", Some(format!("Android {release}"))),
+        ("HOME_URL", Some("https://www.android.com/".to_owned())),
+        ("ANDROID_SDK", sdk),
+        (
+            "ANDROID_SECURITY_PATCH",
+            property("ro.build.version.security_patch"),
+        ),
+        (
+            "ANDROID_PRODUCT_MANUFACTURER",
+            property("ro.product.manufacturer"),
+        ),
+        ("ANDROID_PRODUCT_MODEL", property("ro.product.model")),
+    ]))
+}
+
+#[cfg(target_os = "android")]
+mod sys {
+    use std::ffi::{c_char, c_int, CStr, CString};
+
+    /// The maximum length of a property value, including the terminating NUL.
+    const PROP_VALUE_MAX: usize = 92;
+
+    extern "C" {
+        fn __system_property_get(name: *const c_char, value: *mut c_char) -> c_int;
+    }
+
+    pub(super) fn property(name: &str) -> Option<String> {
+        let name = CString::new(name).ok()?;
+        let mut value = [0 as c_char; PROP_VALUE_MAX];
+        // SAFETY: `name` is NUL-terminated and `value` has room for `PROP_VALUE_MAX` bytes.
+        let len = unsafe { __system_property_get(name.as_ptr(), value.as_mut_ptr()) };
+        if len <= 0 {
+            return None;
+        }
+        // SAFETY: the value is NUL-terminated.
+        let value = unsafe { CStr::from_ptr(value.as_ptr()) };
+        value.to_str().ok().map(str::to_owned)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_properties() {
+        let properties = [
+            ("ro.build.version.release", "14"),
+            ("ro.build.version.sdk", "34"),
+            ("ro.build.id", "UQ1A.240205.004"),
+            ("ro.product.manufacturer", "Google"),
+            ("ro.product.model", "Pixel 8"),
+        ];
+        let property = |name: &str| {
+            properties
+                .iter()
+                .find_map(|(n, v)| (*n == name).then(|| (*v).to_owned()))
+        };
+        let os_release = from_properties(property).unwrap();
+        assert_eq!(os_release.id(), "android");
+        assert_eq!(os_release.version(), Some("14 (API level 34)"));
+        assert_eq!(os_release.version_id(), Some("14"));
+        assert_eq!(os_release.build_id(), Some("UQ1A.240205.004"));
+        assert_eq!(os_release.pretty_name(), "Android 14");
+        assert_eq!(
+            os_release.get_value("ANDROID_PRODUCT_MODEL"),
+            Some("Pixel 8")
+        );
+        assert_eq!(os_release.get_value("ANDROID_SECURITY_PATCH"), None);
+
+        assert!(from_properties(|_| None).is_none());
+    }
+}
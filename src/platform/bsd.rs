@@ -0,0 +1,90 @@
+#![cfg_attr(
+    not(any(
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "dragonfly"
+    )),
+    allow(dead_code)
+)]
+
+use crate::OsRelease;
+
+#[cfg(any(
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd",
+    target_os = "dragonfly",
+))]
+pub(super) fn open() -> Result<OsRelease, crate::Error> {
+    let uts = crate::uname::uname().map_err(|_| crate::Error::NoOsRelease)?;
+    // The userland version may be newer than the kernel version reported by uname.
+    let release = sys::freebsd_version().unwrap_or(uts.release);
+    from_uname(&uts.sysname, &release).ok_or(crate::Error::NoOsRelease)
+}
+
+/// Synthesize an `OsRelease` from the system name and release reported by `uname`.
+fn from_uname(sysname: &str, release: &str) -> Option<OsRelease> {
+    let (id, home_url) = match sysname {
+        "FreeBSD" => ("freebsd", "https://FreeBSD.org/"),
+        "OpenBSD" => ("openbsd", "https://www.openbsd.org/"),
+        "NetBSD" => ("netbsd", "https://www.netbsd.org/"),
+        "DragonFly" => ("dragonfly", "https://www.dragonflybsd.org/"),
+        _ => return None,
+    };
+    // e.g. `14.0-RELEASE-p6` -> `14.0`
+    let version_id = release.split('-').next().unwrap_or(release);
+    let cpe_name = (id == "freebsd").then(|| format!("cpe:/o:freebsd:freebsd:{version_id}"));
+    Some(super::from_optional_fields([
+        ("NAME", Some(sysname.to_owned())),
+        ("ID", Some(id.to_owned())),
+        ("VERSION", Some(release.to_owned())),
+        ("VERSION_ID", Some(version_id.to_owned())),
+        ("PRETTY_NAME", Some(format!("{sysname} {release}"))),
+        ("CPE_NAME", cpe_name),
+        ("HOME_URL", Some(home_url.to_owned())),
+    ]))
+}
+
+#[cfg(any(
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd",
+    target_os = "dragonfly",
+))]
+mod sys {
+    use std::process::Command;
+
+    /// Returns the version of the installed userland reported by `freebsd-version -u`, on FreeBSD.
+    pub(super) fn freebsd_version() -> Option<String> {
+        if !cfg!(target_os = "freebsd") {
+            return None;
+        }
+        let output = Command::new("freebsd-version").arg("-u").output().ok()?;
+        let version = String::from_utf8(output.stdout).ok()?;
+        let version = version.trim();
+        (output.status.success() && !version.is_empty()).then(|| version.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_uname() {
+        let os_release = from_uname("FreeBSD", "14.0-RELEASE-p6").unwrap();
+        assert_eq!(os_release.id(), "freebsd");
+        assert_eq!(os_release.version(), Some("14.0-RELEASE-p6"));
+        assert_eq!(os_release.version_id(), Some("14.0"));
+        assert_eq!(os_release.pretty_name(), "FreeBSD 14.0-RELEASE-p6");
+        assert_eq!(os_release.cpe_name(), Some("cpe:/o:freebsd:freebsd:14.0"));
+
+        let os_release = from_uname("OpenBSD", "7.5").unwrap();
+        assert_eq!(os_release.id(), "openbsd");
+        assert_eq!(os_release.version_id(), Some("7.5"));
+        assert_eq!(os_release.cpe_name(), None);
+
+        assert!(from_uname("Linux", "6.8.0").is_none());
+    }
+}
@@ -0,0 +1,78 @@
+#![cfg_attr(not(target_os = "macos"), allow(dead_code))]
+
+use crate::OsRelease;
+
+#[cfg(target_os = "macos")]
+pub(super) fn open() -> Result<OsRelease, crate::Error> {
+    use std::{fs, path::Path};
+
+    let path = Path::new("/System/Library/CoreServices/SystemVersion.plist");
+    let plist = fs::read_to_string(path).map_err(|err| match err.kind() {
+        std::io::ErrorKind::NotFound => crate::Error::NoOsRelease,
+        _ => crate::Error::Open {
+            path: path.to_owned(),
+            err,
+        },
+    })?;
+    from_plist(&plist).ok_or(crate::Error::NoOsRelease)
+}
+
+/// Synthesize an `OsRelease` from the contents of `SystemVersion.plist`.
+fn from_plist(plist: &str) -> Option<OsRelease> {
+    let name = plist_string(plist, "ProductName").unwrap_or("macOS");
+    let version = plist_string(plist, "ProductVersion")?;
+    let build = plist_string(plist, "ProductBuildVersion");
+    Some(super::from_optional_fields([
+        ("NAME", Some(name.to_owned())),
+        ("ID", Some("macos".to_owned())),
+        ("VERSION", Some(version.to_owned())),
+        ("VERSION_ID", Some(version.to_owned())),
+        ("BUILD_ID", build.map(str::to_owned)),
+        ("PRETTY_NAME", Some(format!("{name} {version}"))),
+        ("HOME_URL", Some("https://www.apple.com/macos/".to_owned())),
+    ]))
+}
+
+/// Returns the `<string>` value of `key` in an XML property list dictionary.
+fn plist_string<'a>(plist: &'a str, key: &str) -> Option<&'a str> {
+    let key = format!("<key>{key}</key>");
+    let rest = plist[plist.find(&key)? + key.len()..].trim_start();
+    let rest = rest.strip_prefix("<string>")?;
+    Some(rest[..rest.find("</string>")?].trim())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_plist() {
+        let plist = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>BuildID</key>
+	<string>5E0A9F8C-0B3C-11EF-8A5E-A8AD2B8E6B0E</string>
+	<key>ProductBuildVersion</key>
+	<string>23E224</string>
+	<key>ProductCopyright</key>
+	<string>1983-2024 Apple Inc.</string>
+	<key>ProductName</key>
+	<string>macOS</string>
+	<key>ProductUserVisibleVersion</key>
+	<string>14.4.1</string>
+	<key>ProductVersion</key>
+	<string>14.4.1</string>
+</dict>
+</plist>
+"#;
+        let os_release = from_plist(plist).unwrap();
+        assert_eq!(os_release.id(), "macos");
+        assert_eq!(os_release.name(), "macOS");
+        assert_eq!(os_release.version_id(), Some("14.4.1"));
+        assert_eq!(os_release.build_id(), Some("23E224"));
+        assert_eq!(os_release.pretty_name(), "macOS 14.4.1");
+
+        assert!(from_plist("<plist><dict></dict></plist>").is_none());
+    }
+}
@@ -0,0 +1,232 @@
+#![cfg_attr(not(windows), allow(dead_code))]
+
+use crate::OsRelease;
+
+/// Version information of Windows, from `RtlGetVersion()` and the registry.
+#[derive(Debug, Default)]
+struct WindowsVersion {
+    major: u32,
+    minor: u32,
+    build: u32,
+    /// `UBR` (update build revision)
+    ubr: Option<u32>,
+    /// `ProductName`, e.g. `Windows 10 Pro`
+    product_name: Option<String>,
+    /// `DisplayVersion`, e.g. `23H2`
+    display_version: Option<String>,
+}
+
+#[cfg(windows)]
+pub(super) fn open() -> Result<OsRelease, crate::Error> {
+    Ok(from_version(&sys::version()))
+}
+
+/// Returns the product name of the version, for systems without `ProductName` in the registry.
+fn product_name_of(version: &WindowsVersion) -> String {
+    let name = match (version.major, version.minor) {
+        (10, 0) if version.build >= 22000 => "11",
+        (10, 0) => "10",
+        (6, 3) => "8.1",
+        (6, 2) => "8",
+        (6, 1) => "7",
+        (6, 0) => "Vista",
+        (5, 1 | 2) => "XP",
+        (major, minor) => return format!("Windows NT {major}.{minor}"),
+    };
+    format!("Windows {name}")
+}
+
+/// Synthesize an `OsRelease` from the version information.
+fn from_version(version: &WindowsVersion) -> OsRelease {
+    // Windows 11 still reports itself as Windows 10 in `ProductName`.
+    let product_name = version.product_name.as_deref().map(|name| {
+        if version.major == 10 && version.build >= 22000 {
+            name.replacen("Windows 10", "Windows 11", 1)
+        } else {
+            name.to_owned()
+        }
+    });
+    let name = product_name.unwrap_or_else(|| product_name_of(version));
+    let pretty_name = match &version.display_version {
+        Some(display_version) => format!("{name} {display_version}"),
+        None => name,
+    };
+    let build_id = match version.ubr {
+        Some(ubr) => format!("{}.{ubr}", version.build),
+        None => version.build.to_string(),
+    };
+    super::from_optional_fields([
+        ("NAME", Some("Windows".to_owned())),
+        ("ID", Some("windows".to_owned())),
+        ("VERSION", version.display_version.clone()),
+        (
+            "VERSION_ID",
+            Some(format!(
+                "{}.{}.{}",
+                version.major, version.minor, version.build
+            )),
+        ),
+        ("BUILD_ID", Some(build_id)),
+        ("PRETTY_NAME", Some(pretty_name)),
+        (
+            "HOME_URL",
+            Some("https://www.microsoft.com/windows/".to_owned()),
+        ),
+    ])
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::{ffi::c_void, os::windows::ffi::OsStringExt, ptr};
+
+    use super::WindowsVersion;
+
+    #[repr(C)]
+    struct OsVersionInfoW {
+        size: u32,
+        major: u32,
+        minor: u32,
+        build: u32,
+        platform_id: u32,
+        csd_version: [u16; 128],
+    }
+
+    #[link(name = "ntdll")]
+    extern "system" {
+        fn RtlGetVersion(info: *mut OsVersionInfoW) -> i32;
+    }
+
+    #[link(name = "advapi32")]
+    extern "system" {
+        fn RegGetValueW(
+            hkey: isize,
+            sub_key: *const u16,
+            value: *const u16,
+            flags: u32,
+            kind: *mut u32,
+            data: *mut c_void,
+            len: *mut u32,
+        ) -> i32;
+    }
+
+    const HKEY_LOCAL_MACHINE: isize = 0x8000_0002_u32 as i32 as isize;
+    const RRF_RT_REG_SZ: u32 = 0x0000_0002;
+    const RRF_RT_REG_DWORD: u32 = 0x0000_0010;
+    const CURRENT_VERSION: &str = r"SOFTWARE\Microsoft\Windows NT\CurrentVersion";
+
+    pub(super) fn version() -> WindowsVersion {
+        let mut info = OsVersionInfoW {
+            size: size_of::<OsVersionInfoW>() as u32,
+            major: 0,
+            minor: 0,
+            build: 0,
+            platform_id: 0,
+            csd_version: [0; 128],
+        };
+        // SAFETY: `info` is a valid `OSVERSIONINFOW` with `dwOSVersionInfoSize` set.
+        unsafe { RtlGetVersion(&mut info) };
+
+        WindowsVersion {
+            major: info.major,
+            minor: info.minor,
+            build: info.build,
+            ubr: reg_dword("UBR"),
+            product_name: reg_string("ProductName"),
+            display_version: reg_string("DisplayVersion"),
+        }
+    }
+
+    fn wide(s: &str) -> Vec<u16> {
+        s.encode_utf16().chain([0]).collect()
+    }
+
+    fn reg_dword(value: &str) -> Option<u32> {
+        let (sub_key, value) = (wide(CURRENT_VERSION), wide(value));
+        let mut data = 0_u32;
+        let mut len = size_of::<u32>() as u32;
+        // SAFETY: the strings are NUL-terminated and `data` is valid for `len` bytes.
+        let ret = unsafe {
+            RegGetValueW(
+                HKEY_LOCAL_MACHINE,
+                sub_key.as_ptr(),
+                value.as_ptr(),
+                RRF_RT_REG_DWORD,
+                ptr::null_mut(),
+                (&mut data as *mut u32).cast(),
+                &mut len,
+            )
+        };
+        (ret == 0).then_some(data)
+    }
+
+    fn reg_string(value: &str) -> Option<String> {
+        let (sub_key, value) = (wide(CURRENT_VERSION), wide(value));
+        let mut data = [0_u16; 256];
+        let mut len = size_of_val(&data) as u32;
+        // SAFETY: the strings are NUL-terminated and `data` is valid for `len` bytes.
+        let ret = unsafe {
+            RegGetValueW(
+                HKEY_LOCAL_MACHINE,
+                sub_key.as_ptr(),
+                value.as_ptr(),
+                RRF_RT_REG_SZ,
+                ptr::null_mut(),
+                data.as_mut_ptr().cast(),
+                &mut len,
+            )
+        };
+        if ret != 0 {
+            return None;
+        }
+        let data = &data[..len as usize / 2];
+        let data = data.split(|&c| c == 0).next().unwrap_or_default();
+        let s = std::ffi::OsString::from_wide(data).into_string().ok()?;
+        Some(s).filter(|s| !s.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_version() {
+        let os_release = from_version(&WindowsVersion {
+            major: 10,
+            minor: 0,
+            build: 22631,
+            ubr: Some(3447),
+            product_name: Some("Windows 10 Pro".to_owned()),
+            display_version: Some("23H2".to_owned()),
+        });
+        assert_eq!(os_release.id(), "windows");
+        assert_eq!(os_release.version(), Some("23H2"));
+        assert_eq!(os_release.version_id(), Some("10.0.22631"));
+        assert_eq!(os_release.build_id(), Some("22631.3447"));
+        assert_eq!(os_release.pretty_name(), "Windows 11 Pro 23H2");
+
+        let os_release = from_version(&WindowsVersion {
+            major: 6,
+            minor: 1,
+            build: 7601,
+            ..WindowsVersion::default()
+        });
+        assert_eq!(os_release.version(), None);
+        assert_eq!(os_release.build_id(), Some("7601"));
+        assert_eq!(os_release.pretty_name(), "Windows 7");
+
+        let product_name = |major, minor, build| {
+            product_name_of(&WindowsVersion {
+                major,
+                minor,
+                build,
+                ..WindowsVersion::default()
+            })
+        };
+        assert_eq!(product_name(10, 0, 22631), "Windows 11");
+        assert_eq!(product_name(10, 0, 19045), "Windows 10");
+        assert_eq!(product_name(6, 3, 9600), "Windows 8.1");
+        assert_eq!(product_name(6, 2, 9200), "Windows 8");
+        assert_eq!(product_name(5, 0, 2195), "Windows NT 5.0");
+    }
+}
@@ -0,0 +1,114 @@
+use crate::OsRelease;
+
+/// Metadata of a product whose identity is used as the operating system identity.
+///
+/// This is intended for appliance builders, whose "operating system" is the product itself.
+/// Use [`cargo_product_metadata!`](crate::cargo_product_metadata) to take the metadata from the Cargo manifest.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::{OsRelease, ProductMetadata};
+///
+/// let metadata = ProductMetadata::new("My Appliance", "1.2.0")
+///     .homepage("https://example.com/")
+///     .repository("https://github.com/example/appliance");
+/// let os_release = OsRelease::from_product_metadata(&metadata);
+///
+/// assert_eq!(os_release.name(), "My Appliance");
+/// assert_eq!(os_release.id(), "my-appliance");
+/// assert_eq!(os_release.version_id(), Some("1.2.0"));
+/// assert_eq!(os_release.pretty_name(), "My Appliance 1.2.0");
+/// assert_eq!(os_release.get_value("BUG_REPORT_URL"), Some("https://github.com/example/appliance"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProductMetadata<'a> {
+    name: &'a str,
+    version: &'a str,
+    homepage: Option<&'a str>,
+    repository: Option<&'a str>,
+}
+
+impl<'a> ProductMetadata<'a> {
+    /// Creates the metadata of a product with the given name and version.
+    pub fn new(name: &'a str, version: &'a str) -> Self {
+        Self {
+            name,
+            version,
+            homepage: None,
+            repository: None,
+        }
+    }
+
+    /// Sets the URL of the homepage of the product.
+    ///
+    /// Empty strings are ignored, so the value of `CARGO_PKG_HOMEPAGE` can be passed as is.
+    pub fn homepage(mut self, homepage: &'a str) -> Self {
+        self.homepage = Some(homepage).filter(|s| !s.is_empty());
+        self
+    }
+
+    /// Sets the URL of the source repository of the product.
+    ///
+    /// Empty strings are ignored, so the value of `CARGO_PKG_REPOSITORY` can be passed as is.
+    pub fn repository(mut self, repository: &'a str) -> Self {
+        self.repository = Some(repository).filter(|s| !s.is_empty());
+        self
+    }
+}
+
+/// Creates a [`ProductMetadata`] from the Cargo manifest of the calling crate.
+///
+/// The name, version, homepage and repository are taken from the `CARGO_PKG_*` environment variables
+/// set by Cargo at compile time.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::{cargo_product_metadata, OsRelease};
+///
+/// let os_release = OsRelease::from_product_metadata(&cargo_product_metadata!());
+/// assert_eq!(os_release.name(), env!("CARGO_PKG_NAME"));
+/// ```
+#[macro_export]
+macro_rules! cargo_product_metadata {
+    () => {
+        $crate::ProductMetadata::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+            .homepage(env!("CARGO_PKG_HOMEPAGE"))
+            .repository(env!("CARGO_PKG_REPOSITORY"))
+    };
+}
+
+/// Methods to construct an `OsRelease` from product metadata.
+impl OsRelease {
+    /// Creates an `OsRelease` identifying the given product.
+    ///
+    /// The following fields are set:
+    ///
+    /// * `NAME=` and `PRETTY_NAME=` from the name (and version)
+    /// * `ID=` and `VERSION_ID=` from the name and version, lower-cased and with characters other than
+    ///   `0–9`, `a–z`, `.`, `_` and `-` replaced with `-`
+    /// * `HOME_URL=` from the homepage, if set
+    /// * `BUG_REPORT_URL=` from the repository, if set
+    pub fn from_product_metadata(metadata: &ProductMetadata<'_>) -> Self {
+        let pretty_name = format!("{} {}", metadata.name, metadata.version);
+        crate::platform::from_optional_fields([
+            ("NAME", Some(metadata.name.to_owned())),
+            ("ID", Some(to_identifier(metadata.name))),
+            ("VERSION_ID", Some(to_identifier(metadata.version))),
+            ("PRETTY_NAME", Some(pretty_name)),
+            ("HOME_URL", metadata.homepage.map(str::to_owned)),
+            ("BUG_REPORT_URL", metadata.repository.map(str::to_owned)),
+        ])
+    }
+}
+
+/// Convert a string to a value suitable for `ID=` or `VERSION_ID=`.
+fn to_identifier(s: &str) -> String {
+    s.chars()
+        .map(|c| match c.to_ascii_lowercase() {
+            c @ ('0'..='9' | 'a'..='z' | '.' | '_' | '-') => c,
+            _ => '-',
+        })
+        .collect()
+}
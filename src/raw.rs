@@ -0,0 +1,187 @@
+use std::{borrow::Cow, io};
+
+use crate::{map::Map, Error, OsRelease, OsReleaseEntry};
+
+/// An os-release file whose values are kept as bytes.
+///
+/// The os-release format does not specify the encoding of values, and some vendor files contain latin-1 bytes
+/// in e.g. `PRETTY_NAME=`, which the shell accepts as is.
+/// Unlike [`OsRelease`], which requires UTF-8, this type keeps such values unchanged, so that no data is lost or rejected.
+/// Lines whose keys are not valid UTF-8 are ignored, as they are not valid os-release fields anyway.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::RawOsRelease;
+///
+/// let raw = RawOsRelease::from_bytes(b"ID=vendor\nPRETTY_NAME=\"Caf\xe9 OS\"\n");
+/// assert_eq!(raw.get_bytes("PRETTY_NAME"), Some(&b"Caf\xe9 OS"[..]));
+/// assert_eq!(raw.non_utf8_keys().collect::<Vec<_>>(), ["PRETTY_NAME"]);
+///
+/// let os_release = raw.to_os_release_lossy();
+/// assert_eq!(os_release.id(), "vendor");
+/// assert_eq!(os_release.pretty_name(), "Caf\u{FFFD} OS");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RawOsRelease {
+    // Use `Map` for reserving insertion order.
+    fields: Map<String, Vec<u8>>,
+}
+
+impl RawOsRelease {
+    /// Parse a byte buffer containing the contents of the os-release file.
+    ///
+    /// For simplicity, this function assumes that the file is well-formed.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let bytes = bytes.strip_prefix(b"\xef\xbb\xbf").unwrap_or(bytes);
+        let fields = bytes
+            .split(|&b| b == b'\n')
+            .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+            .filter_map(parse_line)
+            .collect();
+        Self { fields }
+    }
+
+    /// Parse the os-release file from a reader.
+    ///
+    /// For simplicity, this function assumes that the file is well-formed.
+    pub fn from_reader(mut reader: impl io::Read) -> Result<Self, Error> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes).map_err(Error::read)?;
+        Ok(Self::from_bytes(&bytes))
+    }
+
+    /// Returns the value of the field `key` as bytes.
+    pub fn get_bytes(&self, key: &str) -> Option<&[u8]> {
+        self.fields.get(key).map(Vec::as_slice)
+    }
+
+    /// Returns the value of the field `key` as an `OsStr`.
+    #[cfg(unix)]
+    pub fn get_os_str(&self, key: &str) -> Option<&std::ffi::OsStr> {
+        use std::os::unix::ffi::OsStrExt;
+
+        self.get_bytes(key).map(std::ffi::OsStr::from_bytes)
+    }
+
+    /// Returns the value of the field `key`, if it is valid UTF-8.
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get_bytes(key)
+            .and_then(|v| std::str::from_utf8(v).ok())
+    }
+
+    /// Returns an iterator over the keys of the fields whose values are not valid UTF-8.
+    pub fn non_utf8_keys(&self) -> impl Iterator<Item = &str> {
+        self.fields
+            .iter()
+            .filter(|(_, v)| std::str::from_utf8(v).is_err())
+            .map(|(k, _)| k.as_str())
+    }
+
+    /// Returns an iterator over the keys and values of the fields.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.fields.iter().map(|(k, v)| (k.as_str(), v.as_slice()))
+    }
+
+    /// Converts to an [`OsRelease`], replacing invalid UTF-8 sequences in values with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// Use [`Self::non_utf8_keys()`] to find out which values are affected.
+    pub fn to_os_release_lossy(&self) -> OsRelease {
+        self.fields
+            .iter()
+            .map(|(k, v)| OsReleaseEntry::new(k.as_str(), String::from_utf8_lossy(v)))
+            .collect()
+    }
+
+    /// Converts to an [`OsRelease`], or returns `None` if any value is not valid UTF-8.
+    pub fn to_os_release(&self) -> Option<OsRelease> {
+        self.fields
+            .iter()
+            .map(|(k, v)| {
+                let v = std::str::from_utf8(v).ok()?;
+                Some(OsReleaseEntry::new(k.as_str(), Cow::Borrowed(v)))
+            })
+            .collect()
+    }
+}
+
+impl From<&OsRelease> for RawOsRelease {
+    fn from(os_release: &OsRelease) -> Self {
+        let fields = os_release
+            .entries()
+            .map(|entry| (entry.key().to_owned(), entry.value().as_bytes().to_vec()))
+            .collect();
+        Self { fields }
+    }
+}
+
+/// Parse a line from the os-release file in the same way as `OsReleaseLine`, without requiring UTF-8.
+fn parse_line(line: &[u8]) -> Option<(String, Vec<u8>)> {
+    let line = line.trim_ascii_start();
+    if line.is_empty() || line[0] == b'#' {
+        return None;
+    }
+
+    let eq = line.iter().position(|&b| b == b'=')?;
+    let key = std::str::from_utf8(&line[..eq]).ok()?.to_owned();
+    let value = &line[eq + 1..];
+
+    let value = match value.first() {
+        // For Bourne shell compatibility, don't unescape single-quoted values.
+        Some(&b'\'') => trim_quote(value, b'\'').to_vec(),
+        // Unescape double-quoted values or unquoted values.
+        Some(&b'"') => unescape(trim_quote(value, b'"')),
+        _ => unescape(value),
+    };
+
+    Some((key, value))
+}
+
+fn trim_quote(value: &[u8], quote: u8) -> &[u8] {
+    let value = &value[1..];
+    value.strip_suffix(&[quote]).unwrap_or(value)
+}
+
+fn unescape(value: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(value.len());
+    let mut escaped = false;
+    for &b in value {
+        if !escaped && b == b'\\' {
+            escaped = true;
+            continue;
+        }
+        escaped = false;
+        output.push(b);
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_raw_os_release() {
+        let raw = RawOsRelease::from_bytes(
+            b"# comment\r\nNAME=\"Vendor\\\"s\xff OS\"\r\nID='v\\x'\nVERSION_ID=1\\.0\n\xff=x\n",
+        );
+        assert_eq!(raw.get_bytes("NAME"), Some(&b"Vendor\"s\xff OS"[..]));
+        assert_eq!(raw.get_str("NAME"), None);
+        assert_eq!(raw.get_str("ID"), Some("v\\x"));
+        assert_eq!(raw.get_str("VERSION_ID"), Some("1.0"));
+        assert_eq!(raw.iter().count(), 3);
+        assert_eq!(raw.non_utf8_keys().collect::<Vec<_>>(), ["NAME"]);
+        assert!(raw.to_os_release().is_none());
+        assert_eq!(raw.to_os_release_lossy().name(), "Vendor\"s\u{FFFD} OS");
+
+        // UTF-8 files are parsed in the same way as `OsRelease`
+        let os_release = OsRelease::spec_example();
+        let raw = RawOsRelease::from_bytes(crate::SPEC_EXAMPLE.as_bytes());
+        let converted = raw.to_os_release().unwrap();
+        assert!(os_release.entries().eq(converted.entries()));
+        let raw = RawOsRelease::from(&OsRelease::from_str("ID=fedora").unwrap());
+        assert_eq!(raw.get_str("ID"), Some("fedora"));
+    }
+}
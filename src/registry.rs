@@ -0,0 +1,326 @@
+use crate::keys;
+
+/// The metadata of a field defined by [`os-release(5)`].
+///
+/// This can be used to drive linters and user interfaces from the specification, instead of hardcoding the fields.
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::{FieldCategory, FieldInfo, ValueType};
+///
+/// let info = FieldInfo::get("SUPPORT_END").unwrap();
+/// assert_eq!(info.category(), FieldCategory::Presentation);
+/// assert_eq!(info.value_type(), ValueType::Date);
+/// assert_eq!(info.since(), Some(252));
+/// assert_eq!(
+///     info.doc_url(),
+///     "https://www.freedesktop.org/software/systemd/man/os-release.html#SUPPORT_END=",
+/// );
+///
+/// assert!(FieldInfo::get("REDHAT_BUGZILLA_PRODUCT").is_none());
+/// ```
+///
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldInfo {
+    key: &'static str,
+    category: FieldCategory,
+    value_type: ValueType,
+    since: Option<u32>,
+}
+
+impl FieldInfo {
+    const fn new(
+        key: &'static str,
+        category: FieldCategory,
+        value_type: ValueType,
+        since: Option<u32>,
+    ) -> Self {
+        Self {
+            key,
+            category,
+            value_type,
+            since,
+        }
+    }
+
+    /// Returns the metadata of all fields defined by os-release(5), in the order of the man page.
+    pub const fn all() -> &'static [FieldInfo] {
+        FIELDS
+    }
+
+    /// Returns the metadata of the field, or `None` if the key is not defined by os-release(5).
+    pub fn get(key: &str) -> Option<&'static FieldInfo> {
+        FIELDS.iter().find(|info| info.key == key)
+    }
+
+    /// Returns the key of the field.
+    pub const fn key(&self) -> &'static str {
+        self.key
+    }
+
+    /// Returns the section of os-release(5) the field is described in.
+    pub const fn category(&self) -> FieldCategory {
+        self.category
+    }
+
+    /// Returns the expected type of the value.
+    pub const fn value_type(&self) -> ValueType {
+        self.value_type
+    }
+
+    /// Returns the version of systemd in which the field was added.
+    ///
+    /// Returns `None` for the fields which os-release(5) does not record the version of, most of which predate the others.
+    pub const fn since(&self) -> Option<u32> {
+        self.since
+    }
+
+    /// Returns the URL of the description of the field in os-release(5).
+    pub fn doc_url(&self) -> String {
+        format!(
+            "https://www.freedesktop.org/software/systemd/man/os-release.html#{}=",
+            self.key
+        )
+    }
+}
+
+/// The section of [`os-release(5)`] a field is described in.
+///
+/// [`os-release(5)`]: https://www.freedesktop.org/software/systemd/man/os-release.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FieldCategory {
+    /// General information identifying the operating system.
+    General,
+    /// Information about the version of the operating system.
+    Version,
+    /// Presentation information and links.
+    Presentation,
+    /// Distribution-level defaults and metadata.
+    DistributionDefaults,
+}
+
+/// The expected type of the value of a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ValueType {
+    /// A free-form string, usually suitable for presentation to the user.
+    Text,
+    /// A lower-case string consisting of `0-9`, `a-z`, `.`, `_` and `-`.
+    Identifier,
+    /// A space-separated list of identifiers.
+    IdentifierList,
+    /// A URL in RFC 3986 format.
+    Url,
+    /// A date in `YYYY-MM-DD` format.
+    Date,
+    /// An ANSI escape sequence for the terminal color.
+    AnsiColor,
+}
+
+const FIELDS: &[FieldInfo] = &[
+    FieldInfo::new(keys::NAME, FieldCategory::General, ValueType::Text, None),
+    FieldInfo::new(
+        keys::ID,
+        FieldCategory::General,
+        ValueType::Identifier,
+        None,
+    ),
+    FieldInfo::new(
+        keys::ID_LIKE,
+        FieldCategory::General,
+        ValueType::IdentifierList,
+        None,
+    ),
+    FieldInfo::new(
+        keys::PRETTY_NAME,
+        FieldCategory::General,
+        ValueType::Text,
+        None,
+    ),
+    FieldInfo::new(
+        keys::CPE_NAME,
+        FieldCategory::General,
+        ValueType::Text,
+        None,
+    ),
+    FieldInfo::new(keys::VARIANT, FieldCategory::General, ValueType::Text, None),
+    FieldInfo::new(
+        keys::VARIANT_ID,
+        FieldCategory::General,
+        ValueType::Identifier,
+        None,
+    ),
+    FieldInfo::new(keys::VERSION, FieldCategory::Version, ValueType::Text, None),
+    FieldInfo::new(
+        keys::VERSION_ID,
+        FieldCategory::Version,
+        ValueType::Identifier,
+        None,
+    ),
+    FieldInfo::new(
+        keys::VERSION_CODENAME,
+        FieldCategory::Version,
+        ValueType::Identifier,
+        None,
+    ),
+    FieldInfo::new(
+        keys::BUILD_ID,
+        FieldCategory::Version,
+        ValueType::Text,
+        None,
+    ),
+    FieldInfo::new(
+        keys::IMAGE_ID,
+        FieldCategory::Version,
+        ValueType::Identifier,
+        Some(249),
+    ),
+    FieldInfo::new(
+        keys::IMAGE_VERSION,
+        FieldCategory::Version,
+        ValueType::Identifier,
+        Some(249),
+    ),
+    FieldInfo::new(
+        keys::RELEASE_TYPE,
+        FieldCategory::Version,
+        ValueType::Identifier,
+        None,
+    ),
+    FieldInfo::new(
+        keys::EXPERIMENT,
+        FieldCategory::Version,
+        ValueType::Text,
+        Some(257),
+    ),
+    FieldInfo::new(
+        keys::EXPERIMENT_URL,
+        FieldCategory::Version,
+        ValueType::Url,
+        Some(257),
+    ),
+    FieldInfo::new(
+        keys::HOME_URL,
+        FieldCategory::Presentation,
+        ValueType::Url,
+        None,
+    ),
+    FieldInfo::new(
+        keys::DOCUMENTATION_URL,
+        FieldCategory::Presentation,
+        ValueType::Url,
+        None,
+    ),
+    FieldInfo::new(
+        keys::SUPPORT_URL,
+        FieldCategory::Presentation,
+        ValueType::Url,
+        None,
+    ),
+    FieldInfo::new(
+        keys::BUG_REPORT_URL,
+        FieldCategory::Presentation,
+        ValueType::Url,
+        None,
+    ),
+    FieldInfo::new(
+        keys::PRIVACY_POLICY_URL,
+        FieldCategory::Presentation,
+        ValueType::Url,
+        None,
+    ),
+    FieldInfo::new(
+        keys::SUPPORT_END,
+        FieldCategory::Presentation,
+        ValueType::Date,
+        Some(252),
+    ),
+    FieldInfo::new(
+        keys::LOGO,
+        FieldCategory::Presentation,
+        ValueType::Text,
+        Some(240),
+    ),
+    FieldInfo::new(
+        keys::ANSI_COLOR,
+        FieldCategory::Presentation,
+        ValueType::AnsiColor,
+        None,
+    ),
+    FieldInfo::new(
+        keys::VENDOR_NAME,
+        FieldCategory::Presentation,
+        ValueType::Text,
+        Some(254),
+    ),
+    FieldInfo::new(
+        keys::VENDOR_URL,
+        FieldCategory::Presentation,
+        ValueType::Url,
+        Some(254),
+    ),
+    FieldInfo::new(
+        keys::DEFAULT_HOSTNAME,
+        FieldCategory::DistributionDefaults,
+        ValueType::Text,
+        Some(248),
+    ),
+    FieldInfo::new(
+        keys::ARCHITECTURE,
+        FieldCategory::DistributionDefaults,
+        ValueType::Identifier,
+        Some(252),
+    ),
+    FieldInfo::new(
+        keys::SYSEXT_LEVEL,
+        FieldCategory::DistributionDefaults,
+        ValueType::Identifier,
+        Some(248),
+    ),
+    FieldInfo::new(
+        keys::CONFEXT_LEVEL,
+        FieldCategory::DistributionDefaults,
+        ValueType::Identifier,
+        Some(254),
+    ),
+    FieldInfo::new(
+        keys::SYSEXT_SCOPE,
+        FieldCategory::DistributionDefaults,
+        ValueType::IdentifierList,
+        Some(250),
+    ),
+    FieldInfo::new(
+        keys::CONFEXT_SCOPE,
+        FieldCategory::DistributionDefaults,
+        ValueType::IdentifierList,
+        Some(254),
+    ),
+    FieldInfo::new(
+        keys::PORTABLE_PREFIXES,
+        FieldCategory::DistributionDefaults,
+        ValueType::IdentifierList,
+        Some(250),
+    ),
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fields() {
+        let registered = FieldInfo::all().iter().map(FieldInfo::key);
+        assert!(registered.eq(keys::ALL.iter().copied()));
+        assert!(FieldInfo::all()
+            .iter()
+            .all(|info| keys::is_known_key(info.key())));
+
+        let info = FieldInfo::get(keys::EXPERIMENT_URL).unwrap();
+        assert_eq!(info.category(), FieldCategory::Version);
+        assert_eq!(info.value_type(), ValueType::Url);
+        assert_eq!(info.since(), Some(257));
+    }
+}
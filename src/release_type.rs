@@ -0,0 +1,71 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+/// The type of a release of the operating system, as used by the [`RELEASE_TYPE=`] field.
+///
+/// Unknown release types are preserved as [`ReleaseType::Other`].
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::ReleaseType;
+///
+/// assert_eq!("lts".parse::<ReleaseType>().unwrap(), ReleaseType::Lts);
+/// assert!(ReleaseType::Development.is_prerelease());
+/// assert!(!ReleaseType::Stable.is_prerelease());
+/// ```
+///
+/// [`RELEASE_TYPE=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#RELEASE_TYPE=
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ReleaseType {
+    /// `stable`: a regular release.
+    Stable,
+    /// `lts`: a release with long-term support.
+    Lts,
+    /// `development`: a pre-release, such as an alpha, beta or release candidate.
+    Development,
+    /// `experimental`: an experimental build, not intended for general use.
+    Experimental,
+    /// A release type not known to this crate.
+    Other(String),
+}
+
+impl ReleaseType {
+    /// Returns the string representation of the release type.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Stable => "stable",
+            Self::Lts => "lts",
+            Self::Development => "development",
+            Self::Experimental => "experimental",
+            Self::Other(s) => s,
+        }
+    }
+
+    /// Returns `true` if the release is not intended for production use.
+    ///
+    /// This is `true` for [`ReleaseType::Development`] and [`ReleaseType::Experimental`].
+    pub fn is_prerelease(&self) -> bool {
+        matches!(self, Self::Development | Self::Experimental)
+    }
+}
+
+impl FromStr for ReleaseType {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "stable" => Self::Stable,
+            "lts" => Self::Lts,
+            "development" => Self::Development,
+            "experimental" => Self::Experimental,
+            _ => Self::Other(s.to_owned()),
+        })
+    }
+}
+
+impl fmt::Display for ReleaseType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
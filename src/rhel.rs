@@ -0,0 +1,122 @@
+use std::fmt;
+
+use crate::OsRelease;
+
+/// A version of Red Hat Enterprise Linux, as a compatibility level of RHEL-compatible distributions.
+///
+/// See [`OsRelease::rhel_compatible_version()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RhelVersion {
+    major: u32,
+    minor: Option<u32>,
+}
+
+impl RhelVersion {
+    /// Returns the major version.
+    pub const fn major(&self) -> u32 {
+        self.major
+    }
+
+    /// Returns the minor version.
+    ///
+    /// This is `None` for distributions tracking a major version as a whole, such as CentOS Stream.
+    pub const fn minor(&self) -> Option<u32> {
+        self.minor
+    }
+}
+
+impl fmt::Display for RhelVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.minor {
+            Some(minor) => write!(f, "{}.{}", self.major, minor),
+            None => write!(f, "{}", self.major),
+        }
+    }
+}
+
+/// Distributions whose `ID_LIKE=` contains `rhel` but whose versions are not RHEL versions.
+const INCOMPATIBLE_IDS: &[&str] = &["amzn"];
+
+/// Methods to get information about RHEL-compatible distributions.
+impl OsRelease {
+    /// Returns the version of RHEL the operating system is compatible with.
+    ///
+    /// This is available for RHEL itself, for distributions with `rhel` in `ID_LIKE=` (such as AlmaLinux, Rocky Linux and
+    /// CentOS Stream), and for Oracle Linux. The version is taken from the major and minor components of `VERSION_ID=`.
+    ///
+    /// Returns `None` for other distributions, or if `VERSION_ID=` is not set or not numeric.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// ID="rocky"
+    /// ID_LIKE="rhel centos fedora"
+    /// VERSION_ID="9.3"
+    /// "#).unwrap();
+    /// let version = os_release.rhel_compatible_version().unwrap();
+    /// assert_eq!((version.major(), version.minor()), (9, Some(3)));
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// ID="centos"
+    /// ID_LIKE="rhel fedora"
+    /// VERSION_ID="9"
+    /// "#).unwrap();
+    /// assert_eq!(os_release.rhel_compatible_version().unwrap().to_string(), "9");
+    ///
+    /// let os_release = OsRelease::from_str("ID=fedora\nVERSION_ID=40\n").unwrap();
+    /// assert_eq!(os_release.rhel_compatible_version(), None);
+    /// ```
+    pub fn rhel_compatible_version(&self) -> Option<RhelVersion> {
+        let id = self.id();
+        let compatible = id == "ol" || (self.is_like("rhel") && !INCOMPATIBLE_IDS.contains(&id));
+        if !compatible {
+            return None;
+        }
+
+        let mut components = self.version_id()?.split('.');
+        let major = components.next()?.parse().ok()?;
+        let minor = components.next().and_then(|minor| minor.parse().ok());
+        Some(RhelVersion { major, minor })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_rhel_compatible_version() {
+        let version = |s: &str| {
+            let os_release = OsRelease::from_str(s).unwrap();
+            os_release.rhel_compatible_version().map(|v| v.to_string())
+        };
+        let some = |s: &str| Some(s.to_owned());
+
+        assert_eq!(version("ID=rhel\nVERSION_ID=8.10\n"), some("8.10"));
+        assert_eq!(
+            version("ID=almalinux\nID_LIKE=\"rhel centos fedora\"\nVERSION_ID=9.4\n"),
+            some("9.4")
+        );
+        assert_eq!(
+            version("ID=ol\nID_LIKE=fedora\nVERSION_ID=8.9\n"),
+            some("8.9")
+        );
+        assert_eq!(
+            version("ID=centos\nID_LIKE=\"rhel fedora\"\nVERSION_ID=7\n"),
+            some("7")
+        );
+        assert_eq!(
+            version("ID=amzn\nID_LIKE=\"centos rhel fedora\"\nVERSION_ID=2\n"),
+            None
+        );
+        assert_eq!(version("ID=rhel\n"), None);
+        assert_eq!(version("ID=debian\nVERSION_ID=12\n"), None);
+    }
+}
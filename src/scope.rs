@@ -0,0 +1,93 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+use crate::OsRelease;
+
+/// A scope of system extension or configuration extension images, as used by the [`SYSEXT_SCOPE=`] and [`CONFEXT_SCOPE=`] fields.
+///
+/// Unknown scopes are preserved as [`Scope::Other`].
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::Scope;
+///
+/// assert_eq!("initrd".parse::<Scope>().unwrap(), Scope::Initrd);
+/// assert_eq!(Scope::Portable.to_string(), "portable");
+/// assert_eq!("vm".parse::<Scope>().unwrap(), Scope::Other("vm".to_owned()));
+/// ```
+///
+/// [`SYSEXT_SCOPE=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#SYSEXT_SCOPE=
+/// [`CONFEXT_SCOPE=`]: https://www.freedesktop.org/software/systemd/man/os-release.html#CONFEXT_SCOPE=
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Scope {
+    /// `system`: the image is applicable to the booted system.
+    System,
+    /// `initrd`: the image is applicable to the initrd.
+    Initrd,
+    /// `portable`: the image is applicable to portable services.
+    Portable,
+    /// A scope not known to this crate.
+    Other(String),
+}
+
+impl Scope {
+    /// Returns the string representation of the scope.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::System => "system",
+            Self::Initrd => "initrd",
+            Self::Portable => "portable",
+            Self::Other(s) => s,
+        }
+    }
+}
+
+impl FromStr for Scope {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "system" => Self::System,
+            "initrd" => Self::Initrd,
+            "portable" => Self::Portable,
+            _ => Self::Other(s.to_owned()),
+        })
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Methods to get the typed scopes of extension images.
+impl OsRelease {
+    /// Returns the list of scopes of system extension images, parsed from [`Self::sysext_scope()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::{OsRelease, Scope};
+    ///
+    /// let os_release = OsRelease::from_str(r#"SYSEXT_SCOPE="system portable""#).unwrap();
+    /// assert_eq!(os_release.sysext_scopes(), Some(vec![Scope::System, Scope::Portable]));
+    /// ```
+    pub fn sysext_scopes(&self) -> Option<Vec<Scope>> {
+        self.sysext_scope().map(parse_scopes)
+    }
+
+    /// Returns the list of scopes of configuration extension images, parsed from [`Self::confext_scope()`].
+    pub fn confext_scopes(&self) -> Option<Vec<Scope>> {
+        self.confext_scope().map(parse_scopes)
+    }
+}
+
+fn parse_scopes<'a>(scopes: impl Iterator<Item = &'a str>) -> Vec<Scope> {
+    scopes
+        .map(|scope| scope.parse().unwrap_or_else(|e| match e {}))
+        .collect()
+}
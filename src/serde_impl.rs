@@ -0,0 +1,139 @@
+use std::fmt;
+
+use serde::{
+    de::{MapAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{OsRelease, OsReleaseEntry};
+
+/// Serializes the fields as a map of strings, in the order of the file.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for OsRelease {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for entry in self.entries() {
+            map.serialize_entry(entry.key(), entry.value())?;
+        }
+        map.end()
+    }
+}
+
+/// Deserializes the fields from a map of strings, preserving the order.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for OsRelease {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct OsReleaseVisitor;
+
+        impl<'de> Visitor<'de> for OsReleaseVisitor {
+            type Value = OsRelease;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a map of strings")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = vec![];
+                while let Some((key, value)) = map.next_entry::<String, String>()? {
+                    entries.push(OsReleaseEntry::new(key, value));
+                }
+                Ok(entries.into_iter().collect())
+            }
+        }
+
+        deserializer.deserialize_map(OsReleaseVisitor)
+    }
+}
+
+/// Methods to convert the fields to JSON.
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+impl OsRelease {
+    /// Returns the fields as a flat JSON object of strings.
+    ///
+    /// The order of the keys follows `serde_json::Map`, that is, sorted unless its `preserve_order` feature is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let json = OsRelease::spec_example().to_json_value();
+    /// assert_eq!(json["ID"], "fedora");
+    /// assert_eq!(json["VERSION_ID"], "32");
+    /// assert!(json.get("IMAGE_ID").is_none());
+    /// ```
+    pub fn to_json_value(&self) -> serde_json::Value {
+        self.entries()
+            .map(|entry| (entry.key().to_owned(), entry.value().into()))
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+
+    /// Returns the fields as a flat JSON object of strings, in the order of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str("NAME=Fedora\nID=fedora\n").unwrap();
+    /// assert_eq!(os_release.to_json_string(), r#"{"NAME":"Fedora","ID":"fedora"}"#);
+    /// ```
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string(self).expect("serializing a map of strings never fails")
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod test {
+    use super::*;
+    use crate::OsReleaseLine;
+
+    #[test]
+    fn test_serde() {
+        let os_release = OsRelease::spec_example();
+        let json = serde_json::to_string(&os_release).unwrap();
+        assert!(json.starts_with(r#"{"NAME":"Fedora","VERSION":"32 (Workstation Edition)""#));
+
+        let deserialized = serde_json::from_str::<OsRelease>(&json).unwrap();
+        assert!(deserialized.entries().eq(os_release.entries()));
+        assert_eq!(deserialized.to_json_value(), os_release.to_json_value());
+
+        assert!(serde_json::from_str::<OsRelease>(r#"{"ID":1}"#).is_err());
+        assert!(serde_json::from_str::<OsRelease>(r#"["ID"]"#).is_err());
+    }
+
+    #[test]
+    fn test_serde_lines() {
+        let lines = crate::parse_lines("# comment\nID=fedora\n").collect::<Vec<_>>();
+        let json = lines
+            .iter()
+            .map(|line| serde_json::to_string(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            json,
+            [
+                r##"{"comment":"# comment"}"##,
+                r#"{"entry":{"key":"ID","value":"fedora"}}"#
+            ]
+        );
+
+        let deserialized = json
+            .iter()
+            .map(|line| serde_json::from_str::<OsReleaseLine<'_>>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(deserialized, lines);
+
+        let entry =
+            serde_json::from_str::<OsReleaseEntry<'_>>(r#"{"key":"ID","value":"fedora"}"#).unwrap();
+        assert_eq!(entry, OsReleaseEntry::new("ID", "fedora"));
+        assert!(matches!(
+            entry.into_parts().0,
+            std::borrow::Cow::Borrowed(_)
+        ));
+    }
+}
@@ -0,0 +1,58 @@
+use crate::OsRelease;
+
+/// Methods to render a summary of the operating system for presentation to the user.
+impl OsRelease {
+    /// Returns a one-line summary of the operating system, such as `Fedora Linux 40 (Workstation Edition)`.
+    ///
+    /// The summary is composed of [`Self::name()`], [`Self::version_id()`] and [`Self::variant()`],
+    /// omitting the fields that are not set.
+    ///
+    /// Use [`Self::summary_with()`] to localize the summary.
+    pub fn summary(&self) -> String {
+        self.summary_with(|_, _| None)
+    }
+
+    /// Returns a one-line summary of the operating system, localizing each field with `localize`.
+    ///
+    /// `localize` is called with the key and value of each field used in the summary,
+    /// and returns the localized value, or `None` to use the value as is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str(r#"
+    /// NAME="Fedora Linux"
+    /// VERSION_ID=40
+    /// VARIANT="Workstation Edition"
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(os_release.summary(), "Fedora Linux 40 (Workstation Edition)");
+    ///
+    /// let summary = os_release.summary_with(|key, value| match (key, value) {
+    ///     ("VARIANT", "Workstation Edition") => Some("Édition Workstation".to_owned()),
+    ///     _ => None,
+    /// });
+    /// assert_eq!(summary, "Fedora Linux 40 (Édition Workstation)");
+    /// ```
+    pub fn summary_with<F>(&self, localize: F) -> String
+    where
+        F: Fn(&str, &str) -> Option<String>,
+    {
+        let field =
+            |key: &str, value: &str| localize(key, value).unwrap_or_else(|| value.to_owned());
+
+        let mut summary = field("NAME", self.name());
+        if let Some(version_id) = self.version_id() {
+            summary.push(' ');
+            summary += &field("VERSION_ID", version_id);
+        }
+        if let Some(variant) = self.variant() {
+            summary += &format!(" ({})", field("VARIANT", variant));
+        }
+        summary
+    }
+}
@@ -0,0 +1,166 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, NaiveDate};
+
+use crate::{FieldError, OsRelease};
+
+#[cfg(feature = "eol-db")]
+mod eol_db;
+
+/// Methods to check the end of support of the operating system.
+impl OsRelease {
+    /// Returns `true` if support for this version of the OS has ended on or before `today`.
+    ///
+    /// As with systemd, support is considered to end at the beginning of the day given by [`Self::support_end()`].
+    /// With the `eol-db` feature, [`Self::known_support_end()`] is used if `SUPPORT_END=` is not set.
+    ///
+    /// Returns `Ok(None)` if the end of support is not known.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use chrono::NaiveDate;
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str("ID=fedora\nSUPPORT_END=2024-05-14\n").unwrap();
+    /// let date = |s| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+    ///
+    /// assert_eq!(os_release.is_eol(date("2024-05-13")).unwrap(), Some(false));
+    /// assert_eq!(os_release.is_eol(date("2024-05-14")).unwrap(), Some(true));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "date")))]
+    pub fn is_eol(&self, today: NaiveDate) -> Result<Option<bool>, FieldError> {
+        Ok(self.effective_support_end()?.map(|end| end <= today))
+    }
+
+    /// Returns `true` if support for this version of the OS has ended, according to the current date in UTC.
+    ///
+    /// See [`Self::is_eol()`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "date")))]
+    pub fn is_eol_now(&self) -> Result<Option<bool>, FieldError> {
+        self.is_eol(today())
+    }
+
+    /// Returns the number of days from `today` until support for this version of the OS ends.
+    ///
+    /// The result is `0` on the day support ends, and negative after that.
+    /// The end of support is determined as in [`Self::is_eol()`].
+    ///
+    /// Returns `Ok(None)` if the end of support is not known.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use chrono::NaiveDate;
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str("ID=fedora\nSUPPORT_END=2024-05-14\n").unwrap();
+    /// let date = |s| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+    ///
+    /// assert_eq!(os_release.days_until_eol(date("2024-05-01")).unwrap(), Some(13));
+    /// assert_eq!(os_release.days_until_eol(date("2024-06-01")).unwrap(), Some(-18));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "date")))]
+    pub fn days_until_eol(&self, today: NaiveDate) -> Result<Option<i64>, FieldError> {
+        Ok(self
+            .effective_support_end()?
+            .map(|end| end.signed_duration_since(today).num_days()))
+    }
+
+    /// Returns the number of days until support for this version of the OS ends, according to the current date in UTC.
+    ///
+    /// See [`Self::days_until_eol()`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "date")))]
+    pub fn days_until_eol_now(&self) -> Result<Option<i64>, FieldError> {
+        self.days_until_eol(today())
+    }
+
+    /// Returns the end of support of this version of the OS, as known by the end-of-life database embedded in this crate.
+    ///
+    /// The database is keyed by [`Self::id()`] and [`Self::version_id()`], where a `VERSION_ID=` of `9.4` matches
+    /// the entry for `9`. It covers major distributions that do not set `SUPPORT_END=`, and is only as current
+    /// as the version of this crate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use chrono::NaiveDate;
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str("ID=rhel\nVERSION_ID=7.9\n").unwrap();
+    /// assert_eq!(os_release.known_support_end(), NaiveDate::from_ymd_opt(2024, 6, 30));
+    /// assert_eq!(os_release.is_eol(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()).unwrap(), Some(true));
+    /// ```
+    #[cfg(feature = "eol-db")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "eol-db")))]
+    pub fn known_support_end(&self) -> Option<NaiveDate> {
+        let id = self.id();
+        let version_id = self.version_id()?;
+        eol_db::EOL_DATES
+            .iter()
+            .find(|(i, v, _)| {
+                *i == id
+                    && version_id
+                        .strip_prefix(v)
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+            })
+            .and_then(|&(_, _, (y, m, d))| NaiveDate::from_ymd_opt(y, m, d))
+    }
+
+    fn effective_support_end(&self) -> Result<Option<NaiveDate>, FieldError> {
+        let end = self.support_end()?;
+        #[cfg(feature = "eol-db")]
+        let end = end.or_else(|| self.known_support_end());
+        Ok(end)
+    }
+}
+
+/// Returns the current date in UTC.
+fn today() -> NaiveDate {
+    let secs = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_secs() as i64,
+        Err(e) => -(e.duration().as_secs() as i64),
+    };
+    DateTime::from_timestamp(secs, 0)
+        .unwrap_or_default()
+        .date_naive()
+}
+
+#[cfg(all(test, feature = "eol-db"))]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_known_support_end() {
+        for (id, version, (y, m, d)) in eol_db::EOL_DATES {
+            assert!(
+                NaiveDate::from_ymd_opt(*y, *m, *d).is_some(),
+                "{id} {version}"
+            );
+        }
+
+        let end = |s: &str| OsRelease::from_str(s).unwrap().known_support_end();
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d);
+        assert_eq!(end("ID=debian\nVERSION_ID=11\n"), date(2024, 8, 14));
+        assert_eq!(end("ID=alpine\nVERSION_ID=3.19.1\n"), date(2025, 11, 1));
+        assert_eq!(end("ID=alpine\nVERSION_ID=3.1\n"), None);
+        assert_eq!(end("ID=rhel\nVERSION_ID=90\n"), None);
+        assert_eq!(end("ID=debian\n"), None);
+
+        // SUPPORT_END= takes precedence
+        let os_release =
+            OsRelease::from_str("ID=debian\nVERSION_ID=11\nSUPPORT_END=2030-01-01\n").unwrap();
+        assert_eq!(
+            os_release.is_eol(date(2025, 1, 1).unwrap()).unwrap(),
+            Some(false)
+        );
+    }
+}
@@ -0,0 +1,42 @@
+//! End-of-life dates of distributions not setting `SUPPORT_END=`.
+//!
+//! The dates are the end of (security) support as published on <https://endoflife.date>.
+
+/// `(ID, VERSION_ID prefix, (year, month, day))`
+pub(super) const EOL_DATES: &[(&str, &str, (i32, u32, u32))] = &[
+    ("almalinux", "8", (2029, 3, 1)),
+    ("almalinux", "9", (2032, 5, 31)),
+    ("alpine", "3.15", (2023, 11, 1)),
+    ("alpine", "3.16", (2024, 5, 23)),
+    ("alpine", "3.17", (2024, 11, 22)),
+    ("alpine", "3.18", (2025, 5, 9)),
+    ("alpine", "3.19", (2025, 11, 1)),
+    ("alpine", "3.20", (2026, 4, 1)),
+    ("alpine", "3.21", (2026, 11, 1)),
+    ("centos", "7", (2024, 6, 30)),
+    ("centos", "8", (2021, 12, 31)),
+    ("debian", "9", (2020, 7, 6)),
+    ("debian", "10", (2022, 9, 10)),
+    ("debian", "11", (2024, 8, 14)),
+    ("debian", "12", (2026, 6, 10)),
+    ("fedora", "36", (2023, 5, 16)),
+    ("fedora", "37", (2023, 12, 5)),
+    ("fedora", "38", (2024, 5, 21)),
+    ("fedora", "39", (2024, 11, 26)),
+    ("fedora", "40", (2025, 5, 13)),
+    ("opensuse-leap", "15.3", (2022, 12, 31)),
+    ("opensuse-leap", "15.4", (2023, 12, 7)),
+    ("opensuse-leap", "15.5", (2024, 12, 31)),
+    ("rhel", "7", (2024, 6, 30)),
+    ("rhel", "8", (2029, 5, 31)),
+    ("rhel", "9", (2032, 5, 31)),
+    ("rocky", "8", (2029, 5, 31)),
+    ("rocky", "9", (2032, 5, 31)),
+    ("ubuntu", "16.04", (2021, 4, 30)),
+    ("ubuntu", "18.04", (2023, 5, 31)),
+    ("ubuntu", "20.04", (2025, 5, 29)),
+    ("ubuntu", "22.04", (2027, 6, 1)),
+    ("ubuntu", "23.10", (2024, 7, 11)),
+    ("ubuntu", "24.04", (2029, 5, 31)),
+    ("ubuntu", "24.10", (2025, 7, 10)),
+];
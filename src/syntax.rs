@@ -0,0 +1,247 @@
+//! The syntax of os-release lines, shared with the `include_os_release!` macro of the derive crate.
+//!
+//! The derive crate includes this file with `#[path]`, so it must depend only on `std` and `thiserror`.
+
+use std::borrow::Cow;
+
+/// The kind of error found in a line of the os-release file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, thiserror::Error)]
+#[non_exhaustive]
+pub enum LineErrorKind {
+    /// The line is neither empty, a comment nor a variable assignment.
+    #[error("missing `=`")]
+    MissingAssignment,
+    /// The variable name is not a valid shell variable name.
+    #[error("invalid variable name {0:?}")]
+    InvalidKey(String),
+    /// A quoted value is not terminated.
+    #[error("unterminated quoted value")]
+    UnterminatedQuote,
+    /// An unquoted value contains a character that must be quoted or escaped.
+    #[error("unquoted special character {0:?}")]
+    UnquotedSpecialCharacter(char),
+    /// A double-quoted value contains a character that must be escaped.
+    #[error("unescaped special character {0:?}")]
+    UnescapedSpecialCharacter(char),
+    /// A quoted value is followed by other characters.
+    #[error("unexpected characters after the closing quote")]
+    TrailingCharacters,
+    /// The value is followed by a comment, which is part of the value unless trailing comments are stripped.
+    ///
+    /// See [`ParseOptions::strip_trailing_comments()`](crate::ParseOptions::strip_trailing_comments).
+    #[error("trailing comment after the value")]
+    TrailingComment,
+}
+
+/// Returns the byte offset of the comment at the end of an assignment line, including the blanks before it.
+///
+/// As in the shell, a comment starts with a `#` at the beginning of a word,
+/// so it must be separated from the value by blanks outside quotes.
+pub(crate) fn trailing_comment_start(line: &str) -> Option<usize> {
+    split_assignment(line)?;
+    let value_start = line.find('=')? + 1;
+    let value = &line[value_start..];
+
+    let mut quote = None;
+    let mut chars = value.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (None, ' ' | '\t') => {
+                let rest = value[i..].trim_start_matches([' ', '\t']);
+                return rest.starts_with('#').then_some(value_start + i);
+            }
+            // Single-quoted values have no escape sequences.
+            (None | Some('"'), '\\') => {
+                chars.next();
+            }
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Splits a line into the key and the raw value at the first `=`.
+///
+/// Returns `None` if the line is empty, a comment or not a variable assignment.
+pub(crate) const fn split_assignment(line: &str) -> Option<(&str, &str)> {
+    let mut bytes = line.as_bytes();
+    // Files edited on Windows may have carriage returns left over from the line terminators.
+    while let Some((b'\r', init)) = bytes.split_last() {
+        bytes = init;
+    }
+    // The shell ignores leading blanks.
+    while let Some((b' ' | b'\t', tail)) = bytes.split_first() {
+        bytes = tail;
+    }
+    if bytes.is_empty() || bytes[0] == b'#' {
+        return None;
+    }
+
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'=' {
+            let (key, rest) = bytes.split_at(i);
+            let (_, value) = rest.split_at(1);
+            return Some((from_ascii_boundary(key), from_ascii_boundary(value)));
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Converts a part of a string split at ASCII characters back to a string.
+pub(crate) const fn from_ascii_boundary(bytes: &[u8]) -> &str {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(_) => panic!("a string split at an ASCII character is valid UTF-8"),
+    }
+}
+
+/// Returns `true` if the key is a valid shell variable name.
+pub(crate) const fn is_valid_key(key: &str) -> bool {
+    let bytes = key.as_bytes();
+    if bytes.is_empty() || !(bytes[0].is_ascii_alphabetic() || bytes[0] == b'_') {
+        return false;
+    }
+    let mut i = 1;
+    while i < bytes.len() {
+        if !(bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Check that a line from the os-release file is well-formed.
+pub(crate) fn validate_line(line: &str) -> Result<(), LineErrorKind> {
+    // As in `split_assignment()`, only the blanks ignored by the shell are trimmed.
+    let line = line.trim_start_matches([' ', '\t']).trim_end_matches('\r');
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(());
+    }
+
+    let (key, value) = line
+        .split_once('=')
+        .ok_or(LineErrorKind::MissingAssignment)?;
+
+    if !is_valid_key(key) {
+        return Err(LineErrorKind::InvalidKey(key.to_owned()));
+    }
+    if trailing_comment_start(line).is_some() {
+        return Err(LineErrorKind::TrailingComment);
+    }
+
+    let mut chars = value.chars();
+    match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => {
+            chars.next();
+            loop {
+                match chars.next() {
+                    None => return Err(LineErrorKind::UnterminatedQuote),
+                    Some(c) if c == quote => break,
+                    // Single-quoted values have no escape sequences.
+                    Some('\\') if quote == '"' => {
+                        chars.next().ok_or(LineErrorKind::UnterminatedQuote)?;
+                    }
+                    Some(c @ ('$' | '`')) if quote == '"' => {
+                        return Err(LineErrorKind::UnescapedSpecialCharacter(c))
+                    }
+                    Some(_) => {}
+                }
+            }
+            if !chars.as_str().trim_end_matches([' ', '\t']).is_empty() {
+                return Err(LineErrorKind::TrailingCharacters);
+            }
+        }
+        _ => {
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => {
+                        chars.next();
+                    }
+                    // Pathname expansion and word splitting are not performed on assignments,
+                    // so only characters that end the word or start an expansion are special.
+                    c if c.is_whitespace() || "\"'`$;&|<>()".contains(c) => {
+                        return Err(LineErrorKind::UnquotedSpecialCharacter(c))
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Trim the outermost quotes from a string.
+///
+/// Returns the trimmed string and the quote character, if any.
+///
+/// For simplicity, this function assumes that the file is well-formed.
+pub(crate) const fn trim_quote(value: &str) -> (&str, Option<char>) {
+    let Some((&quote @ (b'"' | b'\''), rest)) = value.as_bytes().split_first() else {
+        return (value, None);
+    };
+    let rest = match rest.split_last() {
+        Some((&last, init)) if last == quote => init,
+        _ => rest,
+    };
+    (from_ascii_boundary(rest), Some(quote as char))
+}
+
+/// Unescape a string.
+///
+/// This function assumes that the os-release file is well-formed.
+///
+/// For simplicity, only simple unescaping is performed.
+pub(crate) fn unescape(value: &str) -> Cow<'_, str> {
+    if !value.contains('\\') {
+        return value.into();
+    }
+
+    let mut output = String::with_capacity(value.len());
+    let mut escaped = false;
+    for c in value.chars() {
+        if escaped {
+            escaped = false;
+            output.push(c);
+            continue;
+        }
+        if c == '\\' {
+            escaped = true;
+            continue;
+        }
+        output.push(c);
+    }
+    output.into()
+}
+
+/// Splits a line into the key and the value, with the outermost quotes trimmed and the value unescaped.
+///
+/// Returns `None` if the line is empty, a comment or not a variable assignment.
+/// The key and value borrow from `line` unless the value has to be unescaped.
+///
+/// For simplicity, this function assumes that the file is well-formed.
+pub(crate) fn parse_assignment(line: &str) -> Option<(&str, Cow<'_, str>)> {
+    let (key, value) = split_assignment(line)?;
+
+    // Blanks after the closing quote are not part of the value.
+    let trimmed = value.trim_end_matches([' ', '\t']);
+    let quoted = match trim_quote(trimmed) {
+        (_, Some(quote)) => trimmed.len() >= 2 && trimmed.ends_with(quote),
+        (_, None) => false,
+    };
+    let value = if quoted { trimmed } else { value };
+
+    let value = match trim_quote(value) {
+        // For Bourne shell compatibility, don't unescape single-quoted values.
+        (value, Some('\'')) => Cow::Borrowed(value),
+        // Unescape double-quoted values or unquoted values.
+        (value, _) => unescape(value),
+    };
+
+    Some((key, value))
+}
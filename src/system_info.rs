@@ -0,0 +1,135 @@
+use crate::OsRelease;
+
+/// The os-release file combined with the kernel information from [`uname(2)`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # #[cfg(unix)]
+/// # fn main() -> Result<(), etc_os_release::Error> {
+/// use etc_os_release::SystemInfo;
+///
+/// let info = SystemInfo::current()?;
+/// println!(
+///     "{} ({} {} {})",
+///     info.os_release().pretty_name(),
+///     info.kernel_name(),
+///     info.kernel_release(),
+///     info.machine(),
+/// );
+/// # Ok(())
+/// # }
+/// # #[cfg(not(unix))]
+/// # fn main() {}
+/// ```
+///
+/// [`uname(2)`]: https://man7.org/linux/man-pages/man2/uname.2.html
+#[derive(Debug, Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "uname")))]
+pub struct SystemInfo {
+    os_release: OsRelease,
+    kernel_name: String,
+    kernel_release: String,
+    kernel_version: String,
+    machine: String,
+}
+
+impl SystemInfo {
+    /// Creates a `SystemInfo` from an `OsRelease` and the `sysname`, `release`, `version` and `machine` fields of `uname(2)`.
+    pub fn new(
+        os_release: OsRelease,
+        kernel_name: impl Into<String>,
+        kernel_release: impl Into<String>,
+        kernel_version: impl Into<String>,
+        machine: impl Into<String>,
+    ) -> Self {
+        Self {
+            os_release,
+            kernel_name: kernel_name.into(),
+            kernel_release: kernel_release.into(),
+            kernel_version: kernel_version.into(),
+            machine: machine.into(),
+        }
+    }
+
+    /// Returns the information of the running system.
+    ///
+    /// The os-release file is opened as in [`OsRelease::open()`].
+    /// Returns [`Error::Uname`](crate::Error::Uname) if `uname(2)` fails.
+    #[cfg(unix)]
+    pub fn current() -> Result<Self, crate::Error> {
+        let os_release = OsRelease::open()?;
+        let uts = crate::uname::uname().map_err(|err| crate::Error::Uname { err })?;
+        Ok(Self::new(
+            os_release,
+            uts.sysname,
+            uts.release,
+            uts.version,
+            uts.machine,
+        ))
+    }
+
+    /// Returns the os-release file.
+    pub fn os_release(&self) -> &OsRelease {
+        &self.os_release
+    }
+
+    /// Returns the os-release file, consuming `self`.
+    pub fn into_os_release(self) -> OsRelease {
+        self.os_release
+    }
+
+    /// Returns the name of the kernel, e.g. `Linux`.
+    ///
+    /// This is the same as `uname -s`.
+    pub fn kernel_name(&self) -> &str {
+        &self.kernel_name
+    }
+
+    /// Returns the release of the kernel, e.g. `6.8.0-45-generic`.
+    ///
+    /// This is the same as `uname -r`.
+    pub fn kernel_release(&self) -> &str {
+        &self.kernel_release
+    }
+
+    /// Returns the version of the kernel, e.g. `#45-Ubuntu SMP PREEMPT_DYNAMIC Fri Aug 30 12:02:04 UTC 2024`.
+    ///
+    /// This is the same as `uname -v`.
+    pub fn kernel_version(&self) -> &str {
+        &self.kernel_version
+    }
+
+    /// Returns the hardware name of the machine, e.g. `x86_64`.
+    ///
+    /// This is the same as `uname -m`.
+    /// Note that this may differ from [`OsRelease::architecture()`] in naming, e.g. `aarch64` vs `arm64`.
+    pub fn machine(&self) -> &str {
+        &self.machine
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_current() {
+        let Ok(info) = SystemInfo::current() else {
+            // The running system has no os-release file.
+            return;
+        };
+        let uts = crate::uname::uname().unwrap();
+        assert_eq!(info.kernel_name(), uts.sysname);
+        assert_eq!(info.kernel_release(), uts.release);
+        assert_eq!(info.machine(), uts.machine);
+    }
+
+    #[test]
+    fn test_new() {
+        let info = SystemInfo::new(OsRelease::spec_example(), "Linux", "6.8.0", "", "x86_64");
+        assert_eq!(info.os_release().id(), "fedora");
+        assert_eq!(info.kernel_name(), "Linux");
+    }
+}
@@ -0,0 +1,372 @@
+use std::{
+    collections::HashMap,
+    io::{self, Read},
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{Error, OsRelease, ParseOptions};
+
+/// Paths of the os-release file in an archive, in order of precedence.
+const OS_RELEASE_PATHS: [&str; 2] = ["etc/os-release", "usr/lib/os-release"];
+
+/// Maximum size of the metadata (long names and PAX headers) of an archive member.
+const MAX_METADATA_SIZE: u64 = 1024 * 1024;
+
+const BLOCK_SIZE: usize = 512;
+
+/// Methods to parse the os-release file in a tar archive.
+impl OsRelease {
+    /// Parse the os-release file contained in a tar archive, such as a layer of an OCI container image.
+    ///
+    /// The archive is read as a stream and only the os-release files are kept in memory,
+    /// so that it is not necessary to unpack the whole archive.
+    /// `etc/os-release` takes precedence over `usr/lib/os-release`,
+    /// and symbolic links and hard links between them are followed.
+    ///
+    /// The archive must be uncompressed: compressed layers should be decompressed by the caller.
+    /// ustar, GNU and PAX archives are supported.
+    ///
+    /// Returns [`Error::NoOsRelease`] if the archive contains no os-release file,
+    /// and [`Error::Read`] if the archive cannot be read or is malformed.
+    #[cfg_attr(docsrs, doc(cfg(feature = "tar")))]
+    pub fn from_tar(reader: impl io::Read) -> Result<Self, Error> {
+        Self::from_tar_with(reader, &ParseOptions::new())
+    }
+
+    /// Parse the os-release file contained in a tar archive with the given options.
+    ///
+    /// The archive is read in the same way as [`Self::from_tar()`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "tar")))]
+    pub fn from_tar_with(reader: impl io::Read, options: &ParseOptions) -> Result<Self, Error> {
+        let members = read_members(reader, options.get_max_size()).map_err(Error::read)?;
+        let content = OS_RELEASE_PATHS
+            .into_iter()
+            .find_map(|path| members.resolve(Path::new(path)))
+            .ok_or(Error::NoOsRelease)?;
+        Self::from_reader_with(content, options)
+    }
+}
+
+/// The os-release files and links found in an archive.
+#[derive(Default)]
+struct Members {
+    files: HashMap<PathBuf, Vec<u8>>,
+    links: HashMap<PathBuf, PathBuf>,
+}
+
+impl Members {
+    /// Returns the content of the file at `path`, following links.
+    fn resolve(&self, path: &Path) -> Option<&[u8]> {
+        const MAX_LINKS: usize = 8;
+
+        let mut path = path.to_owned();
+        for _ in 0..MAX_LINKS {
+            if let Some(content) = self.files.get(&path) {
+                return Some(content);
+            }
+            path = self.links.get(&path)?.clone();
+        }
+        None
+    }
+}
+
+fn read_members(mut reader: impl io::Read, max_size: u64) -> io::Result<Members> {
+    let mut members = Members::default();
+    let mut long_name = None;
+    let mut long_link = None;
+
+    let mut header = [0; BLOCK_SIZE];
+    loop {
+        if !read_block(&mut reader, &mut header)? {
+            break;
+        }
+        // The archive ends with zero-filled blocks.
+        if header.iter().all(|&b| b == 0) {
+            break;
+        }
+        if !checksum_matches(&header) {
+            return Err(invalid_data("invalid tar header checksum"));
+        }
+
+        let size = parse_number(&header[124..136])?;
+        let kind = header[156];
+        let mut data = (&mut reader).take(padded(size));
+
+        match kind {
+            // GNU long name and long link name
+            b'L' | b'K' => {
+                let value = read_metadata(&mut data, size)?;
+                let value = PathBuf::from(String::from_utf8_lossy(trim_nul(&value)).into_owned());
+                if kind == b'L' {
+                    long_name = Some(value);
+                } else {
+                    long_link = Some(value);
+                }
+            }
+            // PAX extended header
+            b'x' => {
+                let records = read_metadata(&mut data, size)?;
+                for (key, value) in parse_pax(&records) {
+                    match key {
+                        "path" => long_name = Some(PathBuf::from(value)),
+                        "linkpath" => long_link = Some(PathBuf::from(value)),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {
+                let name = long_name.take().unwrap_or_else(|| header_path(&header));
+                let link = long_link
+                    .take()
+                    .unwrap_or_else(|| PathBuf::from(header_str(&header[157..257])));
+                let path = normalize(Path::new("/"), &name);
+                let wanted = path.as_ref().filter(|path| {
+                    OS_RELEASE_PATHS
+                        .iter()
+                        .any(|p| path.as_path() == Path::new(p))
+                });
+                match (kind, wanted) {
+                    (b'0' | b'\0' | b'7', Some(path)) => {
+                        // Larger files are rejected by the parser, so the rest need not be buffered.
+                        let mut content = vec![];
+                        (&mut data)
+                            .take(size.min(max_size.saturating_add(1)))
+                            .read_to_end(&mut content)?;
+                        members.links.remove(path);
+                        members.files.insert(path.clone(), content);
+                    }
+                    // hard link, relative to the root of the archive
+                    (b'1', Some(path)) => {
+                        if let Some(target) = normalize(Path::new("/"), &link) {
+                            members.files.remove(path);
+                            members.links.insert(path.clone(), target);
+                        }
+                    }
+                    // symbolic link, relative to the directory of the link
+                    (b'2', Some(path)) => {
+                        let dir = Path::new("/").join(path.parent().unwrap_or(Path::new("")));
+                        if let Some(target) = normalize(&dir, &link) {
+                            members.files.remove(path);
+                            members.links.insert(path.clone(), target);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        io::copy(&mut data, &mut io::sink())?;
+        if data.limit() > 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+    }
+
+    Ok(members)
+}
+
+/// Read a block, returning `false` at the end of the stream.
+fn read_block(reader: &mut impl io::Read, block: &mut [u8; BLOCK_SIZE]) -> io::Result<bool> {
+    let mut read = 0;
+    while read < block.len() {
+        match reader.read(&mut block[read..]) {
+            Ok(0) if read == 0 => return Ok(false),
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => read += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(true)
+}
+
+fn read_metadata(reader: &mut impl io::Read, size: u64) -> io::Result<Vec<u8>> {
+    if size > MAX_METADATA_SIZE {
+        return Err(invalid_data("tar metadata too large"));
+    }
+    let mut buf = vec![];
+    reader.take(size).read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn padded(size: u64) -> u64 {
+    size.div_ceil(BLOCK_SIZE as u64) * BLOCK_SIZE as u64
+}
+
+fn checksum_matches(header: &[u8; BLOCK_SIZE]) -> bool {
+    let Ok(expected) = parse_number(&header[148..156]) else {
+        return false;
+    };
+    let actual: u64 = header
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| if (148..156).contains(&i) { b' ' } else { b })
+        .map(u64::from)
+        .sum();
+    actual == expected
+}
+
+/// Parse a numeric header field, in octal or GNU base-256 encoding.
+fn parse_number(field: &[u8]) -> io::Result<u64> {
+    if field.first().is_some_and(|&b| b & 0x80 != 0) {
+        return field[1..]
+            .iter()
+            .try_fold(u64::from(field[0] & 0x7f), |acc, &b| {
+                acc.checked_mul(256)?.checked_add(u64::from(b))
+            })
+            .ok_or_else(|| invalid_data("tar header number too large"));
+    }
+    let s = header_str(field);
+    let s = s.trim_matches(|c: char| c == ' ' || c == '\0');
+    if s.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(s, 8).map_err(|_| invalid_data("invalid tar header number"))
+}
+
+fn trim_nul(field: &[u8]) -> &[u8] {
+    let len = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    &field[..len]
+}
+
+fn header_str(field: &[u8]) -> String {
+    String::from_utf8_lossy(trim_nul(field)).into_owned()
+}
+
+fn header_path(header: &[u8; BLOCK_SIZE]) -> PathBuf {
+    let name = header_str(&header[0..100]);
+    let is_ustar = &header[257..262] == b"ustar";
+    match header_str(&header[345..500]) {
+        prefix if is_ustar && !prefix.is_empty() => Path::new(&prefix).join(name),
+        _ => PathBuf::from(name),
+    }
+}
+
+/// Parse PAX extended header records of the form `<length> <key>=<value>\n`.
+fn parse_pax(mut records: &[u8]) -> impl Iterator<Item = (&str, &str)> {
+    std::iter::from_fn(move || loop {
+        let space = records.iter().position(|&b| b == b' ')?;
+        let len: usize = std::str::from_utf8(&records[..space]).ok()?.parse().ok()?;
+        if len <= space || len > records.len() {
+            return None;
+        }
+        let (record, rest) = records.split_at(len);
+        records = rest;
+        let record = std::str::from_utf8(&record[space + 1..]).ok();
+        if let Some((key, value)) = record.and_then(|r| r.strip_suffix('\n')?.split_once('=')) {
+            return Some((key, value));
+        }
+    })
+}
+
+/// Resolve `path` relative to `dir` lexically, returning a path relative to the root of the archive.
+///
+/// Returns `None` if the path refers to the root itself.
+fn normalize(dir: &Path, path: &Path) -> Option<PathBuf> {
+    let mut components = vec![];
+    let path = if path.has_root() {
+        path.to_owned()
+    } else {
+        dir.join(path)
+    };
+    for component in path.components() {
+        match component {
+            Component::Normal(c) => components.push(c),
+            Component::ParentDir => {
+                components.pop();
+            }
+            Component::RootDir | Component::CurDir | Component::Prefix(_) => {}
+        }
+    }
+    (!components.is_empty()).then(|| components.iter().collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn header(name: &str, kind: u8, size: usize, link: &str) -> [u8; BLOCK_SIZE] {
+        let mut header = [0; BLOCK_SIZE];
+        header[..name.len()].copy_from_slice(name.as_bytes());
+        header[100..107].copy_from_slice(b"0000644");
+        header[124..135].copy_from_slice(format!("{size:011o}").as_bytes());
+        header[156] = kind;
+        header[157..157 + link.len()].copy_from_slice(link.as_bytes());
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[263..265].copy_from_slice(b"00");
+        header[148..156].copy_from_slice(b"        ");
+        let sum: u32 = header.iter().map(|&b| u32::from(b)).sum();
+        header[148..155].copy_from_slice(format!("{sum:06o}\0").as_bytes());
+        header
+    }
+
+    fn archive(members: &[(&str, u8, &str)]) -> Vec<u8> {
+        let mut archive = vec![];
+        for (name, kind, data) in members {
+            if *kind == b'2' || *kind == b'1' {
+                archive.extend(header(name, *kind, 0, data));
+                continue;
+            }
+            archive.extend(header(name, *kind, data.len(), ""));
+            archive.extend(data.as_bytes());
+            archive.resize(padded(archive.len() as u64) as usize, 0);
+        }
+        archive.extend([0; BLOCK_SIZE * 2]);
+        archive
+    }
+
+    #[test]
+    fn test_from_tar() {
+        let id =
+            |archive: Vec<u8>| OsRelease::from_tar(archive.as_slice()).map(|os| os.id().to_owned());
+
+        // symbolic link to usr/lib/os-release
+        let tar = archive(&[
+            ("./", b'5', ""),
+            ("./etc/os-release", b'2', "../usr/lib/os-release"),
+            ("./usr/lib/os-release", b'0', "ID=debian\n"),
+        ]);
+        assert_eq!(id(tar).unwrap(), "debian");
+
+        // absolute symbolic link and a large file before
+        let large = "x".repeat(5000);
+        let tar = archive(&[
+            ("etc/os-release", b'2', "/usr/lib/os-release"),
+            ("usr/bin/large", b'0', &large),
+            ("usr/lib/os-release", b'0', "ID=fedora\n"),
+        ]);
+        assert_eq!(id(tar).unwrap(), "fedora");
+
+        // etc/os-release takes precedence
+        let tar = archive(&[
+            ("usr/lib/os-release", b'0', "ID=fedora\n"),
+            ("etc/os-release", b'0', "ID=custom\n"),
+        ]);
+        assert_eq!(id(tar).unwrap(), "custom");
+
+        // hard link
+        let tar = archive(&[
+            ("usr/lib/os-release", b'0', "ID=alpine\n"),
+            ("etc/os-release", b'1', "usr/lib/os-release"),
+        ]);
+        assert_eq!(id(tar).unwrap(), "alpine");
+
+        // PAX path
+        let record = "27 path=usr/lib/os-release\n";
+        let tar = archive(&[
+            ("PaxHeaders/x", b'x', record),
+            ("usr/lib/placeholder", b'0', "ID=arch\n"),
+        ]);
+        assert_eq!(id(tar).unwrap(), "arch");
+
+        let tar = archive(&[("etc/hostname", b'0', "example\n")]);
+        assert!(matches!(id(tar), Err(Error::NoOsRelease)));
+
+        let mut tar = archive(&[("usr/lib/os-release", b'0', "ID=arch\n")]);
+        tar[0] ^= 1;
+        assert!(matches!(id(tar), Err(Error::Read { .. })));
+    }
+}
@@ -0,0 +1,48 @@
+// Only the BSD provider uses this without the `uname` feature, and only on BSD targets.
+#![cfg_attr(not(feature = "uname"), allow(dead_code))]
+
+use std::{ffi::CStr, io, mem::MaybeUninit};
+
+/// The fields of `struct utsname` used by this crate.
+#[derive(Debug)]
+pub(crate) struct Utsname {
+    pub(crate) sysname: String,
+    pub(crate) release: String,
+    pub(crate) version: String,
+    pub(crate) machine: String,
+}
+
+/// Returns the system information from `uname(2)`.
+pub(crate) fn uname() -> io::Result<Utsname> {
+    let mut uts = MaybeUninit::<libc::utsname>::uninit();
+    // SAFETY: `uts` is valid for writes.
+    if unsafe { libc::uname(uts.as_mut_ptr()) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: `uname()` succeeded, so `uts` is initialized with NUL-terminated strings.
+    let uts = unsafe { uts.assume_init() };
+    let field = |f: &[libc::c_char]| {
+        // SAFETY: the field is NUL-terminated.
+        let s = unsafe { CStr::from_ptr(f.as_ptr()) };
+        s.to_string_lossy().into_owned()
+    };
+    Ok(Utsname {
+        sysname: field(&uts.sysname),
+        release: field(&uts.release),
+        version: field(&uts.version),
+        machine: field(&uts.machine),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_uname() {
+        let uts = uname().unwrap();
+        assert!(!uts.sysname.is_empty());
+        assert!(!uts.release.is_empty());
+        assert!(!uts.machine.is_empty());
+    }
+}
@@ -0,0 +1,108 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    os::unix::net::UnixStream,
+    path::Path,
+};
+
+use serde_json::Value;
+
+use crate::{Error, HostnameProperties, OsRelease};
+
+/// The socket of the `io.systemd.Hostname` varlink service.
+const HOSTNAME_SOCKET: &str = "/run/systemd/io.systemd.Hostname";
+
+/// Methods to construct an `OsRelease` from the varlink interface of systemd-hostnamed.
+impl OsRelease {
+    /// Query the operating system identity from the `io.systemd.Hostname` varlink service of systemd-hostnamed.
+    ///
+    /// This calls `io.systemd.Hostname.Describe` on `/run/systemd/io.systemd.Hostname`, which is lighter-weight than D-Bus
+    /// and available on minimal systems and early in boot.
+    /// If the reply contains the full os-release data (`OperatingSystemReleaseData`, systemd 257 and later), it is parsed.
+    /// Otherwise, the `OperatingSystem*` fields are converted as in [`Self::from_hostname_properties()`].
+    ///
+    /// Returns [`Error::Open`] if the service is not available, and [`Error::Read`] if the call fails.
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "varlink"))))]
+    pub fn from_varlink() -> Result<Self, Error> {
+        let path = Path::new(HOSTNAME_SOCKET);
+        let stream = UnixStream::connect(path).map_err(|err| Error::Open {
+            path: path.to_owned(),
+            err,
+        })?;
+        let reply = describe(stream).map_err(|err| Error::read(err).with_path(path))?;
+        Ok(from_describe_reply(&reply))
+    }
+}
+
+/// Call `io.systemd.Hostname.Describe` and return the parameters of the reply.
+fn describe(mut stream: UnixStream) -> io::Result<Value> {
+    let call = serde_json::json!({
+        "method": "io.systemd.Hostname.Describe",
+        "parameters": {},
+    });
+    let mut message = serde_json::to_vec(&call)?;
+    message.push(0);
+    stream.write_all(&message)?;
+
+    let mut reply = vec![];
+    BufReader::new(stream).read_until(0, &mut reply)?;
+    if reply.pop() != Some(0) {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    let mut reply: Value = serde_json::from_slice(&reply)?;
+    if let Some(error) = reply.get("error").and_then(Value::as_str) {
+        return Err(io::Error::other(format!("varlink error: {error}")));
+    }
+    Ok(reply["parameters"].take())
+}
+
+fn from_describe_reply(parameters: &Value) -> OsRelease {
+    if let Some(data) = parameters["OperatingSystemReleaseData"].as_array() {
+        return data.iter().filter_map(Value::as_str).collect();
+    }
+
+    let string = |key: &str| parameters[key].as_str().unwrap_or_default();
+    let mut properties = HostnameProperties::new()
+        .pretty_name(string("OperatingSystemPrettyName"))
+        .cpe_name(string("OperatingSystemCPEName"))
+        .home_url(string("OperatingSystemHomeURL"));
+    if let Some(usec) = parameters["OperatingSystemSupportEnd"].as_u64() {
+        properties = properties.support_end_usec(usec);
+    }
+    OsRelease::from_hostname_properties(&properties)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_describe_reply() {
+        let reply = serde_json::json!({
+            "Hostname": "example",
+            "OperatingSystemPrettyName": "Fedora Linux 40 (Workstation Edition)",
+            "OperatingSystemCPEName": "cpe:/o:fedoraproject:fedora:40",
+            "OperatingSystemHomeURL": "",
+            "OperatingSystemSupportEnd": 1_747_094_400_000_000u64,
+        });
+        let os_release = from_describe_reply(&reply);
+        assert_eq!(
+            os_release.pretty_name(),
+            "Fedora Linux 40 (Workstation Edition)"
+        );
+        assert_eq!(
+            os_release.cpe_name(),
+            Some("cpe:/o:fedoraproject:fedora:40")
+        );
+        assert_eq!(os_release.get_value("HOME_URL"), None);
+        assert_eq!(os_release.get_value("SUPPORT_END"), Some("2025-05-13"));
+
+        let reply = serde_json::json!({
+            "OperatingSystemPrettyName": "Fedora Linux 41",
+            "OperatingSystemReleaseData": ["NAME=\"Fedora Linux\"", "ID=fedora", "VERSION_ID=41"],
+        });
+        let os_release = from_describe_reply(&reply);
+        assert_eq!(os_release.name(), "Fedora Linux");
+        assert_eq!(os_release.id(), "fedora");
+        assert_eq!(os_release.version_id(), Some("41"));
+    }
+}
@@ -0,0 +1,329 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::OsRelease;
+
+#[cfg(feature = "deb")]
+pub use self::deb::compare_deb_versions;
+#[cfg(feature = "rpm")]
+pub use self::rpm::compare_rpm_versions;
+
+#[cfg(feature = "deb")]
+mod deb;
+#[cfg(feature = "rpm")]
+mod rpm;
+
+/// Compares two version strings using the algorithm of systemd.
+///
+/// This is the same comparison as `systemd-analyze compare-versions` (`strverscmp_improved()` in systemd),
+/// which is also used to compare `VERSION_ID=`, `SYSEXT_LEVEL=` and similar fields.
+/// Unlike lexical comparison, numeric segments are compared by value, so `9.10` is newer than `9.9`.
+///
+/// * Characters other than ASCII alphanumerics, `~`, `-`, `^` and `.` are ignored as separators.
+/// * Numeric segments are compared numerically, and are newer than alphabetic segments.
+/// * A `~` segment is older than anything, including the end of the string (e.g. `1~rc1` < `1`).
+/// * A `-` segment (separating version and release) is older than other segments, and a `^` segment (a patched release)
+///   is older than `.` segments.
+/// * Otherwise, the string with more segments is newer.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::cmp::Ordering;
+///
+/// use etc_os_release::compare_versions;
+///
+/// assert_eq!(compare_versions("9.10", "9.9"), Ordering::Greater);
+/// assert_eq!(compare_versions("38", "38.1"), Ordering::Less);
+/// assert_eq!(compare_versions("1~rc1", "1"), Ordering::Less);
+/// assert_eq!(compare_versions("00123", "123"), Ordering::Equal);
+/// ```
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.as_bytes(), b.as_bytes());
+    if a.is_empty() || b.is_empty() {
+        return a.cmp(b);
+    }
+
+    loop {
+        // Drop leading invalid characters.
+        a = trim_invalid(a);
+        b = trim_invalid(b);
+
+        // `~` marks pre-releases, which are older than anything else.
+        if a.first() == Some(&b'~') || b.first() == Some(&b'~') {
+            let r = (a.first() != Some(&b'~')).cmp(&(b.first() != Some(&b'~')));
+            if r.is_ne() {
+                return r;
+            }
+            a = &a[1..];
+            b = &b[1..];
+        }
+
+        // If at least one string reaches the end, the longer one is newer.
+        if a.is_empty() || b.is_empty() {
+            return a.cmp(b);
+        }
+
+        // `-` separates the version and the release, `^` marks patched releases and `.` marks point releases.
+        for sep in [b'-', b'^', b'.'] {
+            if a.first() == Some(&sep) || b.first() == Some(&sep) {
+                let r = (a.first() != Some(&sep)).cmp(&(b.first() != Some(&sep)));
+                if r.is_ne() {
+                    return r;
+                }
+                a = &a[1..];
+                b = &b[1..];
+            }
+        }
+
+        let (a_seg, b_seg);
+        if a.first().is_some_and(u8::is_ascii_digit) || b.first().is_some_and(u8::is_ascii_digit) {
+            (a_seg, a) = split_while(a, u8::is_ascii_digit);
+            (b_seg, b) = split_while(b, u8::is_ascii_digit);
+
+            // Numeric segments are newer than alphabetic segments.
+            let r = (!a_seg.is_empty()).cmp(&!b_seg.is_empty());
+            if r.is_ne() {
+                return r;
+            }
+
+            // Skip leading zeros, then the longer and larger number is newer.
+            let a_seg = trim_zeros(a_seg);
+            let b_seg = trim_zeros(b_seg);
+            let r = a_seg.len().cmp(&b_seg.len()).then_with(|| a_seg.cmp(b_seg));
+            if r.is_ne() {
+                return r;
+            }
+        } else {
+            (a_seg, a) = split_while(a, u8::is_ascii_alphabetic);
+            (b_seg, b) = split_while(b, u8::is_ascii_alphabetic);
+
+            // Compare the common prefix, then the longer segment is newer.
+            let len = a_seg.len().min(b_seg.len());
+            let r = a_seg[..len]
+                .cmp(&b_seg[..len])
+                .then_with(|| a_seg.len().cmp(&b_seg.len()));
+            if r.is_ne() {
+                return r;
+            }
+        }
+    }
+}
+
+fn is_valid_version_char(c: &u8) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, b'~' | b'-' | b'^' | b'.')
+}
+
+fn trim_invalid(s: &[u8]) -> &[u8] {
+    let n = s.iter().take_while(|c| !is_valid_version_char(c)).count();
+    &s[n..]
+}
+
+fn trim_zeros(s: &[u8]) -> &[u8] {
+    let n = s.iter().take_while(|&&c| c == b'0').count();
+    &s[n..]
+}
+
+fn split_while(s: &[u8], f: impl Fn(&u8) -> bool) -> (&[u8], &[u8]) {
+    let n = s.iter().take_while(|c| f(c)).count();
+    s.split_at(n)
+}
+
+/// A version interpreted as a relaxed semantic version.
+///
+/// Unlike [Semantic Versioning], the minor and patch versions may be omitted, in which case they are `0`.
+/// This allows versions such as `12` or `22.04` to be used with policies written in terms of semantic versions.
+/// Versions are ordered by major, minor and patch version.
+///
+/// See [`OsRelease::version_id_semver()`].
+///
+/// # Examples
+///
+/// ```rust
+/// use etc_os_release::RelaxedVersion;
+///
+/// let version = RelaxedVersion::parse("22.04").unwrap();
+/// assert_eq!((version.major(), version.minor(), version.patch()), (22, 4, 0));
+/// assert_eq!(version.to_string(), "22.4.0");
+///
+/// assert!(RelaxedVersion::parse("12").unwrap() < version);
+/// assert!(RelaxedVersion::parse("rolling").is_none());
+/// ```
+///
+/// [Semantic Versioning]: https://semver.org/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RelaxedVersion {
+    major: u64,
+    minor: u64,
+    patch: u64,
+}
+
+impl RelaxedVersion {
+    /// Creates a version from its components.
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parses a version consisting of one to three numeric components separated by `.`.
+    ///
+    /// Anything following the numeric components, such as a pre-release suffix (`-rc1`) or a fourth component, is ignored.
+    /// Returns `None` if `s` does not start with a number.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut components = [0; 3];
+        let mut rest = s.trim();
+        for (i, component) in components.iter_mut().enumerate() {
+            if i > 0 {
+                match rest.strip_prefix('.') {
+                    Some(r) if r.starts_with(|c: char| c.is_ascii_digit()) => rest = r,
+                    _ => break,
+                }
+            }
+            let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+            if len == 0 {
+                return None;
+            }
+            *component = rest[..len].parse().ok()?;
+            rest = &rest[len..];
+        }
+        let [major, minor, patch] = components;
+        Some(Self::new(major, minor, patch))
+    }
+
+    /// Returns the major version.
+    pub const fn major(&self) -> u64 {
+        self.major
+    }
+
+    /// Returns the minor version.
+    pub const fn minor(&self) -> u64 {
+        self.minor
+    }
+
+    /// Returns the patch version.
+    pub const fn patch(&self) -> u64 {
+        self.patch
+    }
+}
+
+impl fmt::Display for RelaxedVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Methods to compare the version of the operating system.
+impl OsRelease {
+    /// Compares [`Self::version_id()`] with `version` using [`compare_versions()`].
+    ///
+    /// Returns `None` if `VERSION_ID=` is not set.
+    pub fn compare_version_id(&self, version: &str) -> Option<Ordering> {
+        self.version_id()
+            .map(|version_id| compare_versions(version_id, version))
+    }
+
+    /// Returns `true` if [`Self::version_id()`] is the same as or newer than `version`.
+    ///
+    /// Returns `false` if `VERSION_ID=` is not set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::OsRelease;
+    ///
+    /// let os_release = OsRelease::from_str("ID=rhel\nVERSION_ID=9.10\n").unwrap();
+    /// assert!(os_release.version_at_least("9.9"));
+    /// assert!(os_release.version_at_least("9.10"));
+    /// assert!(!os_release.version_at_least("10"));
+    /// ```
+    pub fn version_at_least(&self, version: &str) -> bool {
+        self.compare_version_id(version)
+            .is_some_and(Ordering::is_ge)
+    }
+
+    /// Returns [`Self::version_id()`] interpreted as a [`RelaxedVersion`].
+    ///
+    /// Returns `None` if `VERSION_ID=` is not set or does not start with a number.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use etc_os_release::{OsRelease, RelaxedVersion};
+    ///
+    /// let os_release = OsRelease::from_str("ID=ubuntu\nVERSION_ID=22.04\n").unwrap();
+    /// let version = os_release.version_id_semver().unwrap();
+    /// assert!(version >= RelaxedVersion::new(20, 4, 0));
+    /// ```
+    pub fn version_id_semver(&self) -> Option<RelaxedVersion> {
+        RelaxedVersion::parse(self.version_id()?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_relaxed_version() {
+        let parse = RelaxedVersion::parse;
+        assert_eq!(parse("12"), Some(RelaxedVersion::new(12, 0, 0)));
+        assert_eq!(parse("22.04"), Some(RelaxedVersion::new(22, 4, 0)));
+        assert_eq!(parse("9.4.1"), Some(RelaxedVersion::new(9, 4, 1)));
+        assert_eq!(parse("1.2.3.4"), Some(RelaxedVersion::new(1, 2, 3)));
+        assert_eq!(parse("3.19.0_alpha"), Some(RelaxedVersion::new(3, 19, 0)));
+        assert_eq!(parse("40-rc1"), Some(RelaxedVersion::new(40, 0, 0)));
+        assert_eq!(parse("15.x"), Some(RelaxedVersion::new(15, 0, 0)));
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("rolling"), None);
+        assert_eq!(parse("v1.0"), None);
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        // in ascending order
+        let versions = [
+            "",
+            "~1",
+            "ab",
+            "abb",
+            "abc",
+            "0001",
+            "002",
+            "12",
+            "122",
+            "122.9",
+            "123~rc1",
+            "123",
+            "123-a",
+            "123-a.1",
+            "123-a1",
+            "123-a1.1",
+            "123-3",
+            "123-3.1",
+            "123^patch1",
+            "123^1",
+            "123.a-1",
+            "123.1-1",
+            "123a-1",
+            "124",
+        ];
+        for (i, a) in versions.iter().enumerate() {
+            for (j, b) in versions.iter().enumerate() {
+                assert_eq!(compare_versions(a, b), i.cmp(&j), "{a:?} vs {b:?}");
+            }
+        }
+
+        assert_eq!(
+            compare_versions("123.45-67.88", "123.45-67.88"),
+            Ordering::Equal
+        );
+        assert_eq!(compare_versions("1+0", "1 0"), Ordering::Equal);
+        assert_eq!(compare_versions("0010", "10"), Ordering::Equal);
+    }
+}
@@ -0,0 +1,124 @@
+use std::cmp::Ordering;
+
+/// Compares two version strings using the algorithm of dpkg.
+///
+/// This is the same comparison as `dpkg --compare-versions`, and is suitable for comparing versions on
+/// Debian-family systems.
+/// A version has the form `[epoch:]upstream_version[-debian_revision]`.
+///
+/// * The epoch is compared numerically. A missing epoch is `0`.
+/// * The upstream version and the revision are compared by alternating non-digit and digit parts.
+///   Non-digit parts are compared per character, where `~` sorts before anything (even the end of the part),
+///   letters sort before other characters, and digit parts are compared numerically.
+/// * A missing revision is equivalent to `0`.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::cmp::Ordering;
+///
+/// use etc_os_release::compare_deb_versions;
+///
+/// assert_eq!(compare_deb_versions("1.0~rc1-1", "1.0-1"), Ordering::Less);
+/// assert_eq!(compare_deb_versions("1:0.9", "2.0"), Ordering::Greater);
+/// assert_eq!(compare_deb_versions("12.4", "12.10"), Ordering::Less);
+/// assert_eq!(compare_deb_versions("1.0", "1.0-0"), Ordering::Equal);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "deb")))]
+pub fn compare_deb_versions(a: &str, b: &str) -> Ordering {
+    let (a_epoch, a_upstream, a_revision) = split_version(a);
+    let (b_epoch, b_upstream, b_revision) = split_version(b);
+    a_epoch
+        .cmp(&b_epoch)
+        .then_with(|| verrevcmp(a_upstream, b_upstream))
+        .then_with(|| verrevcmp(a_revision, b_revision))
+}
+
+/// Split a version into the epoch, the upstream version and the revision.
+fn split_version(version: &str) -> (u64, &[u8], &[u8]) {
+    let version = version.trim();
+    let (epoch, rest) = match version.split_once(':') {
+        Some((epoch, rest)) => (epoch.parse().unwrap_or(0), rest),
+        None => (0, version),
+    };
+    let (upstream, revision) = rest.rsplit_once('-').unwrap_or((rest, ""));
+    (epoch, upstream.as_bytes(), revision.as_bytes())
+}
+
+/// The sort weight of a character in a non-digit part.
+fn order(c: Option<u8>) -> i32 {
+    match c {
+        None => 0,
+        Some(c) if c.is_ascii_digit() => 0,
+        Some(c) if c.is_ascii_alphabetic() => i32::from(c),
+        Some(b'~') => -1,
+        Some(c) => i32::from(c) + 256,
+    }
+}
+
+/// Compare upstream versions or revisions.
+fn verrevcmp(mut a: &[u8], mut b: &[u8]) -> Ordering {
+    let is_digit = |s: &[u8]| s.first().is_some_and(u8::is_ascii_digit);
+
+    while !a.is_empty() || !b.is_empty() {
+        while (!a.is_empty() && !is_digit(a)) || (!b.is_empty() && !is_digit(b)) {
+            let r = order(a.first().copied()).cmp(&order(b.first().copied()));
+            if r.is_ne() {
+                return r;
+            }
+            a = a.get(1..).unwrap_or_default();
+            b = b.get(1..).unwrap_or_default();
+        }
+
+        let a_len = a.iter().take_while(|c| c.is_ascii_digit()).count();
+        let b_len = b.iter().take_while(|c| c.is_ascii_digit()).count();
+        let (a_num, b_num);
+        (a_num, a) = a.split_at(a_len);
+        (b_num, b) = b.split_at(b_len);
+
+        let trim = |s: &[u8]| -> Vec<u8> { s.iter().copied().skip_while(|&c| c == b'0').collect() };
+        let (a_num, b_num) = (trim(a_num), trim(b_num));
+        let r = a_num
+            .len()
+            .cmp(&b_num.len())
+            .then_with(|| a_num.cmp(&b_num));
+        if r.is_ne() {
+            return r;
+        }
+    }
+    Ordering::Equal
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_compare_deb_versions() {
+        // in ascending order
+        let versions = [
+            "0~",
+            "0",
+            "0.9~beta1",
+            "0.9",
+            "0.9-1~bpo1",
+            "0.9-1",
+            "0.9-1.1",
+            "0.9a",
+            "0.9+dfsg",
+            "0.10",
+            "1.0",
+            "1:0.1",
+            "2:0",
+        ];
+        for (i, a) in versions.iter().enumerate() {
+            for (j, b) in versions.iter().enumerate() {
+                assert_eq!(compare_deb_versions(a, b), i.cmp(&j), "{a:?} vs {b:?}");
+            }
+        }
+
+        assert_eq!(compare_deb_versions("0:1.0", "1.0"), Ordering::Equal);
+        assert_eq!(compare_deb_versions("1.01", "1.1"), Ordering::Equal);
+        assert_eq!(compare_deb_versions("1.0-0", "1.0"), Ordering::Equal);
+    }
+}
@@ -0,0 +1,169 @@
+use std::cmp::Ordering;
+
+/// Compares two version strings using the algorithm of rpm.
+///
+/// This is the same comparison as `rpmdev-vercmp`, and is suitable for comparing versions on RHEL-family systems.
+/// A version has the form `[epoch:]version[-release]`.
+///
+/// * The epoch is compared numerically. A missing epoch is `0`.
+/// * The version and the release are compared by `rpmvercmp()`, segment by segment.
+///   Segments are maximal runs of digits or letters, and other characters are ignored as separators.
+///   Numeric segments are compared numerically and are newer than alphabetic segments.
+///   A `~` is older than anything, including the end of the string, and a `^` is newer than the end of the string
+///   but older than anything else.
+/// * A missing release is older than any release.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::cmp::Ordering;
+///
+/// use etc_os_release::compare_rpm_versions;
+///
+/// assert_eq!(compare_rpm_versions("9.4", "9.10"), Ordering::Less);
+/// assert_eq!(compare_rpm_versions("1.0~rc1", "1.0"), Ordering::Less);
+/// assert_eq!(compare_rpm_versions("1.0^git1", "1.0"), Ordering::Greater);
+/// assert_eq!(compare_rpm_versions("1:1.0-1.el8", "2.0-1.el9"), Ordering::Greater);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "rpm")))]
+pub fn compare_rpm_versions(a: &str, b: &str) -> Ordering {
+    let (a_epoch, a_version, a_release) = split_evr(a);
+    let (b_epoch, b_version, b_release) = split_evr(b);
+    a_epoch
+        .cmp(&b_epoch)
+        .then_with(|| rpmvercmp(a_version, b_version))
+        .then_with(|| match (a_release, b_release) {
+            (Some(a), Some(b)) => rpmvercmp(a, b),
+            (a, b) => a.is_some().cmp(&b.is_some()),
+        })
+}
+
+/// Split a version into the epoch, the version and the release.
+fn split_evr(evr: &str) -> (u64, &[u8], Option<&[u8]>) {
+    let evr = evr.trim();
+    let (epoch, rest) = match evr.split_once(':') {
+        Some((epoch, rest)) => (epoch.parse().unwrap_or(0), rest),
+        None => (0, evr),
+    };
+    match rest.rsplit_once('-') {
+        Some((version, release)) => (epoch, version.as_bytes(), Some(release.as_bytes())),
+        None => (epoch, rest.as_bytes(), None),
+    }
+}
+
+/// Compare versions or releases.
+fn rpmvercmp(mut a: &[u8], mut b: &[u8]) -> Ordering {
+    if a == b {
+        return Ordering::Equal;
+    }
+
+    let is_separator = |c: &u8| !c.is_ascii_alphanumeric() && *c != b'~' && *c != b'^';
+    while !a.is_empty() || !b.is_empty() {
+        a = &a[a.iter().take_while(|c| is_separator(c)).count()..];
+        b = &b[b.iter().take_while(|c| is_separator(c)).count()..];
+
+        // `~` is older than anything
+        if a.first() == Some(&b'~') || b.first() == Some(&b'~') {
+            if a.first() != Some(&b'~') {
+                return Ordering::Greater;
+            }
+            if b.first() != Some(&b'~') {
+                return Ordering::Less;
+            }
+            (a, b) = (&a[1..], &b[1..]);
+            continue;
+        }
+
+        // `^` is newer than the end, but older than anything else
+        if a.first() == Some(&b'^') || b.first() == Some(&b'^') {
+            if a.is_empty() {
+                return Ordering::Less;
+            }
+            if b.is_empty() {
+                return Ordering::Greater;
+            }
+            if a.first() != Some(&b'^') {
+                return Ordering::Greater;
+            }
+            if b.first() != Some(&b'^') {
+                return Ordering::Less;
+            }
+            (a, b) = (&a[1..], &b[1..]);
+            continue;
+        }
+
+        if a.is_empty() || b.is_empty() {
+            break;
+        }
+
+        let is_num = a[0].is_ascii_digit();
+        let in_segment = |c: &&u8| {
+            if is_num {
+                c.is_ascii_digit()
+            } else {
+                c.is_ascii_alphabetic()
+            }
+        };
+        let b_len = b.iter().take_while(in_segment).count();
+        if b_len == 0 {
+            // numeric segments are newer than alphabetic ones
+            return if is_num {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            };
+        }
+        let a_len = a.iter().take_while(in_segment).count();
+        let (mut a_seg, mut b_seg);
+        (a_seg, a) = a.split_at(a_len);
+        (b_seg, b) = b.split_at(b_len);
+
+        let r = if is_num {
+            a_seg = &a_seg[a_seg.iter().take_while(|&&c| c == b'0').count()..];
+            b_seg = &b_seg[b_seg.iter().take_while(|&&c| c == b'0').count()..];
+            a_seg.len().cmp(&b_seg.len()).then_with(|| a_seg.cmp(b_seg))
+        } else {
+            a_seg.cmp(b_seg)
+        };
+        if r.is_ne() {
+            return r;
+        }
+    }
+
+    a.is_empty().cmp(&b.is_empty()).reverse()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_compare_rpm_versions() {
+        // in ascending order
+        let versions = [
+            "0~rc1",
+            "0",
+            "0^git1",
+            "0a",
+            "0.1",
+            "1.0~rc1",
+            "1.0",
+            "1.0-1",
+            "1.0-1.el8",
+            "1.0-2",
+            "1.0a",
+            "1.0.1",
+            "1.10",
+            "1:0.1",
+        ];
+        for (i, a) in versions.iter().enumerate() {
+            for (j, b) in versions.iter().enumerate() {
+                assert_eq!(compare_rpm_versions(a, b), i.cmp(&j), "{a:?} vs {b:?}");
+            }
+        }
+
+        assert_eq!(compare_rpm_versions("0:1.0", "1.0"), Ordering::Equal);
+        assert_eq!(compare_rpm_versions("1.01", "1.1"), Ordering::Equal);
+        assert_eq!(compare_rpm_versions("1_0", "1.0"), Ordering::Equal);
+    }
+}
@@ -0,0 +1,252 @@
+use std::{
+    ffi::CString,
+    fs::File,
+    io::{self, Read},
+    os::{
+        fd::{AsRawFd, FromRawFd, OwnedFd},
+        unix::ffi::OsStrExt,
+    },
+    path::{Path, PathBuf},
+};
+
+use crate::{Error, OsRelease};
+
+/// Directories containing the os-release files, relative to the root.
+const WATCHED_DIRS: [&str; 2] = ["etc", "usr/lib"];
+
+/// A watcher which yields the os-release file whenever it changes.
+///
+/// The directories containing `/etc/os-release` and `/usr/lib/os-release` are watched with [`inotify(7)`],
+/// so that changes are noticed even if the file is replaced by renaming another file over it.
+/// After a change, the os-release file is opened as in [`OsRelease::open_root()`],
+/// and yielded only if its fields differ from the previously yielded value.
+/// While neither file exists, nothing is yielded.
+///
+/// Replacing the whole `/usr` mount, as some image-based systems do, is not detected.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use etc_os_release::OsReleaseWatcher;
+///
+/// // Blocks until the os-release file changes.
+/// for os_release in OsReleaseWatcher::new()? {
+///     println!("updated to {}", os_release?.pretty_name());
+/// }
+/// # Ok::<(), etc_os_release::Error>(())
+/// ```
+///
+/// [`inotify(7)`]: https://man7.org/linux/man-pages/man7/inotify.7.html
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(all(target_os = "linux", feature = "watch"))))]
+pub struct OsReleaseWatcher {
+    inotify: File,
+    root: PathBuf,
+    last: Option<OsRelease>,
+}
+
+impl OsReleaseWatcher {
+    /// Starts watching the os-release file of the running system.
+    pub fn new() -> Result<Self, Error> {
+        Self::with_root("/")
+    }
+
+    /// Starts watching the os-release file of the operating system tree at `root`.
+    ///
+    /// Returns [`Error::Watch`] if none of `<root>/etc` and `<root>/usr/lib` can be watched.
+    pub fn with_root(root: impl AsRef<Path>) -> Result<Self, Error> {
+        let root = root.as_ref().to_owned();
+        let inotify = sys::init().map_err(|err| Error::Watch { err })?;
+
+        let mut last_err = None;
+        let mut watched = false;
+        for dir in WATCHED_DIRS {
+            match sys::add_watch(&inotify, &root.join(dir)) {
+                Ok(()) => watched = true,
+                Err(err) => last_err = Some(err),
+            }
+        }
+        if !watched {
+            let err = last_err.unwrap_or_else(|| io::ErrorKind::NotFound.into());
+            return Err(Error::Watch { err });
+        }
+
+        let last = OsRelease::open_root(&root).ok();
+        Ok(Self {
+            inotify,
+            root,
+            last,
+        })
+    }
+
+    /// Returns the most recently yielded os-release file,
+    /// or the one read when the watcher was created if nothing has been yielded yet.
+    pub fn latest(&self) -> Option<&OsRelease> {
+        self.last.as_ref()
+    }
+
+    /// Blocks until the os-release file changes, and returns the new value.
+    pub fn wait(&mut self) -> Result<OsRelease, Error> {
+        loop {
+            sys::wait_os_release_event(&mut self.inotify).map_err(|err| Error::Watch { err })?;
+            let os_release = match OsRelease::open_root(&self.root) {
+                Ok(os_release) => os_release,
+                Err(Error::NoOsRelease) => continue,
+                Err(e) => return Err(e),
+            };
+            if self.last.as_ref().is_some_and(|last| *last == os_release) {
+                continue;
+            }
+            self.last = Some(os_release.clone());
+            return Ok(os_release);
+        }
+    }
+}
+
+impl Iterator for OsReleaseWatcher {
+    type Item = Result<OsRelease, Error>;
+
+    /// Blocks until the os-release file changes, as in [`OsReleaseWatcher::wait()`].
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.wait())
+    }
+}
+
+mod sys {
+    use super::*;
+
+    const NAME: &[u8] = b"os-release";
+    const EVENT_HEADER_LEN: usize = 16;
+
+    pub(super) fn init() -> io::Result<File> {
+        // SAFETY: `inotify_init1()` has no preconditions.
+        let fd = unsafe { libc::inotify_init1(libc::IN_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: `fd` is a newly created file descriptor owned by nobody else.
+        Ok(File::from(unsafe { OwnedFd::from_raw_fd(fd) }))
+    }
+
+    pub(super) fn add_watch(inotify: &File, dir: &Path) -> io::Result<()> {
+        let dir = CString::new(dir.as_os_str().as_bytes())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let mask = libc::IN_CLOSE_WRITE
+            | libc::IN_CREATE
+            | libc::IN_DELETE
+            | libc::IN_MOVED_FROM
+            | libc::IN_MOVED_TO
+            | libc::IN_ONLYDIR;
+        // SAFETY: `dir` is a NUL-terminated string.
+        let wd = unsafe { libc::inotify_add_watch(inotify.as_raw_fd(), dir.as_ptr(), mask) };
+        if wd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Blocks until an event on a file named `os-release` is read.
+    pub(super) fn wait_os_release_event(inotify: &mut File) -> io::Result<()> {
+        let mut buf = [0; 4096];
+        loop {
+            let len = inotify.read(&mut buf)?;
+            if has_os_release_event(&buf[..len]) {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Returns `true` if the buffer of `struct inotify_event`s contains an event on `os-release`,
+    /// or if the event queue overflowed.
+    pub(super) fn has_os_release_event(mut buf: &[u8]) -> bool {
+        while buf.len() >= EVENT_HEADER_LEN {
+            let field = |i: usize| u32::from_ne_bytes(buf[i..i + 4].try_into().unwrap());
+            let mask = field(4);
+            let len = field(12) as usize;
+            let Some(name) = buf.get(EVENT_HEADER_LEN..EVENT_HEADER_LEN + len) else {
+                break;
+            };
+            let name = name.split(|&b| b == 0).next().unwrap_or_default();
+            if mask & libc::IN_Q_OVERFLOW != 0 || name == NAME {
+                return true;
+            }
+            buf = &buf[EVENT_HEADER_LEN + len..];
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env, fs};
+
+    use super::*;
+
+    #[test]
+    fn test_watcher() {
+        let root = env::temp_dir().join(format!("etc-os-release-watch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("etc")).unwrap();
+        fs::write(root.join("etc/os-release"), "ID=fedora\nVERSION_ID=40\n").unwrap();
+
+        let mut watcher = OsReleaseWatcher::with_root(&root).unwrap();
+        assert_eq!(watcher.latest().unwrap().version_id(), Some("40"));
+
+        // unrelated files and unchanged contents are ignored
+        fs::write(root.join("etc/hostname"), "localhost\n").unwrap();
+        fs::write(root.join("etc/os-release"), "ID=fedora\nVERSION_ID=40\n").unwrap();
+        // replaced by renaming
+        fs::write(
+            root.join("etc/.os-release.tmp"),
+            "ID=fedora\nVERSION_ID=41\n",
+        )
+        .unwrap();
+        fs::rename(
+            root.join("etc/.os-release.tmp"),
+            root.join("etc/os-release"),
+        )
+        .unwrap();
+        let os_release = watcher.next().unwrap().unwrap();
+        assert_eq!(os_release.version_id(), Some("41"));
+        assert_eq!(watcher.latest().unwrap().version_id(), Some("41"));
+
+        assert!(matches!(
+            OsReleaseWatcher::with_root(root.join("nonexistent")),
+            Err(Error::Watch { .. })
+        ));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_has_os_release_event() {
+        let event = |mask: u32, name: &[u8]| {
+            let len = name.len().next_multiple_of(16);
+            let mut buf = vec![];
+            buf.extend(1_i32.to_ne_bytes());
+            buf.extend(mask.to_ne_bytes());
+            buf.extend(0_u32.to_ne_bytes());
+            buf.extend((len as u32).to_ne_bytes());
+            buf.extend(name);
+            buf.resize(16 + len, 0);
+            buf
+        };
+        assert!(!sys::has_os_release_event(&[]));
+        assert!(!sys::has_os_release_event(&event(
+            libc::IN_CREATE,
+            b"hostname"
+        )));
+        assert!(!sys::has_os_release_event(&event(
+            libc::IN_CREATE,
+            b"os-release.tmp"
+        )));
+        assert!(sys::has_os_release_event(
+            &[
+                event(libc::IN_CREATE, b"hostname"),
+                event(libc::IN_MOVED_TO, b"os-release")
+            ]
+            .concat()
+        ));
+        assert!(sys::has_os_release_event(&event(libc::IN_Q_OVERFLOW, b"")));
+    }
+}